@@ -0,0 +1,194 @@
+//! Reading and writing the regions of a seekable stream addressed by a [`Range`].
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::vec::Vec;
+
+use crate::range::Range;
+
+/// Reads the bytes in `range` from `r`, seeking to its start first.
+///
+/// A zero-length range reads nothing and succeeds, even past the end of the
+/// input.
+///
+/// # Errors
+/// Returns [`io::ErrorKind::InvalidInput`] if `range` is inverted, and
+/// [`io::ErrorKind::UnexpectedEof`] if the input ends before `range` does. Other
+/// errors from seeking or reading are passed through.
+///
+/// # Example
+/// ```
+/// use ars::io::read_range;
+/// use ars::range::Range;
+/// use std::io::Cursor;
+///
+/// let mut file = Cursor::new(b"header:payload");
+/// assert_eq!(read_range(&mut file, Range(7, 14))?, b"payload");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn read_range<R: Read + Seek>(r: &mut R, range: Range) -> io::Result<Vec<u8>> {
+    check_not_inverted(range)?;
+    r.seek(SeekFrom::Start(range.start() as u64))?;
+    // Read through `take` rather than into a zeroed buffer, so a bogus range past
+    // the end doesn't allocate its whole length up front.
+    let mut buf = Vec::new();
+    r.take(range.len() as u64).read_to_end(&mut buf)?;
+    if buf.len() < range.len() {
+        return Err(past_eof(range));
+    }
+    Ok(buf)
+}
+
+/// Reads the bytes in `range` from `r` into `buf`, seeking to its start first.
+///
+/// # Errors
+/// Returns [`io::ErrorKind::InvalidInput`] if `range` is inverted or
+/// `buf.len() != range.len()`, and [`io::ErrorKind::UnexpectedEof`] if the input
+/// ends before `range` does, in which case the contents of `buf` are unspecified.
+/// Other errors from seeking or reading are passed through.
+pub fn read_range_into<R: Read + Seek>(r: &mut R, range: Range, buf: &mut [u8]) -> io::Result<()> {
+    check_not_inverted(range)?;
+    check_len(range, buf.len())?;
+    r.seek(SeekFrom::Start(range.start() as u64))?;
+    r.read_exact(buf).map_err(|e| match e.kind() {
+        io::ErrorKind::UnexpectedEof => past_eof(range),
+        _ => e,
+    })
+}
+
+/// Writes `data` over the bytes in `range` of `w`, seeking to its start first.
+///
+/// Whether writing past the current end extends the output is up to `w`.
+///
+/// # Errors
+/// Returns [`io::ErrorKind::InvalidInput`] if `range` is inverted or
+/// `data.len() != range.len()`. Errors from seeking or writing are passed
+/// through.
+///
+/// # Example
+/// ```
+/// use ars::io::write_range;
+/// use ars::range::Range;
+/// use std::io::Cursor;
+///
+/// let mut file = Cursor::new(b"header:payload".to_vec());
+/// write_range(&mut file, Range(0, 6), b"HEADER")?;
+/// assert_eq!(file.get_ref(), b"HEADER:payload");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_range<W: Write + Seek>(w: &mut W, range: Range, data: &[u8]) -> io::Result<()> {
+    check_not_inverted(range)?;
+    check_len(range, data.len())?;
+    w.seek(SeekFrom::Start(range.start() as u64))?;
+    w.write_all(data)
+}
+
+fn check_not_inverted(range: Range) -> io::Result<()> {
+    if range.start() > range.end() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            std::format!(
+                "range start {} is greater than end {}",
+                range.start(),
+                range.end()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn check_len(range: Range, len: usize) -> io::Result<()> {
+    if len != range.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            std::format!("buffer of length {len} does not match range {range}"),
+        ));
+    }
+    Ok(())
+}
+
+fn past_eof(range: Range) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        std::format!("range {range} extends past the end of the input"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const DATA: &[u8] = b"0123456789";
+
+    #[test]
+    fn reads_ranges() {
+        let mut c = Cursor::new(DATA);
+        assert_eq!(read_range(&mut c, Range(2, 5)).unwrap(), b"234");
+        assert_eq!(read_range(&mut c, Range(0, 10)).unwrap(), DATA);
+        // Reads seek from the start, regardless of the current position.
+        assert_eq!(read_range(&mut c, Range(8, 10)).unwrap(), b"89");
+
+        let mut buf = [0; 3];
+        read_range_into(&mut c, Range(7, 10), &mut buf).unwrap();
+        assert_eq!(&buf, b"789");
+    }
+
+    #[test]
+    fn zero_length_ranges_read_nothing() {
+        let mut c = Cursor::new(DATA);
+        assert!(read_range(&mut c, Range(4, 4)).unwrap().is_empty());
+        assert!(read_range(&mut c, Range(10, 10)).unwrap().is_empty());
+        assert!(read_range(&mut c, Range(50, 50)).unwrap().is_empty());
+        read_range_into(&mut c, Range(50, 50), &mut []).unwrap();
+    }
+
+    #[test]
+    fn short_reads_are_unexpected_eof() {
+        let mut c = Cursor::new(DATA);
+        for r in [
+            Range(8, 11),
+            Range(10, 11),
+            Range(20, 30),
+            Range(0, usize::MAX),
+        ] {
+            let err = read_range(&mut c, r).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof, "{r}");
+        }
+        let mut buf = [0; 4];
+        let err = read_range_into(&mut c, Range(8, 12), &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let mut c = Cursor::new(DATA.to_vec());
+        let kind = |r: io::Result<()>| r.unwrap_err().kind();
+        let inverted = read_range(&mut c, Range(5, 2)).unwrap_err();
+        assert_eq!(inverted.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            kind(read_range_into(&mut c, Range(0, 3), &mut [0; 2])),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(write_range(&mut c, Range(0, 3), b"ab")),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(write_range(&mut c, Range(3, 0), b"")),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(c.get_ref(), DATA);
+    }
+
+    #[test]
+    fn writes_ranges() {
+        let mut c = Cursor::new(DATA.to_vec());
+        write_range(&mut c, Range(2, 4), b"ab").unwrap();
+        write_range(&mut c, Range(5, 5), b"").unwrap();
+        assert_eq!(c.get_ref(), b"01ab456789");
+        // A cursor over a `Vec` grows, zero-filling any gap.
+        write_range(&mut c, Range(12, 14), b"yz").unwrap();
+        assert_eq!(c.get_ref(), b"01ab456789\0\0yz");
+        assert_eq!(read_range(&mut c, Range(2, 4)).unwrap(), b"ab");
+    }
+}
@@ -4,6 +4,15 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod fmt;
+#[cfg(feature = "io")]
+pub mod io;
+pub mod prelude;
 pub mod range;
+pub mod slice;
+pub mod str;
+#[cfg(feature = "alloc")]
+pub mod vec;
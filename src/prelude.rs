@@ -0,0 +1,10 @@
+//! Convenience re-exports of the crate's extension traits.
+//!
+//! ```
+//! use ars::prelude::*;
+//! ```
+
+pub use crate::slice::SliceExt;
+pub use crate::str::StrExt;
+#[cfg(feature = "alloc")]
+pub use crate::vec::VecExt;
@@ -0,0 +1,146 @@
+//! Splitting a [`Range`] around aligned blocks, for direct I/O.
+
+use super::Range;
+
+/// The pieces of a range split at an alignment, returned by
+/// [`Range::decompose_aligned`].
+///
+/// The non-`None` pieces tile the original range in order: `head`, then `body`,
+/// then `tail`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AlignedParts {
+    /// The unaligned part before the first whole block.
+    pub head: Option<Range>,
+    /// The whole blocks; both bounds are multiples of the alignment.
+    pub body: Option<Range>,
+    /// The unaligned part after the last whole block.
+    pub tail: Option<Range>,
+}
+
+impl Range {
+    /// Splits the range into an unaligned head, a body of whole `align`-sized
+    /// blocks, and an unaligned tail.
+    ///
+    /// Empty pieces are `None`. A range containing no whole block is returned as
+    /// head only, and an empty or inverted range yields no pieces at all.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{AlignedParts, Range};
+    /// assert_eq!(
+    ///     Range(100, 9000).decompose_aligned(4096),
+    ///     AlignedParts {
+    ///         head: Some(Range(100, 4096)),
+    ///         body: Some(Range(4096, 8192)),
+    ///         tail: Some(Range(8192, 9000)),
+    ///     }
+    /// );
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn decompose_aligned(&self, align: usize) -> AlignedParts {
+        let body = self.align_inward(align);
+        if self.is_empty() {
+            return AlignedParts {
+                head: None,
+                body: None,
+                tail: None,
+            };
+        }
+        if body.is_empty() {
+            return AlignedParts {
+                head: Some(*self),
+                body: None,
+                tail: None,
+            };
+        }
+        let head = Self(self.0, body.0);
+        let tail = Self(body.1, self.1);
+        AlignedParts {
+            head: if head.is_empty() { None } else { Some(head) },
+            body: Some(body),
+            tail: if tail.is_empty() { None } else { Some(tail) },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(r: Range, align: usize) -> [Option<Range>; 3] {
+        let p = r.decompose_aligned(align);
+        [p.head, p.body, p.tail]
+    }
+
+    #[test]
+    fn across_block_boundaries() {
+        assert_eq!(
+            parts(Range(3, 21), 8),
+            [Some(Range(3, 8)), Some(Range(8, 16)), Some(Range(16, 21))]
+        );
+        assert_eq!(
+            parts(Range(3, 16), 8),
+            [Some(Range(3, 8)), Some(Range(8, 16)), None]
+        );
+        assert_eq!(
+            parts(Range(8, 21), 8),
+            [None, Some(Range(8, 16)), Some(Range(16, 21))]
+        );
+    }
+
+    #[test]
+    fn exactly_aligned_input() {
+        assert_eq!(parts(Range(8, 32), 8), [None, Some(Range(8, 32)), None]);
+        assert_eq!(parts(Range(0, 8), 8), [None, Some(Range(0, 8)), None]);
+        assert_eq!(parts(Range(5, 9), 1), [None, Some(Range(5, 9)), None]);
+    }
+
+    #[test]
+    fn tiny_ranges_are_head_only() {
+        assert_eq!(parts(Range(3, 5), 8), [Some(Range(3, 5)), None, None]);
+        assert_eq!(parts(Range(6, 12), 8), [Some(Range(6, 12)), None, None]);
+        assert_eq!(parts(Range(8, 9), 8), [Some(Range(8, 9)), None, None]);
+        assert_eq!(parts(Range(4, 4), 8), [None, None, None]);
+        assert_eq!(parts(Range(8, 8), 8), [None, None, None]);
+        assert_eq!(parts(Range(9, 2), 8), [None, None, None]);
+    }
+
+    #[test]
+    fn pieces_tile_the_range() {
+        for align in [1, 2, 4, 8] {
+            for start in 0..30 {
+                for end in start..40 {
+                    let r = Range(start, end);
+                    let mut cursor = start;
+                    for piece in parts(r, align).into_iter().flatten() {
+                        assert_eq!(piece.start(), cursor, "{r:?} align {align}");
+                        assert!(!piece.is_empty());
+                        cursor = piece.end();
+                    }
+                    assert_eq!(cursor, end, "{r:?} align {align}");
+                    if let Some(body) = r.decompose_aligned(align).body {
+                        assert!(body.start().is_multiple_of(align));
+                        assert!(body.end().is_multiple_of(align));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "`align` must be a power of two"]
+    fn non_power_of_two_panics() {
+        let _ = Range(0, 10).decompose_aligned(6);
+    }
+
+    #[test]
+    fn const_decompose() {
+        const P: AlignedParts = Range(1, 10).decompose_aligned(4);
+        assert_eq!(P.body, Some(Range(4, 8)));
+    }
+}
@@ -0,0 +1,308 @@
+//! [`RangeAllocator`], a first-fit suballocator handing out [`Range`]s.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::{FreeError, Range};
+
+/// A first-fit allocator carving sub-ranges out of one total [`Range`], for
+/// suballocating regions of a large buffer.
+///
+/// Free space is tracked as a sorted list of non-overlapping, non-adjacent
+/// ranges. The allocator does not remember individual allocations: any range
+/// that is fully allocated can be freed, including part of an earlier
+/// allocation.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, RangeAllocator};
+///
+/// let mut heap = RangeAllocator::new(Range(0, 1024));
+/// let a = heap.allocate(100).unwrap();
+/// let b = heap.allocate_aligned(100, 256).unwrap();
+/// assert_eq!((a, b), (Range(0, 100), Range(256, 356)));
+/// heap.free(a).unwrap();
+/// assert_eq!(heap.free_ranges(), [Range(0, 256), Range(356, 1024)]);
+/// assert!(heap.free(a).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeAllocator {
+    bounds: Range,
+    free: Vec<Range>,
+}
+
+impl RangeAllocator {
+    /// Creates an allocator where all of `bounds` is free.
+    ///
+    /// Empty or inverted bounds yield an allocator with nothing to hand out.
+    #[must_use]
+    pub fn new(bounds: Range) -> Self {
+        let free = if bounds.is_empty() {
+            Vec::new()
+        } else {
+            vec![bounds]
+        };
+        Self { bounds, free }
+    }
+
+    /// Returns the range the allocator manages.
+    #[must_use]
+    pub const fn bounds(&self) -> Range {
+        self.bounds
+    }
+
+    /// Returns the free blocks, sorted and with no two blocks touching.
+    #[must_use]
+    pub fn free_ranges(&self) -> &[Range] {
+        &self.free
+    }
+
+    /// Returns the total number of free indices.
+    #[must_use]
+    pub fn free_len(&self) -> usize {
+        self.free.iter().map(Range::len).sum()
+    }
+
+    /// Returns the largest free block, the first one if several tie, or `None` if
+    /// nothing is free.
+    #[must_use]
+    pub fn largest_free_block(&self) -> Option<Range> {
+        self.free
+            .iter()
+            .copied()
+            .reduce(|best, r| if r.len() > best.len() { r } else { best })
+    }
+
+    /// Allocates `len` indices from the first free block large enough.
+    ///
+    /// Returns `None` if `len` is 0 or no free block fits.
+    pub fn allocate(&mut self, len: usize) -> Option<Range> {
+        self.allocate_aligned(len, 1)
+    }
+
+    /// Allocates `len` indices starting at a multiple of `align`, from the first
+    /// free block with room for it.
+    ///
+    /// Alignment is relative to index 0, not to the start of the bounds. The
+    /// padding skipped to align the start stays free. Returns `None` if `len` is
+    /// 0 or no free block fits.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    #[track_caller]
+    pub fn allocate_aligned(&mut self, len: usize, align: usize) -> Option<Range> {
+        assert!(align.is_power_of_two(), "`align` must be a power of two");
+        if len == 0 {
+            return None;
+        }
+        let mask = align - 1;
+        let (i, block, taken) = self.free.iter().enumerate().find_map(|(i, &block)| {
+            let start = block.start().checked_add(mask)? & !mask;
+            let taken = Range::checked_from_start_len(start, len)?;
+            (taken.end() <= block.end()).then_some((i, block, taken))
+        })?;
+
+        // Replace the block by the free space left on either side of `taken`.
+        let before = Range(block.start(), taken.start());
+        let after = Range(taken.end(), block.end());
+        match (before.is_empty(), after.is_empty()) {
+            (true, true) => {
+                self.free.remove(i);
+            }
+            (false, true) => self.free[i] = before,
+            (true, false) => self.free[i] = after,
+            (false, false) => {
+                self.free[i] = before;
+                self.free.insert(i + 1, after);
+            }
+        }
+        Some(taken)
+    }
+
+    /// Returns `range` to the free list, merging it with adjacent free blocks.
+    ///
+    /// Freeing an empty range does nothing.
+    ///
+    /// # Errors
+    /// Fails without changing the free list if `range` is inverted, lies outside
+    /// the bounds, or overlaps free space (for example on a double free).
+    pub fn free(&mut self, range: Range) -> Result<(), FreeError> {
+        if !range.is_valid() {
+            return Err(FreeError::Inverted(range));
+        }
+        if range.is_empty() {
+            return Ok(());
+        }
+        if !self.bounds.contains_range(&range) {
+            return Err(FreeError::OutOfBounds {
+                range,
+                bounds: self.bounds,
+            });
+        }
+
+        // The first free block ending after `range` starts is the only one that
+        // can overlap it or follow it directly.
+        let i = self.free.partition_point(|f| f.end() <= range.start());
+        if self
+            .free
+            .get(i)
+            .is_some_and(|next| next.start() < range.end())
+        {
+            return Err(FreeError::NotAllocated(range));
+        }
+
+        let merge_prev = i > 0 && self.free[i - 1].end() == range.start();
+        let merge_next = self
+            .free
+            .get(i)
+            .is_some_and(|next| next.start() == range.end());
+        match (merge_prev, merge_next) {
+            (true, true) => {
+                self.free[i - 1].1 = self.free[i].end();
+                self.free.remove(i);
+            }
+            (true, false) => self.free[i - 1].1 = range.end(),
+            (false, true) => self.free[i].0 = range.start(),
+            (false, false) => self.free.insert(i, range),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    fn assert_invariants(heap: &RangeAllocator) {
+        let free = heap.free_ranges();
+        for r in free {
+            assert!(!r.is_empty(), "empty free block in {free:?}");
+            assert!(heap.bounds().contains_range(r), "{r:?} outside bounds");
+        }
+        for w in free.windows(2) {
+            assert!(
+                w[0].end() < w[1].start(),
+                "unsorted or uncoalesced: {free:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn first_fit_and_exhaustion() {
+        let mut heap = RangeAllocator::new(Range(10, 20));
+        assert_eq!(heap.allocate(4), Some(Range(10, 14)));
+        assert_eq!(heap.allocate(4), Some(Range(14, 18)));
+        assert_eq!(heap.allocate(4), None);
+        assert_eq!(heap.allocate(2), Some(Range(18, 20)));
+        assert!(heap.free_ranges().is_empty());
+        assert_eq!(heap.largest_free_block(), None);
+        assert_eq!(heap.allocate(0), None);
+    }
+
+    #[test]
+    fn first_fit_reuses_earlier_holes() {
+        let mut heap = RangeAllocator::new(Range(0, 100));
+        let a = heap.allocate(10).unwrap();
+        let _b = heap.allocate(10).unwrap();
+        heap.free(a).unwrap();
+        assert_eq!(heap.allocate(5), Some(Range(0, 5)));
+        assert_eq!(heap.allocate(10), Some(Range(20, 30)));
+        assert_eq!(heap.largest_free_block(), Some(Range(30, 100)));
+    }
+
+    #[test]
+    fn aligned_allocation_keeps_padding_free() {
+        let mut heap = RangeAllocator::new(Range(3, 64));
+        assert_eq!(heap.allocate_aligned(8, 16), Some(Range(16, 24)));
+        assert_eq!(heap.free_ranges(), [Range(3, 16), Range(24, 64)]);
+        assert_eq!(heap.allocate_aligned(8, 8), Some(Range(8, 16)));
+        assert_eq!(heap.free_ranges(), [Range(3, 8), Range(24, 64)]);
+        assert_eq!(heap.allocate_aligned(64, 64), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn aligned_allocation_rejects_bad_alignment() {
+        let _ = RangeAllocator::new(Range(0, 8)).allocate_aligned(1, 3);
+    }
+
+    #[test]
+    fn free_coalesces_both_sides() {
+        let mut heap = RangeAllocator::new(Range(0, 30));
+        let a = heap.allocate(10).unwrap();
+        let b = heap.allocate(10).unwrap();
+        let c = heap.allocate(10).unwrap();
+        heap.free(a).unwrap();
+        heap.free(c).unwrap();
+        assert_eq!(heap.free_ranges(), [a, c]);
+        heap.free(b).unwrap();
+        assert_eq!(heap.free_ranges(), [Range(0, 30)]);
+    }
+
+    #[test]
+    fn free_errors_leave_state_untouched() {
+        let mut heap = RangeAllocator::new(Range(0, 16));
+        let a = heap.allocate(8).unwrap();
+        heap.free(a).unwrap();
+        let before = heap.clone();
+
+        assert_eq!(heap.free(a), Err(FreeError::NotAllocated(a)));
+        assert_eq!(
+            heap.free(Range(4, 12)),
+            Err(FreeError::NotAllocated(Range(4, 12)))
+        );
+        assert_eq!(
+            heap.free(Range(10, 20)),
+            Err(FreeError::OutOfBounds {
+                range: Range(10, 20),
+                bounds: Range(0, 16)
+            })
+        );
+        assert_eq!(
+            heap.free(Range(5, 3)),
+            Err(FreeError::Inverted(Range(5, 3)))
+        );
+        assert_eq!(heap.free(Range(3, 3)), Ok(()));
+        assert_eq!(heap, before);
+        assert_eq!(
+            std::format!("{}", FreeError::NotAllocated(a)),
+            "range 0..8 is not allocated"
+        );
+    }
+
+    #[test]
+    fn fuzz_alloc_free_sequences() {
+        let bounds = Range(5, 1000);
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut heap = RangeAllocator::new(bounds);
+            let mut live: std::vec::Vec<Range> = std::vec::Vec::new();
+            for _ in 0..500 {
+                if live.is_empty() || rng.random_bool(0.55) {
+                    let len = rng.random_range(1..80);
+                    let align = 1 << rng.random_range(0..5);
+                    if let Some(r) = heap.allocate_aligned(len, align) {
+                        assert_eq!(r.len(), len);
+                        assert!(r.start().is_multiple_of(align));
+                        assert!(live.iter().all(|l| !l.overlaps(&r)));
+                        live.push(r);
+                    }
+                } else {
+                    let r = live.swap_remove(rng.random_range(0..live.len()));
+                    heap.free(r).unwrap();
+                    assert!(heap.free(r).is_err());
+                }
+                assert_invariants(&heap);
+                let used: usize = live.iter().map(Range::len).sum();
+                assert_eq!(heap.free_len() + used, bounds.len());
+            }
+            for r in live.drain(..) {
+                heap.free(r).unwrap();
+            }
+            assert_eq!(heap.free_ranges(), [bounds]);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Long-lived positions that follow a buffer through a stream of edits.
+//!
+//! An [`Anchors`] collection holds positions created once and updated by every
+//! [`Edit`] applied to it, so ranges can be rebuilt from them at any time instead
+//! of being remapped one edit at a time.
+//!
+//! # Example
+//! ```
+//! use ars::range::Range;
+//! use ars::range::anchor::{Anchors, Bias};
+//! use ars::range::edit::Edit;
+//!
+//! // "hello world": anchor the word "world" so it grows with typing at its end.
+//! let mut anchors = Anchors::new();
+//! let start = anchors.insert(6, Bias::Right);
+//! let end = anchors.insert(11, Bias::Right);
+//!
+//! anchors.edit(Edit::insert(5, 1)); // "hello, world"
+//! anchors.edit(Edit::insert(12, 1)); // "hello, world!"
+//! assert_eq!(anchors.resolve_range(start, end), Range(7, 13));
+//! ```
+
+use alloc::vec::Vec;
+
+use super::Range;
+use super::edit::Edit;
+
+/// Which side of an edit an anchor sticks to when the edit happens exactly at
+/// its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Bias {
+    /// Stay before text inserted at the anchor, like the end of a word you are not
+    /// typing into.
+    Left,
+    /// Move after text inserted at the anchor, like a cursor while typing.
+    Right,
+}
+
+/// Identifies an anchor within the [`Anchors`] collection that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnchorId(usize);
+
+/// A collection of biased positions, kept up to date as edits are applied.
+///
+/// Each [`edit`](Self::edit) visits every anchor, costing `O(n)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Anchors {
+    anchors: Vec<(usize, Bias)>,
+}
+
+impl Anchors {
+    /// Creates an empty collection.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            anchors: Vec::new(),
+        }
+    }
+
+    /// Returns the number of anchors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// Returns `true` if there are no anchors.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Adds an anchor at `pos` in the current buffer.
+    pub fn insert(&mut self, pos: usize, bias: Bias) -> AnchorId {
+        self.anchors.push((pos, bias));
+        AnchorId(self.anchors.len() - 1)
+    }
+
+    /// Updates every anchor for `edit`, expressed against the buffer as left by
+    /// the edits before it.
+    ///
+    /// - Anchors before `edit.at` are unchanged.
+    /// - Anchors after the removed region are shifted by the change in length.
+    /// - Anchors within the removed region, bounds included, collapse to the
+    ///   start of the inserted elements if [`Bias::Left`], or to their end if
+    ///   [`Bias::Right`]. For a pure insertion, that is just anchors exactly at
+    ///   `edit.at`.
+    ///
+    /// Anchors with the same bias keep their relative order. Overflow is not
+    /// checked.
+    pub fn edit(&mut self, edit: Edit) {
+        let old_end = edit.at + edit.removed;
+        for (pos, bias) in &mut self.anchors {
+            if *pos < edit.at {
+                continue;
+            }
+            *pos = if *pos > old_end {
+                *pos - edit.removed + edit.inserted
+            } else {
+                match bias {
+                    Bias::Left => edit.at,
+                    Bias::Right => edit.at + edit.inserted,
+                }
+            };
+        }
+    }
+
+    /// Returns the current position of `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was created by a different collection with more anchors.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve(&self, id: AnchorId) -> usize {
+        self.anchors[id.0].0
+    }
+
+    /// Returns the bias `id` was created with.
+    ///
+    /// # Panics
+    /// Panics if `id` was created by a different collection with more anchors.
+    #[must_use]
+    #[track_caller]
+    pub fn bias(&self, id: AnchorId) -> Bias {
+        self.anchors[id.0].1
+    }
+
+    /// Returns the range between the current positions of `start` and `end`.
+    ///
+    /// If `start` has moved past `end`, e.g. a [`Bias::Right`] start and a
+    /// [`Bias::Left`] end at the same position receiving an insertion, the result
+    /// is the empty range at `start`.
+    ///
+    /// # Panics
+    /// Panics if either id was created by a different collection with more
+    /// anchors.
+    #[must_use]
+    #[track_caller]
+    pub fn resolve_range(&self, start: AnchorId, end: AnchorId) -> Range {
+        let (start, end) = (self.resolve(start), self.resolve(end));
+        Range(start, end.max(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    #[test]
+    fn insertion_at_anchor_respects_bias() {
+        let mut anchors = Anchors::new();
+        let left = anchors.insert(5, Bias::Left);
+        let right = anchors.insert(5, Bias::Right);
+        let before = anchors.insert(4, Bias::Right);
+        let after = anchors.insert(6, Bias::Left);
+        assert_eq!(anchors.len(), 4);
+        assert_eq!(anchors.bias(left), Bias::Left);
+
+        anchors.edit(Edit::insert(5, 3));
+        assert_eq!(anchors.resolve(left), 5);
+        assert_eq!(anchors.resolve(right), 8);
+        assert_eq!(anchors.resolve(before), 4);
+        assert_eq!(anchors.resolve(after), 9);
+        // Inserted text lands between the two anchors.
+        assert_eq!(anchors.resolve_range(left, right), Range(5, 8));
+        assert_eq!(anchors.resolve_range(right, left), Range(8, 8));
+
+        // An empty insertion moves nothing.
+        anchors.edit(Edit::insert(5, 0));
+        assert_eq!(anchors.resolve(right), 8);
+    }
+
+    #[test]
+    fn deletion_spanning_anchors_collapses_them() {
+        let mut anchors = Anchors::new();
+        let ids: Vec<_> = (0..12)
+            .map(|pos| {
+                (
+                    anchors.insert(pos, Bias::Left),
+                    anchors.insert(pos, Bias::Right),
+                )
+            })
+            .collect();
+
+        anchors.edit(Edit::delete(Range(3, 8)));
+        for (pos, &(left, right)) in ids.iter().enumerate() {
+            let expected = match pos {
+                ..3 => pos,
+                3..=8 => 3,
+                _ => pos - 5,
+            };
+            assert_eq!(anchors.resolve(left), expected, "{pos}");
+            assert_eq!(anchors.resolve(right), expected, "{pos}");
+        }
+        let (start, _) = ids[4];
+        let (_, end) = ids[10];
+        assert_eq!(anchors.resolve_range(start, end), Range(3, 5));
+    }
+
+    #[test]
+    fn replacement_splits_collapsed_anchors_by_bias() {
+        let mut anchors = Anchors::new();
+        let left = anchors.insert(4, Bias::Left);
+        let right = anchors.insert(4, Bias::Right);
+        let at_end = anchors.insert(6, Bias::Left);
+        let past = anchors.insert(7, Bias::Left);
+
+        anchors.edit(Edit::replace(Range(2, 6), 10));
+        assert_eq!(anchors.resolve(left), 2);
+        assert_eq!(anchors.resolve(right), 12);
+        assert_eq!(anchors.resolve(at_end), 2);
+        assert_eq!(anchors.resolve(past), 13);
+    }
+
+    #[test]
+    fn randomized_edits_keep_anchors_ordered_and_in_bounds() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut buffer_len = 100;
+            let mut anchors = Anchors::new();
+            let mut ids: Vec<AnchorId> = (0..40)
+                .map(|_| {
+                    let bias = if rng.random_bool(0.5) {
+                        Bias::Left
+                    } else {
+                        Bias::Right
+                    };
+                    anchors.insert(rng.random_range(0..=buffer_len), bias)
+                })
+                .collect();
+            ids.sort_unstable_by_key(|&id| anchors.resolve(id));
+
+            for _ in 0..100 {
+                let at = rng.random_range(0..=buffer_len);
+                let removed = rng.random_range(0..=(buffer_len - at).min(10));
+                let inserted = rng.random_range(0..10);
+                anchors.edit(Edit {
+                    at,
+                    removed,
+                    inserted,
+                });
+                buffer_len = buffer_len - removed + inserted;
+
+                for bias in [Bias::Left, Bias::Right] {
+                    let resolved: Vec<usize> = ids
+                        .iter()
+                        .filter(|&&id| anchors.bias(id) == bias)
+                        .map(|&id| anchors.resolve(id))
+                        .collect();
+                    assert!(resolved.is_sorted(), "{bias:?} {resolved:?}");
+                    assert!(resolved.iter().all(|&pos| pos <= buffer_len));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! [`bytemuck`] support for [`Range`] and the compact range types, enabled by the
+//! `bytemuck` feature.
+
+use bytemuck::{Pod, Zeroable};
+
+use super::{Range, Range16, Range32};
+
+// SAFETY: each type is `#[repr(C)]` with two fields of the same primitive integer
+// type, so it has no padding, every bit pattern is valid, and all zeros is
+// `Self(0, 0)`.
+unsafe impl Zeroable for Range {}
+// SAFETY: see above; the types are also `Copy` and `'static`.
+unsafe impl Pod for Range {}
+
+// SAFETY: as for `Range`.
+unsafe impl Zeroable for Range32 {}
+// SAFETY: as for `Range`.
+unsafe impl Pod for Range32 {}
+
+// SAFETY: as for `Range`.
+unsafe impl Zeroable for Range16 {}
+// SAFETY: as for `Range`.
+unsafe impl Pod for Range16 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::{align_of, size_of};
+
+    #[test]
+    fn layout_matches_two_bounds() {
+        assert_eq!(size_of::<Range>(), size_of::<[usize; 2]>());
+        assert_eq!(align_of::<Range>(), align_of::<usize>());
+        assert_eq!(size_of::<Range32>(), size_of::<[u32; 2]>());
+        assert_eq!(align_of::<Range32>(), align_of::<u32>());
+        assert_eq!(size_of::<Range16>(), size_of::<[u16; 2]>());
+        assert_eq!(align_of::<Range16>(), align_of::<u16>());
+    }
+
+    #[test]
+    fn cast_slice_round_trips() {
+        let ranges = [Range(0, 4), Range(4, 10), Range(usize::MAX - 1, usize::MAX)];
+        let bytes: &[u8] = bytemuck::cast_slice(&ranges);
+        assert_eq!(bytes.len(), 3 * 2 * size_of::<usize>());
+        let back: &[Range] = bytemuck::cast_slice(bytes);
+        assert_eq!(back, ranges);
+
+        let bounds: &[usize] = bytemuck::cast_slice(&ranges);
+        assert_eq!(bounds, [0, 4, 4, 10, usize::MAX - 1, usize::MAX]);
+    }
+
+    #[test]
+    fn compact_cast_slice_round_trips() {
+        let ranges = [Range32(1, 2), Range32(3, u32::MAX)];
+        let bounds: &[u32] = bytemuck::cast_slice(&ranges);
+        assert_eq!(bounds, [1, 2, 3, u32::MAX]);
+        assert_eq!(bytemuck::cast_slice::<u32, Range32>(bounds), ranges);
+
+        let ranges = [Range16(7, 9)];
+        let bytes: &[u8] = bytemuck::cast_slice(&ranges);
+        assert_eq!(bytemuck::cast_slice::<u8, Range16>(bytes), ranges);
+    }
+
+    #[test]
+    fn zeroed_is_empty_range_at_zero() {
+        assert_eq!(Range::zeroed(), Range(0, 0));
+        assert_eq!(Range32::zeroed(), Range32(0, 0));
+    }
+}
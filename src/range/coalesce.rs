@@ -0,0 +1,126 @@
+//! Normalizing unordered collections of [`Range`]s.
+
+use alloc::vec::Vec;
+
+use super::Range;
+
+/// Sorts `ranges` by start and merges overlapping and touching ranges, dropping
+/// empty and inverted ones.
+///
+/// Afterwards `ranges` is sorted, and no two of its ranges overlap or touch, as in
+/// a [`RangeSet`](super::RangeSet). This sorts in place and then merges in a single
+/// pass, without allocating.
+///
+/// # Example
+/// ```
+/// use ars::range::{coalesce, Range};
+/// let mut ranges = vec![Range(8, 10), Range(0, 3), Range(2, 5), Range(5, 6), Range(7, 7)];
+/// coalesce(&mut ranges);
+/// assert_eq!(ranges, [Range(0, 6), Range(8, 10)]);
+/// ```
+pub fn coalesce(ranges: &mut Vec<Range>) {
+    ranges.sort_unstable_by_key(Range::start);
+    let mut len = 0usize;
+    for i in 0..ranges.len() {
+        let r = ranges[i];
+        if r.is_empty() {
+            continue;
+        }
+        match len.checked_sub(1).map(|last| &mut ranges[last]) {
+            Some(last) if last.end() >= r.start() => {
+                if r.end() > last.end() {
+                    last.1 = r.end();
+                }
+            }
+            _ => {
+                ranges[len] = r;
+                len += 1;
+            }
+        }
+    }
+    ranges.truncate(len);
+}
+
+/// Collects `ranges` and [`coalesce`]s them.
+///
+/// # Example
+/// ```
+/// use ars::range::{coalesce_iter, Range};
+/// let merged = coalesce_iter([Range(4, 6), Range(0, 4), Range(10, 12)]);
+/// assert_eq!(merged, [Range(0, 6), Range(10, 12)]);
+/// ```
+#[must_use]
+pub fn coalesce_iter<I: IntoIterator<Item = Range>>(ranges: I) -> Vec<Range> {
+    let mut ranges = ranges.into_iter().collect();
+    coalesce(&mut ranges);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::collections::BTreeSet;
+    use std::vec;
+
+    #[test]
+    fn merges_duplicates_and_nested_ranges() {
+        let mut ranges = vec![Range(3, 5), Range(3, 5), Range(0, 10), Range(2, 4)];
+        coalesce(&mut ranges);
+        assert_eq!(ranges, [Range(0, 10)]);
+
+        let mut ranges = vec![Range(0, 2), Range(2, 4), Range(5, 6), Range(6, 6)];
+        coalesce(&mut ranges);
+        assert_eq!(ranges, [Range(0, 4), Range(5, 6)]);
+    }
+
+    #[test]
+    fn drops_empty_and_inverted_ranges() {
+        let mut ranges = vec![Range(5, 5), Range(9, 2), Range(0, 0)];
+        coalesce(&mut ranges);
+        assert!(ranges.is_empty());
+
+        // An empty range between two others does not bridge them.
+        assert_eq!(
+            coalesce_iter([Range(6, 8), Range(4, 4), Range(0, 3), Range(5, 3)]),
+            [Range(0, 3), Range(6, 8)]
+        );
+        assert!(coalesce_iter([]).is_empty());
+    }
+
+    #[test]
+    fn sorted_input_and_idempotence() {
+        let sorted = vec![Range(0, 1), Range(2, 3), Range(10, 20)];
+        let mut ranges = sorted.clone();
+        coalesce(&mut ranges);
+        assert_eq!(ranges, sorted);
+
+        let mut ranges = vec![Range(9, 12), Range(1, 4), Range(3, 6), Range(20, 21)];
+        coalesce(&mut ranges);
+        let once = ranges.clone();
+        coalesce(&mut ranges);
+        assert_eq!(ranges, once);
+    }
+
+    #[test]
+    fn randomized_against_index_model() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let ranges: std::vec::Vec<Range> = (0..rng.random_range(0..20))
+                .map(|_| {
+                    let start = rng.random_range(0..100);
+                    Range(start, start + rng.random_range(0..15))
+                })
+                .collect();
+            let model: BTreeSet<usize> = ranges.iter().flat_map(Range::iter).collect();
+            let merged = coalesce_iter(ranges);
+            let covered: BTreeSet<usize> = merged.iter().flat_map(Range::iter).collect();
+            assert_eq!(covered, model);
+            assert!(merged.iter().all(|r| !r.is_empty()));
+            assert!(merged.windows(2).all(|w| w[0].end() < w[1].start()));
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Compact [`Range`] variants with narrower bounds.
+//!
+//! [`Range32`] and [`Range16`] store their bounds as `u32` and `u16`, halving or
+//! quartering the memory used by large collections of spans. They expose the same
+//! core methods as [`Range`], convert into it infallibly, and convert from it with
+//! [`TryFrom`], failing if a bound does not fit.
+//!
+//! Like [`Range`], both types are `#[repr(C)]` with the layout of a two-element
+//! array of their bound type.
+
+use core::fmt::{Debug, Formatter};
+
+use super::{Range, TryFromRangeError};
+
+macro_rules! compact_range {
+    ($(#[$meta:meta])* $name:ident, $int:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(C)]
+        pub struct $name(pub $int, pub $int);
+
+        impl $name {
+            #[doc = concat!("Construct a new `", stringify!($name), "` from `start` and `end` (start inclusive, end exclusive).")]
+            ///
+            /// The values are not validated, see [`Range::new`].
+            #[must_use]
+            #[inline]
+            pub const fn new(start: $int, end: $int) -> Self {
+                Self(start, end)
+            }
+
+            /// Returns the start (inclusive) of the range.
+            #[must_use]
+            #[inline]
+            pub const fn start(&self) -> $int {
+                self.0
+            }
+
+            /// Returns the end (exclusive) of the range.
+            #[must_use]
+            #[inline]
+            pub const fn end(&self) -> $int {
+                self.1
+            }
+
+            /// Returns the length of the range, saturating at 0 if `end < start`.
+            #[must_use]
+            #[inline]
+            pub const fn len(&self) -> $int {
+                self.1.saturating_sub(self.0)
+            }
+
+            /// Returns `true` if the range contains no elements (i.e. `start >= end`).
+            #[must_use]
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.0 >= self.1
+            }
+
+            /// Returns `true` if the provided index is inside the range.
+            #[must_use]
+            #[inline]
+            pub const fn contains(&self, index: $int) -> bool {
+                index >= self.0 && index < self.1
+            }
+
+            /// Returns a new range clamped to the provided `len`, see [`Range::clamp_to`].
+            #[must_use]
+            #[inline]
+            pub const fn clamp_to(&self, len: $int) -> Self {
+                let s = if self.0 < len { self.0 } else { len };
+                let e = if self.1 < len { self.1 } else { len };
+                Self(s, e)
+            }
+
+            /// Returns the intersection of `self` and `other`, or `None` if they don't overlap.
+            #[must_use]
+            #[inline]
+            pub const fn intersect(&self, other: &Self) -> Option<Self> {
+                let s = if self.0 > other.0 { self.0 } else { other.0 };
+                let e = if self.1 < other.1 { self.1 } else { other.1 };
+                if s < e { Some(Self(s, e)) } else { None }
+            }
+
+            /// Shift the range by `delta` (adds to both start and end).
+            ///
+            /// Like [`Range::offset`] this does not check overflow.
+            #[must_use]
+            #[inline]
+            pub const fn offset(&self, delta: $int) -> Self {
+                Self(self.0 + delta, self.1 + delta)
+            }
+
+            /// Attempt to shrink the range from the start and/or end by provided
+            /// amounts, see [`Range::shrink`].
+            #[must_use]
+            pub const fn shrink(&self, start_shrink: $int, end_shrink: $int) -> Self {
+                let s = self.0.saturating_add(start_shrink);
+                let e = self.1.saturating_sub(end_shrink);
+                if s < e {
+                    return Self(s, e);
+                }
+                let max = if self.0 > self.1 { self.0 } else { self.1 };
+                let s = if s < max { s } else { max };
+                Self(s, s)
+            }
+        }
+
+        /// Renders as `start..end`, or as the verbose struct form with `{:#?}`,
+        /// matching [`Range`].
+        impl Debug for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+                if f.alternate() {
+                    f.debug_struct(stringify!($name))
+                        .field("start", &self.0)
+                        .field("end", &self.1)
+                        .finish()
+                } else {
+                    Debug::fmt(&self.0, f)?;
+                    f.write_str("..")?;
+                    Debug::fmt(&self.1, f)
+                }
+            }
+        }
+
+        impl From<$name> for Range {
+            fn from(r: $name) -> Self {
+                Self(r.0 as usize, r.1 as usize)
+            }
+        }
+
+        impl TryFrom<Range> for $name {
+            type Error = TryFromRangeError;
+
+            fn try_from(r: Range) -> Result<Self, Self::Error> {
+                match (<$int>::try_from(r.0), <$int>::try_from(r.1)) {
+                    (Ok(s), Ok(e)) => Ok(Self(s, e)),
+                    _ => Err(TryFromRangeError(())),
+                }
+            }
+        }
+
+        impl<T> core::ops::Index<$name> for [T] {
+            type Output = [T];
+
+            fn index(&self, index: $name) -> &Self::Output {
+                &self[Range::from(index)]
+            }
+        }
+
+        impl<T> core::ops::Index<&$name> for [T] {
+            type Output = [T];
+
+            fn index(&self, index: &$name) -> &Self::Output {
+                &self[Range::from(*index)]
+            }
+        }
+
+        impl<T> core::ops::IndexMut<$name> for [T] {
+            fn index_mut(&mut self, index: $name) -> &mut Self::Output {
+                &mut self[Range::from(index)]
+            }
+        }
+
+        impl<T> core::ops::IndexMut<&$name> for [T] {
+            fn index_mut(&mut self, index: &$name) -> &mut Self::Output {
+                &mut self[Range::from(*index)]
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> core::ops::Index<$name> for alloc::vec::Vec<T> {
+            type Output = [T];
+
+            fn index(&self, index: $name) -> &Self::Output {
+                &self[Range::from(index)]
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> core::ops::Index<&$name> for alloc::vec::Vec<T> {
+            type Output = [T];
+
+            fn index(&self, index: &$name) -> &Self::Output {
+                &self[Range::from(*index)]
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> core::ops::IndexMut<$name> for alloc::vec::Vec<T> {
+            fn index_mut(&mut self, index: $name) -> &mut Self::Output {
+                &mut self[Range::from(index)]
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T> core::ops::IndexMut<&$name> for alloc::vec::Vec<T> {
+            fn index_mut(&mut self, index: &$name) -> &mut Self::Output {
+                &mut self[Range::from(*index)]
+            }
+        }
+    };
+}
+
+compact_range!(
+    /// A [`Range`] with `u32` bounds, for spans over buffers smaller than 4 GiB.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, Range32};
+    /// let a = [1, 2, 3, 4];
+    /// let r = Range32::new(1, 3);
+    /// assert_eq!(&a[r], &[2, 3]);
+    /// assert_eq!(Range::from(r), Range(1, 3));
+    /// assert!(Range32::try_from(Range(0, usize::MAX)).is_err());
+    /// ```
+    Range32,
+    u32
+);
+
+compact_range!(
+    /// A [`Range`] with `u16` bounds, for spans over buffers smaller than 64 KiB.
+    Range16,
+    u16
+);
+
+#[cfg(test)]
+mod tests {
+    // Shared tests keep every range type's behavior in sync with `Range`.
+    macro_rules! range_type_tests {
+        ($module:ident, $name:ident, $int:ty) => {
+            mod $module {
+                use crate::range::*;
+                extern crate std;
+                use std::format;
+
+                #[test]
+                fn helpers_len_empty_contains() {
+                    assert_eq!($name::new(2, 5).len(), 3);
+                    assert!($name::new(2, 2).is_empty());
+                    assert!($name::new(3, 4).contains(3));
+                    assert!(!$name::new(3, 4).contains(4));
+                    assert_eq!($name::new(5, 3).len(), 0);
+                    assert!($name::new(5, 3).is_empty());
+                }
+
+                #[test]
+                fn clamp_and_intersect() {
+                    let r = $name::new(2, 10);
+                    assert_eq!(r.clamp_to(5), $name::new(2, 5));
+                    assert_eq!(r.clamp_to(1), $name::new(1, 1));
+                    let a = $name::new(0, 5);
+                    assert_eq!(a.intersect(&$name::new(3, 8)), Some($name::new(3, 5)));
+                    assert_eq!($name::new(0, 2).intersect(&$name::new(2, 4)), None);
+                }
+
+                #[test]
+                fn offset_and_shrink() {
+                    let r = $name::new(2, 7);
+                    assert_eq!(r.offset(3), $name::new(5, 10));
+                    assert_eq!(r.shrink(1, 2), $name::new(3, 5));
+                    assert!(r.shrink(10, 0).is_empty());
+                    assert_eq!(r.shrink(10, 0), $name::new(7, 7));
+                    assert_eq!($name::new(5, 2).shrink(1, 1), $name::new(5, 5));
+                }
+
+                #[test]
+                fn indexing() {
+                    let mut buf = [10, 20, 30, 40, 50];
+                    let r = $name::new(1, 4);
+                    assert_eq!(&buf[r], &[20, 30, 40]);
+                    assert_eq!(&buf[&r], &[20, 30, 40]);
+                    buf[r].fill(0);
+                    buf[&$name::new(0, 1)][0] = 1;
+                    assert_eq!(buf, [1, 0, 0, 0, 50]);
+                }
+
+                #[test]
+                fn conversions() {
+                    let r = $name::new(3, 9);
+                    assert_eq!(Range::from(r), Range(3, 9));
+                    assert_eq!($name::try_from(Range(3, 9)), Ok(r));
+                    let max = <$int>::MAX as usize;
+                    assert_eq!(
+                        $name::try_from(Range(0, max)),
+                        Ok($name::new(0, <$int>::MAX))
+                    );
+                    assert_eq!(
+                        $name::try_from(Range(0, max + 1)),
+                        Err(TryFromRangeError(()))
+                    );
+                    assert_eq!(
+                        $name::try_from(Range(max + 1, max + 2)),
+                        Err(TryFromRangeError(()))
+                    );
+                }
+
+                #[test]
+                fn methods_agree_with_range() {
+                    for a in 0..6 {
+                        for b in 0..6 {
+                            let x = $name::new(a, b);
+                            let wide = Range::from(x);
+                            assert_eq!(x.len() as usize, wide.len());
+                            assert_eq!(x.is_empty(), wide.is_empty());
+                            assert_eq!(Range::from(x.clamp_to(3)), wide.clamp_to(3));
+                            assert_eq!(Range::from(x.shrink(1, 2)), wide.shrink(1, 2));
+                            assert_eq!(Range::from(x.offset(4)), wide.offset(4));
+                            for c in 0..6 {
+                                for d in 0..6 {
+                                    let y = $name::new(c, d);
+                                    let i = x.intersect(&y).map(Range::from);
+                                    assert_eq!(i, wide.intersect(&Range::from(y)));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                #[test]
+                fn const_helpers() {
+                    const R: $name = $name::new(2, 10).clamp_to(6).offset(1).shrink(1, 1);
+                    const I: Option<$name> = R.intersect(&$name::new(0, 5));
+                    assert_eq!(R, $name::new(4, 6));
+                    assert_eq!(I, Some($name::new(4, 5)));
+                }
+
+                #[test]
+                fn debug_matches_range() {
+                    assert_eq!(format!("{:?}", $name::new(2, 5)), "2..5");
+                    assert!(format!("{:#?}", $name::new(2, 5)).starts_with(stringify!($name)));
+                }
+            }
+        };
+    }
+
+    range_type_tests!(range32, Range32, u32);
+    range_type_tests!(range16, Range16, u16);
+}
@@ -0,0 +1,153 @@
+//! Measuring how many indices a collection of [`Range`]s covers.
+
+use super::Range;
+
+/// Returns the number of indices covered by at least one of `ranges`, which may
+/// be in any order and overlap.
+///
+/// This collects and [`coalesce`](super::coalesce)s the ranges; use
+/// [`covered_len_sorted`] to avoid the allocation when they are sorted by start.
+///
+/// # Example
+/// ```
+/// use ars::range::{covered_len, Range};
+/// assert_eq!(covered_len([Range(5, 10), Range(0, 6), Range(20, 21)]), 11);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn covered_len(ranges: impl IntoIterator<Item = Range>) -> usize {
+    super::coalesce_iter(ranges).iter().map(Range::len).sum()
+}
+
+#[cfg_attr(
+    feature = "alloc",
+    doc = " Like [`covered_len`], for ranges sorted by start, counting in a single"
+)]
+#[cfg_attr(
+    not(feature = "alloc"),
+    doc = " Returns the number of indices covered by at least one of `ranges`, which",
+    doc = " must be sorted by start, counting in a single"
+)]
+/// streaming pass without allocating.
+///
+/// # Panics
+/// In debug builds, panics if `ranges` is not sorted by start. Empty and inverted
+/// ranges are skipped and may appear anywhere.
+#[must_use]
+#[track_caller]
+pub fn covered_len_sorted(ranges: impl IntoIterator<Item = Range>) -> usize {
+    let mut total = 0;
+    let mut current: Option<Range> = None;
+    let mut last_start = 0;
+    for r in ranges.into_iter().filter(|r| !r.is_empty()) {
+        debug_assert!(r.start() >= last_start, "ranges must be sorted by start");
+        last_start = r.start();
+        match &mut current {
+            Some(cur) if r.start() <= cur.end() => cur.1 = cur.end().max(r.end()),
+            Some(cur) => {
+                total += cur.len();
+                *cur = r;
+            }
+            None => current = Some(r),
+        }
+    }
+    total + current.map_or(0, |cur| cur.len())
+}
+
+/// Returns the fraction of `0..total_len` covered by `ranges`, between 0 and 1.
+///
+/// Ranges are clamped to `total_len` first, so parts past the end don't count.
+/// A `total_len` of 0 yields 0.
+///
+/// # Example
+/// ```
+/// use ars::range::{covered_fraction, Range};
+/// let downloaded = [Range(0, 300), Range(200, 500), Range(900, 2000)];
+/// assert_eq!(covered_fraction(downloaded, 1000), 0.6);
+/// ```
+#[cfg(feature = "alloc")]
+#[must_use]
+pub fn covered_fraction(ranges: impl IntoIterator<Item = Range>, total_len: usize) -> f64 {
+    if total_len == 0 {
+        return 0.0;
+    }
+    let covered = covered_len(ranges.into_iter().map(|r| r.clamp_to(total_len)));
+    covered as f64 / total_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_counts_overlaps_once() {
+        assert_eq!(covered_len_sorted([]), 0);
+        assert_eq!(covered_len_sorted([Range(0, 10)]), 10);
+        assert_eq!(
+            covered_len_sorted([Range(0, 10), Range(0, 10), Range(2, 5), Range(8, 12)]),
+            12
+        );
+        assert_eq!(
+            covered_len_sorted([Range(0, 2), Range(2, 4), Range(6, 7)]),
+            5
+        );
+        // Empty ranges are skipped wherever they are.
+        assert_eq!(
+            covered_len_sorted([Range(9, 9), Range(1, 3), Range(5, 2), Range(2, 4)]),
+            3
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "ranges must be sorted by start"]
+    fn sorted_rejects_unsorted_input_in_debug() {
+        let _ = covered_len_sorted([Range(5, 10), Range(0, 2)]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn general_path_handles_heavy_overlap() {
+        let nested = (0..100).map(|i| Range(i, 200 - i));
+        assert_eq!(covered_len(nested), 200);
+        let repeated = core::iter::repeat_n(Range(3, 7), 50).chain([Range(0, 1)]);
+        assert_eq!(covered_len(repeated), 5);
+        assert_eq!(covered_len([Range(10, 5)]), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sorted_path_agrees_with_general_path() {
+        extern crate std;
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+        use std::vec::Vec;
+
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut ranges: Vec<Range> = (0..rng.random_range(0..30))
+                .map(|_| {
+                    let start = rng.random_range(0..100);
+                    Range(start, start + rng.random_range(0..20))
+                })
+                .collect();
+            let general = covered_len(ranges.iter().copied());
+            ranges.sort_unstable_by_key(Range::start);
+            assert_eq!(covered_len_sorted(ranges.iter().copied()), general);
+            let naive = (0..120)
+                .filter(|&i| ranges.iter().any(|r| r.contains(i)))
+                .count();
+            assert_eq!(general, naive);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fraction_of_total() {
+        assert_eq!(covered_fraction([Range(0, 50), Range(25, 75)], 100), 0.75);
+        assert_eq!(covered_fraction([Range(0, 50)], 0), 0.0);
+        assert_eq!(covered_fraction([Range(50, 500)], 100), 0.5);
+        assert_eq!(covered_fraction([Range(0, 100), Range(0, 100)], 100), 1.0);
+        assert_eq!(covered_fraction([], 10), 0.0);
+    }
+}
@@ -0,0 +1,191 @@
+//! [`Cursor`], for consuming a [`Range`] front to back.
+
+use super::Range;
+
+/// A cursor consuming a [`Range`] from the front, handing out the consumed
+/// sub-ranges.
+///
+/// The consumed ranges are consecutive and never overlap, so they index the
+/// underlying buffer directly.
+///
+/// # Example
+/// ```
+/// use ars::range::{Cursor, Range};
+///
+/// fn header(buf: &[u8]) -> Option<(&[u8], u8)> {
+///     let mut cursor = Cursor::new(Range(0, buf.len()));
+///     let magic = &buf[cursor.take(4)?];
+///     let version = buf[cursor.take(1)?][0];
+///     Some((magic, version))
+/// }
+///
+/// assert_eq!(header(b"ARS\x001rest"), Some((&b"ARS\x00"[..], b'1')));
+/// assert_eq!(header(b"AR"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    remaining: Range,
+}
+
+impl Cursor {
+    /// Creates a cursor positioned at the start of `range`.
+    ///
+    /// An inverted range is treated as empty at its start.
+    #[must_use]
+    #[inline]
+    pub const fn new(range: Range) -> Self {
+        let end = if range.1 < range.0 { range.0 } else { range.1 };
+        Self {
+            remaining: Range(range.0, end),
+        }
+    }
+
+    /// Returns the part of the range not consumed yet.
+    #[must_use]
+    #[inline]
+    pub const fn remaining(&self) -> Range {
+        self.remaining
+    }
+
+    /// Returns the current position, the start of [`remaining`](Self::remaining).
+    #[must_use]
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.remaining.0
+    }
+
+    /// Returns `true` if the whole range has been consumed.
+    #[must_use]
+    #[inline]
+    pub const fn is_finished(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// Returns the next `n` indices without consuming them, or `None` if fewer
+    /// than `n` remain.
+    #[must_use]
+    #[inline]
+    pub const fn peek(&self, n: usize) -> Option<Range> {
+        if n <= self.remaining.len() {
+            Some(Range::from_start_len(self.remaining.0, n))
+        } else {
+            None
+        }
+    }
+
+    /// Consumes and returns the next `n` indices, or returns `None` and consumes
+    /// nothing if fewer than `n` remain.
+    #[inline]
+    pub const fn take(&mut self, n: usize) -> Option<Range> {
+        let Some(taken) = self.peek(n) else {
+            return None;
+        };
+        self.remaining.0 = taken.1;
+        Some(taken)
+    }
+
+    /// Consumes and returns everything that remains, leaving the cursor finished.
+    #[inline]
+    pub const fn take_rest(&mut self) -> Range {
+        let rest = self.remaining;
+        self.remaining.0 = rest.1;
+        rest
+    }
+}
+
+impl From<Range> for Cursor {
+    fn from(range: Range) -> Self {
+        Self::new(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    // A record is a 2-byte magic, a 1-byte count, `count` 2-byte big-endian
+    // values, then a trailer.
+    const RECORD: &[u8] = b"\xAB\xCD\x03\x00\x01\x01\x00\xFF\xFFtrailer";
+
+    struct Parsed {
+        values: Vec<u16>,
+        trailer: Range,
+        consumed: Vec<Range>,
+    }
+
+    fn parse(buf: &[u8]) -> Option<Parsed> {
+        let mut cursor = Cursor::new(Range(0, buf.len()));
+        let mut consumed = Vec::new();
+        let mut take = |cursor: &mut Cursor, n| {
+            let r = cursor.take(n)?;
+            consumed.push(r);
+            Some(r)
+        };
+        if buf[take(&mut cursor, 2)?] != [0xAB, 0xCD] {
+            return None;
+        }
+        let count = buf[take(&mut cursor, 1)?][0];
+        let mut values = Vec::new();
+        for _ in 0..count {
+            let bytes = &buf[take(&mut cursor, 2)?];
+            values.push(u16::from_be_bytes([bytes[0], bytes[1]]));
+        }
+        let trailer = cursor.take_rest();
+        consumed.push(trailer);
+        assert!(cursor.is_finished());
+        Some(Parsed {
+            values,
+            trailer,
+            consumed,
+        })
+    }
+
+    #[test]
+    fn parse_binary_record() {
+        let parsed = parse(RECORD).unwrap();
+        assert_eq!(parsed.values, [1, 256, 0xFFFF]);
+        assert_eq!(&RECORD[parsed.trailer], b"trailer");
+
+        // Consumed ranges are consecutive: no overlap and full coverage.
+        let mut expected_start = 0;
+        for r in &parsed.consumed {
+            assert_eq!(r.start(), expected_start);
+            expected_start = r.end();
+        }
+        assert_eq!(expected_start, RECORD.len());
+        assert!(parsed.consumed.windows(2).all(|w| !w[0].overlaps(&w[1])));
+    }
+
+    #[test]
+    fn parse_truncated_record() {
+        assert!(parse(&RECORD[..6]).is_none());
+        assert!(parse(b"\xAB").is_none());
+    }
+
+    #[test]
+    fn take_and_peek() {
+        let mut cursor = Cursor::new(Range(10, 16));
+        assert_eq!(cursor.peek(4), Some(Range(10, 14)));
+        assert_eq!(cursor.position(), 10);
+        assert_eq!(cursor.take(4), Some(Range(10, 14)));
+        assert_eq!(cursor.remaining(), Range(14, 16));
+        // A failed take consumes nothing.
+        assert_eq!(cursor.take(3), None);
+        assert_eq!(cursor.remaining(), Range(14, 16));
+        assert_eq!(cursor.take(0), Some(Range(14, 14)));
+        assert_eq!(cursor.take(2), Some(Range(14, 16)));
+        assert!(cursor.is_finished());
+        assert_eq!(cursor.peek(0), Some(Range(16, 16)));
+        assert_eq!(cursor.take_rest(), Range(16, 16));
+    }
+
+    #[test]
+    fn inverted_range_is_empty() {
+        let mut cursor = Cursor::from(Range(5, 2));
+        assert!(cursor.is_finished());
+        assert_eq!(cursor.take(1), None);
+        assert_eq!(cursor.take_rest(), Range(5, 5));
+    }
+}
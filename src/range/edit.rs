@@ -0,0 +1,365 @@
+//! Remapping ranges through buffer edits.
+//!
+//! An [`Edit`] describes replacing `removed` elements at `at` with `inserted` new
+//! ones. [`Edit::apply_to`] moves a [`Range`] recorded before the edit to where the
+//! same content lives afterwards.
+//!
+//! # Example
+//! ```
+//! use ars::range::Range;
+//! use ars::range::edit::Edit;
+//!
+//! // "hello world" -> "hello, world": insert "," at 5.
+//! let world = Range(6, 11);
+//! assert_eq!(Edit::insert(5, 1).apply_to(world), Some(Range(7, 12)));
+//! ```
+
+use super::Range;
+
+/// A single buffer edit: `removed` elements starting at `at` are replaced by
+/// `inserted` new elements.
+///
+/// Pure insertions have `removed == 0` and pure deletions have `inserted == 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edit {
+    /// Position of the edit in the buffer before it is applied.
+    pub at: usize,
+    /// Number of elements removed at `at`.
+    pub removed: usize,
+    /// Number of elements inserted at `at`.
+    pub inserted: usize,
+}
+
+impl Edit {
+    /// An edit inserting `len` elements at `at`.
+    #[must_use]
+    #[inline]
+    pub const fn insert(at: usize, len: usize) -> Self {
+        Self {
+            at,
+            removed: 0,
+            inserted: len,
+        }
+    }
+
+    /// An edit deleting the elements in `range`.
+    #[must_use]
+    #[inline]
+    pub const fn delete(range: Range) -> Self {
+        Self {
+            at: range.start(),
+            removed: range.len(),
+            inserted: 0,
+        }
+    }
+
+    /// An edit replacing the elements in `range` with `inserted` new ones.
+    #[must_use]
+    #[inline]
+    pub const fn replace(range: Range, inserted: usize) -> Self {
+        Self {
+            at: range.start(),
+            removed: range.len(),
+            inserted,
+        }
+    }
+
+    /// Returns the range removed from the buffer before the edit.
+    #[must_use]
+    #[inline]
+    pub const fn removed_range(&self) -> Range {
+        Range::from_start_len(self.at, self.removed)
+    }
+
+    /// Returns the range the inserted elements occupy after the edit.
+    #[must_use]
+    #[inline]
+    pub const fn inserted_range(&self) -> Range {
+        Range::from_start_len(self.at, self.inserted)
+    }
+
+    /// Maps `r`, expressed against the buffer before the edit, to the buffer after
+    /// it.
+    ///
+    /// - A span ending at or before `at` is unchanged.
+    /// - A span starting at or after the removed region is shifted. In particular a
+    ///   pure insertion exactly at a span's start pushes the span right.
+    /// - A span lying entirely within the removed region (bounds included) was
+    ///   deleted, and `None` is returned.
+    /// - A span strictly containing the removed region is resized around the
+    ///   inserted elements.
+    /// - A span whose head or tail is removed is truncated to the part that
+    ///   survives.
+    ///
+    /// The inserted elements become part of the span only if the removed region
+    /// lies strictly inside it. An edit reaching either boundary of the span places
+    /// them outside, just like a pure insertion at that boundary.
+    ///
+    /// The span is expected to be non-inverted. Overflow is not checked.
+    #[must_use]
+    pub const fn apply_to(&self, r: Range) -> Option<Range> {
+        let at = self.at;
+        let old_end = self.at + self.removed;
+        let (start, end) = (r.start(), r.end());
+
+        if end <= at {
+            Some(r)
+        } else if start >= old_end {
+            Some(Range(
+                start - self.removed + self.inserted,
+                end - self.removed + self.inserted,
+            ))
+        } else if at <= start && end <= old_end {
+            None
+        } else if start < at && old_end < end {
+            Some(Range(start, end - self.removed + self.inserted))
+        } else if at <= start {
+            // The edit covers the beginning of the span.
+            Some(Range(
+                at + self.inserted,
+                end - self.removed + self.inserted,
+            ))
+        } else {
+            // The edit covers the end of the span.
+            Some(Range(start, at))
+        }
+    }
+}
+
+/// An ordered sequence of [`Edit`]s, each expressed against the buffer as left by
+/// the edits before it.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// use ars::range::edit::{Delta, Edit};
+///
+/// let delta: Delta = [Edit::insert(0, 2), Edit::delete(Range(4, 6))].into_iter().collect();
+/// assert_eq!(delta.apply_all(Range(1, 8)), Some(Range(3, 8)));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Delta {
+    edits: alloc::vec::Vec<Edit>,
+}
+
+#[cfg(feature = "alloc")]
+impl Delta {
+    /// Creates an empty delta.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            edits: alloc::vec::Vec::new(),
+        }
+    }
+
+    /// Appends an edit applied after all edits already in the delta.
+    pub fn push(&mut self, edit: Edit) {
+        self.edits.push(edit);
+    }
+
+    /// Returns the edits in application order.
+    #[must_use]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Maps `r` through every edit in order, returning `None` as soon as an edit
+    /// deletes it.
+    #[must_use]
+    pub fn apply_all(&self, r: Range) -> Option<Range> {
+        self.edits.iter().try_fold(r, |r, edit| edit.apply_to(r))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromIterator<Edit> for Delta {
+    fn from_iter<I: IntoIterator<Item = Edit>>(iter: I) -> Self {
+        Self {
+            edits: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Extend<Edit> for Delta {
+    fn extend<I: IntoIterator<Item = Edit>>(&mut self, iter: I) {
+        self.edits.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPAN: Range = Range(10, 20);
+
+    #[test]
+    fn span_before_edit_is_unchanged() {
+        assert_eq!(Edit::insert(25, 3).apply_to(SPAN), Some(SPAN));
+        assert_eq!(Edit::delete(Range(22, 30)).apply_to(SPAN), Some(SPAN));
+        // Adjacent: the edit starts exactly at the span end.
+        assert_eq!(Edit::insert(20, 3).apply_to(SPAN), Some(SPAN));
+        assert_eq!(Edit::delete(Range(20, 25)).apply_to(SPAN), Some(SPAN));
+        assert_eq!(Edit::replace(Range(20, 25), 1).apply_to(SPAN), Some(SPAN));
+    }
+
+    #[test]
+    fn span_after_edit_is_shifted() {
+        assert_eq!(Edit::insert(2, 3).apply_to(SPAN), Some(Range(13, 23)));
+        assert_eq!(Edit::delete(Range(2, 5)).apply_to(SPAN), Some(Range(7, 17)));
+        assert_eq!(
+            Edit::replace(Range(2, 5), 1).apply_to(SPAN),
+            Some(Range(8, 18))
+        );
+        // Adjacent: the removed region ends exactly at the span start.
+        assert_eq!(
+            Edit::delete(Range(5, 10)).apply_to(SPAN),
+            Some(Range(5, 15))
+        );
+        // Pure insertion exactly at the start pushes the span right.
+        assert_eq!(Edit::insert(10, 4).apply_to(SPAN), Some(Range(14, 24)));
+    }
+
+    #[test]
+    fn span_containing_edit_is_resized() {
+        assert_eq!(Edit::insert(15, 4).apply_to(SPAN), Some(Range(10, 24)));
+        assert_eq!(
+            Edit::delete(Range(12, 15)).apply_to(SPAN),
+            Some(Range(10, 17))
+        );
+        assert_eq!(
+            Edit::replace(Range(12, 15), 5).apply_to(SPAN),
+            Some(Range(10, 22))
+        );
+        // Removed region touching either inner boundary.
+        assert_eq!(
+            Edit::delete(Range(10, 12)).apply_to(SPAN),
+            Some(Range(10, 18))
+        );
+        assert_eq!(
+            Edit::delete(Range(18, 20)).apply_to(SPAN),
+            Some(Range(10, 18))
+        );
+    }
+
+    #[test]
+    fn span_overlapping_deletion_is_truncated() {
+        // Edit covers the beginning of the span.
+        assert_eq!(
+            Edit::delete(Range(5, 15)).apply_to(SPAN),
+            Some(Range(5, 10))
+        );
+        assert_eq!(
+            Edit::replace(Range(5, 15), 3).apply_to(SPAN),
+            Some(Range(8, 13))
+        );
+        assert_eq!(
+            Edit::delete(Range(10, 15)).apply_to(SPAN),
+            Some(Range(10, 15))
+        );
+        assert_eq!(
+            Edit::replace(Range(10, 15), 3).apply_to(SPAN),
+            Some(Range(13, 18))
+        );
+        // Edit covers the end of the span.
+        assert_eq!(
+            Edit::delete(Range(15, 25)).apply_to(SPAN),
+            Some(Range(10, 15))
+        );
+        assert_eq!(
+            Edit::replace(Range(15, 25), 3).apply_to(SPAN),
+            Some(Range(10, 15))
+        );
+        assert_eq!(
+            Edit::replace(Range(15, 20), 3).apply_to(SPAN),
+            Some(Range(10, 15))
+        );
+    }
+
+    #[test]
+    fn span_fully_deleted() {
+        assert_eq!(Edit::delete(Range(5, 25)).apply_to(SPAN), None);
+        assert_eq!(Edit::delete(SPAN).apply_to(SPAN), None);
+        assert_eq!(Edit::replace(SPAN, 4).apply_to(SPAN), None);
+        assert_eq!(Edit::delete(Range(10, 25)).apply_to(SPAN), None);
+        assert_eq!(Edit::delete(Range(5, 20)).apply_to(SPAN), None);
+    }
+
+    #[test]
+    fn zero_length_edits() {
+        let noop = Edit::insert(15, 0);
+        assert_eq!(noop.apply_to(SPAN), Some(SPAN));
+        assert_eq!(Edit::delete(Range(3, 3)).apply_to(SPAN), Some(SPAN));
+
+        // Empty spans behave like positions.
+        assert_eq!(Edit::insert(5, 2).apply_to(Range(5, 5)), Some(Range(5, 5)));
+        assert_eq!(Edit::insert(4, 2).apply_to(Range(5, 5)), Some(Range(7, 7)));
+        assert_eq!(Edit::delete(Range(3, 8)).apply_to(Range(5, 5)), None);
+    }
+
+    #[test]
+    fn edit_ranges() {
+        let e = Edit::replace(Range(4, 7), 5);
+        assert_eq!(e.removed_range(), Range(4, 7));
+        assert_eq!(e.inserted_range(), Range(4, 9));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn delta_applies_in_order() {
+        let mut delta = Delta::new();
+        assert_eq!(delta.apply_all(SPAN), Some(SPAN));
+        delta.push(Edit::insert(0, 5));
+        delta.push(Edit::delete(Range(15, 17)));
+        assert_eq!(delta.edits().len(), 2);
+        assert_eq!(delta.apply_all(SPAN), Some(Range(15, 23)));
+
+        delta.extend([Edit::delete(Range(0, 30))]);
+        assert_eq!(delta.apply_all(SPAN), None);
+    }
+
+    #[test]
+    fn positions_track_a_real_buffer() {
+        extern crate std;
+        use std::vec::Vec;
+
+        let mut buf: Vec<u32> = (0..30).collect();
+        let edits = [
+            Edit::insert(12, 3),
+            Edit::delete(Range(0, 4)),
+            Edit::replace(Range(14, 18), 1),
+            Edit::delete(Range(19, 21)),
+        ];
+        let spans = [Range(0, 2), Range(5, 9), Range(10, 20), Range(25, 30)];
+        let expected: Vec<Option<Vec<u32>>> = spans
+            .iter()
+            .map(|r| Some(buf[r.start()..r.end()].to_vec()))
+            .collect();
+        let mut current: Vec<Option<Range>> = spans.iter().copied().map(Some).collect();
+
+        for edit in edits {
+            let removed = edit.removed_range();
+            buf.splice(
+                removed.start()..removed.end(),
+                (0..edit.inserted).map(|_| 999),
+            );
+            for r in &mut current {
+                *r = r.and_then(|r| edit.apply_to(r));
+            }
+        }
+
+        // Untouched spans keep their content, touched ones keep the surviving part.
+        assert_eq!(current[0], None);
+        let r1 = current[1].unwrap();
+        assert_eq!(Some(buf[r1.start()..r1.end()].to_vec()), expected[1]);
+        let r3 = current[3].unwrap();
+        assert_eq!(Some(buf[r3.start()..r3.end()].to_vec()), expected[3]);
+        let r2 = current[2].unwrap();
+        assert!(
+            buf[r2.start()..r2.end()]
+                .iter()
+                .all(|&x| x == 999 || (10..20).contains(&x))
+        );
+    }
+}
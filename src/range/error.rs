@@ -0,0 +1,242 @@
+//! Error types returned by fallible [`Range`](super::Range) operations.
+
+use core::fmt::{Display, Formatter};
+use core::num::ParseIntError;
+
+/// The error returned when a range would be inverted (`start > end`).
+///
+/// Returned by [`Range::try_new`](super::Range::try_new) and reused by the parsing
+/// and deserialization paths so validation errors look the same everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidRange {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl InvalidRange {
+    /// Returns the rejected start.
+    #[must_use]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the rejected end.
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl Display for InvalidRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "invalid range {}..{}: start is greater than end",
+            self.start, self.end
+        )
+    }
+}
+
+impl core::error::Error for InvalidRange {}
+
+/// The error returned when converting a [`RangeInclusive<usize>`](core::ops::RangeInclusive)
+/// whose end is `usize::MAX` into a [`Range`](super::Range), since the exclusive end
+/// would overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryFromRangeInclusiveError(pub(crate) ());
+
+impl Display for TryFromRangeInclusiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "inclusive range end is usize::MAX, exclusive end would overflow"
+        )
+    }
+}
+
+impl core::error::Error for TryFromRangeInclusiveError {}
+
+/// The error returned when parsing a [`Range`](super::Range) from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRangeError {
+    /// The input contains neither `..` nor `..=`.
+    MissingSeparator,
+    /// One of the bounds is not a valid `usize`.
+    InvalidInteger(ParseIntError),
+    /// The input uses `..=` with an end of `usize::MAX`, so the exclusive end would
+    /// overflow.
+    InclusiveEndOverflow,
+    /// Both bounds parsed, but the start is greater than the end.
+    InvalidRange(InvalidRange),
+}
+
+impl Display for ParseRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::MissingSeparator => write!(f, "missing `..` or `..=` separator"),
+            Self::InvalidInteger(err) => write!(f, "invalid range bound: {err}"),
+            Self::InclusiveEndOverflow => {
+                write!(
+                    f,
+                    "inclusive range end is usize::MAX, exclusive end would overflow"
+                )
+            }
+            Self::InvalidRange(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseRangeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidInteger(err) => Some(err),
+            Self::InvalidRange(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for ParseRangeError {
+    fn from(err: ParseIntError) -> Self {
+        Self::InvalidInteger(err)
+    }
+}
+
+impl From<InvalidRange> for ParseRangeError {
+    fn from(err: InvalidRange) -> Self {
+        Self::InvalidRange(err)
+    }
+}
+
+impl From<TryFromRangeInclusiveError> for ParseRangeError {
+    fn from(_: TryFromRangeInclusiveError) -> Self {
+        Self::InclusiveEndOverflow
+    }
+}
+
+/// The error returned when a [`Range`](super::Range) does not fit into one of the
+/// compact range types like [`Range32`](super::Range32).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryFromRangeError(pub(crate) ());
+
+impl Display for TryFromRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "range bound out of range for the compact range type")
+    }
+}
+
+impl core::error::Error for TryFromRangeError {}
+
+/// The error returned by [`RangeAllocator::free`](super::RangeAllocator::free)
+/// when a range cannot be returned to the free list.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FreeError {
+    /// The range is inverted.
+    Inverted(super::Range),
+    /// The range extends outside the allocator's bounds.
+    OutOfBounds {
+        /// The offending range.
+        range: super::Range,
+        /// The allocator's bounds.
+        bounds: super::Range,
+    },
+    /// Part of the range is already free, e.g. because of a double free.
+    NotAllocated(super::Range),
+}
+
+#[cfg(feature = "alloc")]
+impl Display for FreeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Inverted(range) => write!(f, "cannot free inverted range {range}"),
+            Self::OutOfBounds { range, bounds } => {
+                write!(f, "range {range} is outside allocator bounds {bounds}")
+            }
+            Self::NotAllocated(range) => {
+                write!(f, "range {range} is not allocated")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for FreeError {}
+
+/// The error returned by [`RangeMap::insert`](super::RangeMap::insert) when a
+/// range cannot be added to the map.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsertError {
+    /// The range is empty or inverted, so no index would map to the value.
+    Empty(super::Range),
+    /// The range overlaps an entry already in the map.
+    Overlap {
+        /// The range being inserted.
+        range: super::Range,
+        /// The first existing entry it overlaps.
+        existing: super::Range,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl Display for InsertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Empty(range) => write!(f, "cannot insert empty range {range}"),
+            Self::Overlap { range, existing } => {
+                write!(f, "range {range} overlaps existing entry {existing}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for InsertError {}
+
+/// The error returned by [`SpanStack::finish`](super::SpanStack::finish) when
+/// some spans were opened but never closed.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnclosedSpans {
+    pub(crate) starts: alloc::vec::Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl UnclosedSpans {
+    /// Returns the start offsets of the unclosed spans, outermost first.
+    #[must_use]
+    pub fn starts(&self) -> &[usize] {
+        &self.starts
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for UnclosedSpans {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{} unclosed span(s) starting at ", self.starts.len())?;
+        for (i, start) in self.starts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{start}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for UnclosedSpans {}
+
+/// The error returned when converting an empty (or inverted)
+/// [`Range`](super::Range) into a [`NonEmptyRange`](super::NonEmptyRange).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmptyRangeError(pub(crate) ());
+
+impl Display for EmptyRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "range is empty")
+    }
+}
+
+impl core::error::Error for EmptyRangeError {}
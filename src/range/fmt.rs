@@ -0,0 +1,144 @@
+//! Formatting impls for [`Range`].
+
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use super::Range;
+
+/// Fixed-capacity buffer large enough for any rendered `Range`.
+///
+/// Rendering into a buffer first lets width, alignment and fill flags apply to
+/// the whole token via [`Formatter::pad`] rather than to each number.
+struct Buf {
+    bytes: [u8; 48],
+    len: usize,
+}
+
+impl Buf {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; 48],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole `&str`s are ever written, so the contents are valid UTF-8.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for Buf {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        let dst = self.bytes.get_mut(self.len..end).ok_or(core::fmt::Error)?;
+        dst.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Renders as `start..end`, or as the interval `[start, end)` with the alternate
+/// flag (`{:#}`).
+///
+/// Width, fill and alignment apply to the whole rendered string.
+///
+/// # Example
+/// ```
+/// let r = ars::range::Range::new(2, 5);
+/// assert_eq!(format!("{r}"), "2..5");
+/// assert_eq!(format!("{r:#}"), "[2, 5)");
+/// assert_eq!(format!("{r:>6}"), "  2..5");
+/// ```
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        let mut buf = Buf::new();
+        if f.alternate() {
+            write!(buf, "[{}, {})", self.0, self.1)?;
+        } else {
+            write!(buf, "{}..{}", self.0, self.1)?;
+        }
+        f.pad(buf.as_str())
+    }
+}
+
+/// Renders as `start..end` like [`core::ops::Range`], or as
+/// `Range { start, end }` with the alternate flag (`{:#?}`).
+///
+/// Integer formatting flags such as `{:x?}` apply to each bound.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// assert_eq!(format!("{:?}", Range(2, 5)), "2..5");
+/// assert_eq!(format!("{:?}", [Range(0, 1), Range(4, 8)]), "[0..1, 4..8]");
+/// ```
+impl Debug for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        if f.alternate() {
+            f.debug_struct("Range")
+                .field("start", &self.0)
+                .field("end", &self.1)
+                .finish()
+        } else {
+            Debug::fmt(&self.0, f)?;
+            f.write_str("..")?;
+            Debug::fmt(&self.1, f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::format;
+
+    #[test]
+    fn display_plain_and_alternate() {
+        assert_eq!(format!("{}", Range(2, 5)), "2..5");
+        assert_eq!(format!("{}", Range(3, 3)), "3..3");
+        assert_eq!(format!("{:#}", Range(2, 5)), "[2, 5)");
+        assert_eq!(format!("{:#}", Range(3, 3)), "[3, 3)");
+    }
+
+    #[test]
+    fn display_pads_whole_token() {
+        let r = Range(2, 5);
+        assert_eq!(format!("{r:>10}"), "      2..5");
+        assert_eq!(format!("{r:<10}|"), "2..5      |");
+        assert_eq!(format!("{r:-^8}"), "--2..5--");
+        assert_eq!(format!("{r:>#8}"), "  [2, 5)");
+    }
+
+    #[test]
+    fn debug_range_notation() {
+        assert_eq!(format!("{:?}", Range(2, 5)), "2..5");
+        assert_eq!(format!("{:?}", Range(3, 3)), "3..3");
+        assert_eq!(format!("{:?}", Range(7, 2)), "7..2");
+        assert_eq!(format!("{:x?}", Range(10, 255)), "a..ff");
+        assert_eq!(format!("{:?}", Range(2, 5)), format!("{:?}", 2..5));
+        assert_eq!(format!("{:?}", Some(Range(1, 2))), "Some(1..2)");
+        assert_eq!(
+            format!("{:?}", std::vec![Range(0, 1), Range(4, 8)]),
+            "[0..1, 4..8]"
+        );
+    }
+
+    #[test]
+    fn debug_alternate_struct_form() {
+        assert_eq!(
+            format!("{:#?}", Range(2, 5)),
+            "Range {\n    start: 2,\n    end: 5,\n}"
+        );
+    }
+
+    #[test]
+    fn display_extremes_fit_in_buffer() {
+        let r = Range(usize::MAX, usize::MAX);
+        assert_eq!(format!("{r}"), format!("{}..{}", usize::MAX, usize::MAX));
+        assert_eq!(
+            format!("{r:#}"),
+            format!("[{}, {})", usize::MAX, usize::MAX)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Iterating the uncovered parts of a bound.
+
+use core::iter::FusedIterator;
+
+use super::Range;
+
+/// Returns an iterator over the sub-ranges of `bound` not covered by `ranges`.
+///
+/// `ranges` must be sorted and non-overlapping; touching and empty ranges are fine,
+/// and ranges may extend past `bound`.
+#[cfg_attr(
+    feature = "alloc",
+    doc = " The output of [`coalesce`](super::coalesce) or",
+    doc = " [`RangeSet::as_slice`](super::RangeSet::as_slice) always qualifies."
+)]
+/// The gaps are yielded in order, including those before the first range and after
+/// the last, and zero-length gaps are skipped. An empty or inverted `bound` has no
+/// gaps.
+///
+/// # Panics
+/// In debug builds, panics if `ranges` is unsorted or overlapping.
+///
+/// # Example
+/// ```
+/// use ars::range::{gaps, Range};
+/// let matched = [Range(2, 4), Range(4, 6), Range(8, 9)];
+/// let unmatched: Vec<Range> = gaps(&matched, Range(0, 12)).collect();
+/// assert_eq!(unmatched, [Range(0, 2), Range(6, 8), Range(9, 12)]);
+/// ```
+#[must_use]
+#[track_caller]
+pub fn gaps(ranges: &[Range], bound: Range) -> Gaps<'_> {
+    debug_assert!(
+        ranges
+            .iter()
+            .filter(|r| !r.is_empty())
+            .zip(ranges.iter().filter(|r| !r.is_empty()).skip(1))
+            .all(|(a, b)| a.end() <= b.start()),
+        "ranges must be sorted and non-overlapping"
+    );
+    Gaps::new(ranges, bound)
+}
+
+impl Range {
+    /// Returns the parts of `self` not covered by any of `exclusions`, in order.
+    ///
+    /// The exclusions may be in any order and may overlap each other; they are
+    /// sorted into a temporary copy first. See
+    /// [`subtract_sorted`](Self::subtract_sorted) to avoid the allocation.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let comments = [Range(30, 40), Range(5, 10), Range(8, 12)];
+    /// assert_eq!(
+    ///     Range(0, 35).subtract_all(&comments),
+    ///     [Range(0, 5), Range(12, 30)]
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn subtract_all(&self, exclusions: &[Range]) -> alloc::vec::Vec<Range> {
+        let mut sorted = exclusions.to_vec();
+        sorted.sort_unstable_by_key(Range::start);
+        self.subtract_sorted(&sorted).collect()
+    }
+
+    /// Returns an iterator over the parts of `self` not covered by any of
+    /// `exclusions`, which must be sorted by start but may overlap.
+    ///
+    /// # Panics
+    /// In debug builds, panics if `exclusions` is not sorted by start.
+    #[must_use]
+    #[track_caller]
+    pub fn subtract_sorted<'a>(&self, exclusions: &'a [Range]) -> Gaps<'a> {
+        debug_assert!(
+            exclusions.is_sorted_by_key(Range::start),
+            "exclusions must be sorted by start"
+        );
+        Gaps::new(exclusions, *self)
+    }
+}
+
+/// An iterator over the gaps between sorted ranges within a bound.
+///
+/// Created by [`gaps`] and [`Range::subtract_sorted`].
+#[derive(Debug, Clone)]
+pub struct Gaps<'a> {
+    ranges: core::slice::Iter<'a, Range>,
+    cursor: usize,
+    end: usize,
+}
+
+impl<'a> Gaps<'a> {
+    /// Unchecked constructor: `ranges` must be sorted by start, and may overlap.
+    fn new(ranges: &'a [Range], bound: Range) -> Self {
+        Self {
+            ranges: ranges.iter(),
+            cursor: bound.start(),
+            end: if bound.is_empty() {
+                bound.start()
+            } else {
+                bound.end()
+            },
+        }
+    }
+}
+
+impl Iterator for Gaps<'_> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        while self.cursor < self.end {
+            let Some(r) = self.ranges.next() else {
+                let gap = Range(self.cursor, self.end);
+                self.cursor = self.end;
+                return Some(gap);
+            };
+            if r.is_empty() || r.end() <= self.cursor {
+                continue;
+            }
+            let gap = Range(self.cursor, r.start().min(self.end));
+            self.cursor = r.end().min(self.end);
+            if !gap.is_empty() {
+                return Some(gap);
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Gaps<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    fn assert_tiles(ranges: &[Range], bound: Range) {
+        let mut pieces: Vec<Range> = ranges
+            .iter()
+            .filter_map(|r| r.intersect(&bound))
+            .chain(gaps(ranges, bound))
+            .collect();
+        pieces.sort_unstable();
+        let mut cursor = bound.start();
+        for piece in pieces {
+            assert_eq!(piece.start(), cursor, "{ranges:?} in {bound:?}");
+            cursor = piece.end();
+        }
+        assert_eq!(
+            cursor,
+            bound.end().max(bound.start()),
+            "{ranges:?} in {bound:?}"
+        );
+    }
+
+    #[test]
+    fn leading_inner_and_trailing_gaps() {
+        let ranges = [Range(3, 5), Range(7, 9)];
+        let found: Vec<Range> = gaps(&ranges, Range(0, 12)).collect();
+        assert_eq!(found, [Range(0, 3), Range(5, 7), Range(9, 12)]);
+        let found: Vec<Range> = gaps(&ranges, Range(3, 9)).collect();
+        assert_eq!(found, [Range(5, 7)]);
+        let found: Vec<Range> = gaps(&[], Range(2, 6)).collect();
+        assert_eq!(found, [Range(2, 6)]);
+    }
+
+    #[test]
+    fn ranges_past_the_bound() {
+        let ranges = [Range(0, 4), Range(6, 8), Range(10, 20)];
+        let found: Vec<Range> = gaps(&ranges, Range(2, 15)).collect();
+        assert_eq!(found, [Range(4, 6), Range(8, 10)]);
+        let found: Vec<Range> = gaps(&ranges, Range(21, 25)).collect();
+        assert_eq!(found, [Range(21, 25)]);
+        assert_eq!(gaps(&ranges, Range(11, 13)).next(), None);
+    }
+
+    #[test]
+    fn skips_zero_length_gaps_and_empty_input_ranges() {
+        let ranges = [
+            Range(0, 2),
+            Range(2, 4),
+            Range(5, 5),
+            Range(6, 6),
+            Range(6, 10),
+        ];
+        let found: Vec<Range> = gaps(&ranges, Range(0, 10)).collect();
+        assert_eq!(found, [Range(4, 6)]);
+        assert_eq!(gaps(&ranges, Range(3, 3)).next(), None);
+        assert_eq!(gaps(&ranges, Range(8, 2)).next(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "ranges must be sorted and non-overlapping"]
+    fn unsorted_input_panics_in_debug() {
+        let _ = gaps(&[Range(5, 8), Range(0, 2)], Range(0, 10));
+    }
+
+    #[test]
+    fn gaps_and_ranges_tile_the_bound() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut ranges = Vec::new();
+            let mut cursor = 0;
+            for _ in 0..rng.random_range(0..10) {
+                let start = cursor + rng.random_range(0..6);
+                cursor = start + rng.random_range(0..6);
+                ranges.push(Range(start, cursor));
+            }
+            let start = rng.random_range(0..40);
+            let bound = Range(start, start + rng.random_range(0..40));
+            assert_tiles(&ranges, bound);
+            assert!(gaps(&ranges, bound).all(|g| !g.is_empty()));
+        }
+    }
+
+    #[test]
+    fn subtract_sorted_edge_cases() {
+        let span = Range(10, 20);
+        let rest = |exclusions: &[Range]| span.subtract_sorted(exclusions).collect::<Vec<_>>();
+        // Exclusions entirely outside the range.
+        assert_eq!(rest(&[Range(0, 5), Range(20, 30)]), [span]);
+        assert_eq!(rest(&[]), [span]);
+        // Exclusions covering it entirely.
+        assert!(rest(&[Range(0, 30)]).is_empty());
+        assert!(rest(&[Range(10, 15), Range(15, 20)]).is_empty());
+        // Touching but not overlapping exclusions.
+        assert_eq!(rest(&[Range(5, 10), Range(20, 25)]), [span]);
+        assert_eq!(
+            rest(&[Range(12, 14), Range(14, 16)]),
+            [Range(10, 12), Range(16, 20)]
+        );
+        // Overlapping and nested exclusions produce no duplicates.
+        assert_eq!(
+            rest(&[Range(11, 15), Range(12, 13), Range(14, 17)]),
+            [Range(10, 11), Range(17, 20)]
+        );
+        assert!(Range(5, 5).subtract_sorted(&[]).next().is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn subtract_all_sorts_exclusions() {
+        let exclusions = [Range(16, 18), Range(0, 11), Range(13, 14), Range(12, 13)];
+        assert_eq!(
+            Range(10, 20).subtract_all(&exclusions),
+            [Range(11, 12), Range(14, 16), Range(18, 20)]
+        );
+        assert!(
+            Range(10, 20)
+                .subtract_all(&[Range(25, 30), Range(0, 20)])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "exclusions must be sorted by start"]
+    fn subtract_sorted_rejects_unsorted_in_debug() {
+        let _ = Range(0, 10).subtract_sorted(&[Range(5, 6), Range(1, 2)]);
+    }
+}
@@ -0,0 +1,306 @@
+//! [`IntervalTree`], an immutable index for repeated overlap queries.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use super::Range;
+
+/// Deep enough for any tree: each level halves the number of entries.
+const MAX_DEPTH: usize = usize::BITS as usize;
+
+/// An immutable collection of possibly overlapping `(Range, V)` entries, indexed
+/// for fast point and overlap queries.
+///
+/// The entries are stored in a flat array laid out as an implicit priority search
+/// tree: each subtree holds the entries with the smallest starts in its left half,
+/// and is rooted at its entry with the largest end. Queries skip any subtree
+/// ending before them or starting after them, so they cost `O(log n + k)` for `k`
+/// matches and allocate nothing. Matches are yielded in no particular order.
+///
+/// Building the tree collects and sorts the entries, costing `O(n log n)` time,
+/// and keeps one extra `usize` per entry.
+///
+/// # Example
+/// ```
+/// use ars::range::{IntervalTree, Range};
+///
+/// let symbols: IntervalTree<&str> = [
+///     (Range(0, 100), "module"),
+///     (Range(10, 40), "fn a"),
+///     (Range(50, 90), "fn b"),
+///     (Range(60, 70), "closure"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let mut at_65: Vec<&str> = symbols.query_point(65).map(|(_, s)| *s).collect();
+/// at_65.sort_unstable();
+/// assert_eq!(at_65, ["closure", "fn b", "module"]);
+/// let in_view = symbols.query_range(Range(35, 55)).count();
+/// assert_eq!(in_view, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalTree<V> {
+    /// The subtree of `n` entries rooted at `i` has a left subtree of
+    /// `(n - 1) / 2` entries at `i + 1`, followed by its right subtree.
+    entries: Vec<(Range, V)>,
+    /// `min_start[i]` is the smallest start in the subtree rooted at entry `i`.
+    min_start: Vec<usize>,
+}
+
+/// The end of `r` as a heap key; empty and inverted ranges match nothing, so
+/// they sort below every query.
+fn priority(r: Range) -> usize {
+    if r.is_empty() { 0 } else { r.end() }
+}
+
+/// Returns the sizes of the left and right subtrees of a subtree of `n > 0`
+/// entries.
+fn child_sizes(n: usize) -> (usize, usize) {
+    let left = (n - 1) / 2;
+    (left, n - 1 - left)
+}
+
+impl<V> IntervalTree<V> {
+    fn build(mut entries: Vec<(Range, V)>) -> Self {
+        entries.sort_unstable_by_key(|(r, _)| *r);
+        let ranges: Vec<Range> = entries.iter().map(|(r, _)| *r).collect();
+        let mut sorted: Vec<usize> = (0..entries.len()).collect();
+        let mut order = alloc::vec![0; entries.len()];
+        let mut min_start = alloc::vec![0; entries.len()];
+        lay_out(&ranges, &mut sorted, &mut order, &mut min_start);
+
+        let mut slots: Vec<Option<(Range, V)>> = entries.into_iter().map(Some).collect();
+        let entries = order
+            .iter()
+            .map(|&i| slots[i].take().expect("layout visits each entry once"))
+            .collect();
+        Self { entries, min_start }
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the tree has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over every entry, in no particular order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range, &V)> + ExactSizeIterator {
+        self.entries.iter().map(|(r, v)| (*r, v))
+    }
+
+    /// Returns the entries whose range contains `index`, in no particular order.
+    pub fn query_point(&self, index: usize) -> IntervalQuery<'_, V> {
+        // No range contains `usize::MAX`, and the saturated query is empty.
+        self.query_range(Range(index, index.saturating_add(1)))
+    }
+
+    /// Returns the entries whose range overlaps `query`, in no particular order.
+    ///
+    /// Empty and inverted ranges, as queries or as entries, overlap nothing.
+    pub fn query_range(&self, query: Range) -> IntervalQuery<'_, V> {
+        let mut q = IntervalQuery {
+            tree: self,
+            query,
+            stack: [(0, 0); MAX_DEPTH],
+            depth: 0,
+        };
+        if !query.is_empty() {
+            q.push(0, self.entries.len());
+        }
+        q
+    }
+}
+
+/// Lays out the entries listed in `sorted`, ordered by range, as the subtree
+/// filling `order`, recording each node's smallest start in `min_start`.
+fn lay_out(ranges: &[Range], sorted: &mut [usize], order: &mut [usize], min_start: &mut [usize]) {
+    let Some(&first) = sorted.first() else {
+        return;
+    };
+    min_start[0] = ranges[first].start();
+    // The first entry with the largest priority becomes the root.
+    let root = (0..sorted.len())
+        .rev()
+        .max_by_key(|&i| priority(ranges[sorted[i]]))
+        .expect("subtree is non-empty");
+    order[0] = sorted[root];
+    sorted[root..].rotate_left(1);
+
+    let (left, _) = child_sizes(sorted.len());
+    let rest = sorted.len() - 1;
+    let (sorted_left, sorted_right) = sorted[..rest].split_at_mut(left);
+    let (order_left, order_right) = order[1..].split_at_mut(left);
+    let (min_left, min_right) = min_start[1..].split_at_mut(left);
+    lay_out(ranges, sorted_left, order_left, min_left);
+    lay_out(ranges, sorted_right, order_right, min_right);
+}
+
+impl<V> FromIterator<(Range, V)> for IntervalTree<V> {
+    fn from_iter<I: IntoIterator<Item = (Range, V)>>(iter: I) -> Self {
+        Self::build(iter.into_iter().collect())
+    }
+}
+
+impl<V> From<Vec<(Range, V)>> for IntervalTree<V> {
+    fn from(entries: Vec<(Range, V)>) -> Self {
+        Self::build(entries)
+    }
+}
+
+/// An iterator over the entries of an [`IntervalTree`] matching a query.
+///
+/// Created by [`IntervalTree::query_point`] and [`IntervalTree::query_range`].
+#[derive(Debug)]
+pub struct IntervalQuery<'a, V> {
+    tree: &'a IntervalTree<V>,
+    query: Range,
+    /// Pending subtrees as `(root, number of entries)`.
+    stack: [(usize, usize); MAX_DEPTH],
+    depth: usize,
+}
+
+impl<V> IntervalQuery<'_, V> {
+    /// Pushes the subtree of `len` entries at `root`, unless none of its entries
+    /// can overlap the query.
+    fn push(&mut self, root: usize, len: usize) {
+        if len == 0
+            || priority(self.tree.entries[root].0) <= self.query.start()
+            || self.tree.min_start[root] >= self.query.end()
+        {
+            return;
+        }
+        self.stack[self.depth] = (root, len);
+        self.depth += 1;
+    }
+}
+
+impl<V> Clone for IntervalQuery<'_, V> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, V> Iterator for IntervalQuery<'a, V> {
+    type Item = (Range, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.depth > 0 {
+            self.depth -= 1;
+            let (root, len) = self.stack[self.depth];
+            let (left, right) = child_sizes(len);
+            self.push(root + 1 + left, right);
+            self.push(root + 1, left);
+            let (r, v) = &self.tree.entries[root];
+            if r.overlaps(&self.query) {
+                return Some((*r, v));
+            }
+        }
+        None
+    }
+}
+
+impl<V> FusedIterator for IntervalQuery<'_, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    fn sample() -> IntervalTree<usize> {
+        [
+            Range(0, 100),
+            Range(10, 40),
+            Range(50, 90),
+            Range(60, 70),
+            Range(95, 96),
+            Range(40, 40),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, r)| (r, i))
+        .collect()
+    }
+
+    /// Returns the values of `matches`, sorted.
+    fn values<'a>(matches: impl Iterator<Item = (Range, &'a usize)>) -> Vec<usize> {
+        let mut values: Vec<usize> = matches.map(|(_, v)| *v).collect();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn point_queries() {
+        let tree = sample();
+        assert_eq!(tree.len(), 6);
+        assert_eq!(values(tree.query_point(0)), [0]);
+        assert_eq!(values(tree.query_point(10)), [0, 1]);
+        assert_eq!(values(tree.query_point(40)), [0]);
+        assert_eq!(values(tree.query_point(65)), [0, 2, 3]);
+        assert_eq!(values(tree.query_point(95)), [0, 4]);
+        assert!(values(tree.query_point(100)).is_empty());
+        assert!(values(tree.query_point(usize::MAX)).is_empty());
+    }
+
+    #[test]
+    fn range_queries() {
+        let tree = sample();
+        assert_eq!(values(tree.query_range(Range(35, 55))), [0, 1, 2]);
+        // Ending exactly at an entry start, or starting exactly at its end.
+        assert_eq!(values(tree.query_range(Range(40, 50))), [0]);
+        assert_eq!(values(tree.query_range(Range(90, 95))), [0]);
+        assert_eq!(values(tree.query_range(Range(0, 1000))), [0, 1, 2, 3, 4]);
+        assert!(values(tree.query_range(Range(100, 200))).is_empty());
+        assert!(values(tree.query_range(Range(20, 20))).is_empty());
+        assert!(values(tree.query_range(Range(60, 20))).is_empty());
+
+        let empty: IntervalTree<()> = IntervalTree::from(Vec::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.query_range(Range(0, 10)).next(), None);
+    }
+
+    #[test]
+    fn randomized_against_brute_force() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        for seed in 0..100 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let entries: Vec<(Range, usize)> = (0..rng.random_range(0..200))
+                .map(|i| {
+                    let start = rng.random_range(0..500);
+                    (Range(start, start + rng.random_range(0..60)), i)
+                })
+                .collect();
+            let tree = IntervalTree::from(entries.clone());
+            let mut all: Vec<(Range, usize)> = tree.iter().map(|(r, v)| (r, *v)).collect();
+            all.sort_unstable_by_key(|(_, v)| *v);
+            assert_eq!(all, entries);
+
+            for _ in 0..50 {
+                let start = rng.random_range(0..600);
+                let query = Range(start, start + rng.random_range(0..80));
+                let expected: Vec<usize> = entries
+                    .iter()
+                    .filter(|(r, _)| r.overlaps(&query))
+                    .map(|(_, v)| *v)
+                    .collect();
+                assert_eq!(values(tree.query_range(query)), expected, "{query:?}");
+
+                let expected: Vec<usize> = entries
+                    .iter()
+                    .filter(|(r, _)| r.contains(start))
+                    .map(|(_, v)| *v)
+                    .collect();
+                assert_eq!(values(tree.query_point(start)), expected, "{start}");
+            }
+        }
+    }
+}
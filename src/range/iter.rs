@@ -0,0 +1,902 @@
+//! Iterators over [`Range`] values.
+
+use core::iter::FusedIterator;
+
+use super::Range;
+
+/// An iterator over the indices covered by a [`Range`].
+///
+/// Created by [`Range::iter`] or by iterating a `Range` (or `&Range`) directly.
+/// Inverted ranges yield no indices, consistent with [`Range::len`] returning 0.
+///
+/// # Example
+/// ```
+/// let r = ars::range::Range::new(2, 5);
+/// let indices: Vec<usize> = r.into_iter().collect();
+/// assert_eq!(indices, [2, 3, 4]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Iter {
+    front: usize,
+    back: usize,
+}
+
+impl Iter {
+    #[inline]
+    pub(crate) const fn new(range: Range) -> Self {
+        // Normalize inverted ranges to empty so `len` stays exact.
+        let back = if range.1 < range.0 { range.0 } else { range.1 };
+        Self {
+            front: range.0,
+            back,
+        }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let index = self.front;
+            self.front += 1;
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.back - self.front {
+            self.front += n;
+            self.next()
+        } else {
+            self.front = self.back;
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.back)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl FusedIterator for Iter {}
+
+/// An iterator over consecutive sub-ranges of a [`Range`], each `size` long
+/// except possibly the last.
+///
+/// Created by [`Range::chunks`].
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// let chunks: Vec<Range> = Range::new(0, 7).chunks(3).collect();
+/// assert_eq!(chunks, [Range(0, 3), Range(3, 6), Range(6, 7)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chunks {
+    remaining: Range,
+    size: usize,
+}
+
+impl Chunks {
+    #[inline]
+    pub(crate) const fn new(range: Range, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        let end = if range.1 < range.0 { range.0 } else { range.1 };
+        Self {
+            remaining: Range(range.0, end),
+            size,
+        }
+    }
+}
+
+impl Iterator for Chunks {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let len = if self.remaining.len() < self.size {
+            self.remaining.len()
+        } else {
+            self.size
+        };
+        let chunk = Range(self.remaining.0, self.remaining.0 + len);
+        self.remaining.0 = chunk.1;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining.len().div_ceil(self.size);
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for Chunks {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let len = match self.remaining.len() % self.size {
+            0 => self.size,
+            rem => rem,
+        };
+        let chunk = Range(self.remaining.1 - len, self.remaining.1);
+        self.remaining.1 = chunk.0;
+        Some(chunk)
+    }
+}
+
+impl ExactSizeIterator for Chunks {}
+
+impl FusedIterator for Chunks {}
+
+/// An iterator over consecutive sub-ranges of a [`Range`] that are exactly
+/// `size` long, leaving any shorter tail to [`remainder`](Self::remainder).
+///
+/// Created by [`Range::chunks_exact`].
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// let mut chunks = Range::new(0, 7).chunks_exact(3);
+/// assert_eq!(chunks.remainder(), Range(6, 7));
+/// assert!(chunks.eq([Range(0, 3), Range(3, 6)]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChunksExact {
+    remaining: Range,
+    remainder: Range,
+    size: usize,
+}
+
+impl ChunksExact {
+    #[inline]
+    pub(crate) const fn new(range: Range, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        let len = range.len();
+        let split = range.0 + (len - len % size);
+        Self {
+            remaining: Range(range.0, split),
+            remainder: Range(split, split + len % size),
+            size,
+        }
+    }
+
+    /// Returns the tail shorter than `size` that is never yielded.
+    ///
+    /// It is empty if `size` divides the length, and unaffected by iteration.
+    #[must_use]
+    #[inline]
+    pub const fn remainder(&self) -> Range {
+        self.remainder
+    }
+}
+
+impl Iterator for ChunksExact {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let chunk = Range(self.remaining.0, self.remaining.0 + self.size);
+        self.remaining.0 = chunk.1;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining.len() / self.size;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for ChunksExact {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let chunk = Range(self.remaining.1 - self.size, self.remaining.1);
+        self.remaining.1 = chunk.0;
+        Some(chunk)
+    }
+}
+
+impl ExactSizeIterator for ChunksExact {}
+
+impl FusedIterator for ChunksExact {}
+
+/// An iterator over consecutive sub-ranges of a [`Range`] starting from the end,
+/// each `size` long except possibly the last, which is at the start.
+///
+/// Created by [`Range::rchunks`].
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// let chunks: Vec<Range> = Range::new(0, 7).rchunks(3).collect();
+/// assert_eq!(chunks, [Range(4, 7), Range(1, 4), Range(0, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RChunks {
+    remaining: Range,
+    size: usize,
+}
+
+impl RChunks {
+    #[inline]
+    pub(crate) const fn new(range: Range, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        let end = if range.1 < range.0 { range.0 } else { range.1 };
+        Self {
+            remaining: Range(range.0, end),
+            size,
+        }
+    }
+}
+
+impl Iterator for RChunks {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let len = if self.remaining.len() < self.size {
+            self.remaining.len()
+        } else {
+            self.size
+        };
+        let chunk = Range(self.remaining.1 - len, self.remaining.1);
+        self.remaining.1 = chunk.0;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining.len().div_ceil(self.size);
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for RChunks {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let len = match self.remaining.len() % self.size {
+            0 => self.size,
+            rem => rem,
+        };
+        let chunk = Range(self.remaining.0, self.remaining.0 + len);
+        self.remaining.0 = chunk.1;
+        Some(chunk)
+    }
+}
+
+impl ExactSizeIterator for RChunks {}
+
+impl FusedIterator for RChunks {}
+
+/// An iterator dividing a [`Range`] into `n` contiguous parts whose lengths
+/// differ by at most one.
+///
+/// Created by [`Range::split_evenly`]. The remainder is distributed across the
+/// first parts, and if `n` exceeds the length the trailing parts are empty ranges
+/// positioned at the end.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// let parts: Vec<Range> = Range::new(0, 10).split_evenly(3).collect();
+/// assert_eq!(parts, [Range(0, 4), Range(4, 7), Range(7, 10)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SplitEvenly {
+    start: usize,
+    base: usize,
+    rem: usize,
+    front: usize,
+    back: usize,
+}
+
+impl SplitEvenly {
+    #[inline]
+    pub(crate) const fn new(range: Range, n: usize) -> Self {
+        assert!(n != 0, "number of parts must be non-zero");
+        let len = range.len();
+        Self {
+            start: range.0,
+            base: len / n,
+            rem: len % n,
+            front: 0,
+            back: n,
+        }
+    }
+
+    /// Returns the start of part `i`.
+    #[inline]
+    const fn part_start(&self, i: usize) -> usize {
+        let extra = if i < self.rem { i } else { self.rem };
+        self.start + i * self.base + extra
+    }
+
+    #[inline]
+    const fn part(&self, i: usize) -> Range {
+        Range(self.part_start(i), self.part_start(i + 1))
+    }
+}
+
+impl Iterator for SplitEvenly {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let part = self.part(self.front);
+            self.front += 1;
+            Some(part)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for SplitEvenly {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.part(self.back))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for SplitEvenly {}
+
+impl FusedIterator for SplitEvenly {}
+
+/// An iterator over every `step`-th index of a [`Range`], starting at its start.
+///
+/// Created by [`Range::iter_step`].
+///
+/// # Example
+/// ```
+/// let indices: Vec<usize> = ars::range::Range::new(2, 10).iter_step(3).collect();
+/// assert_eq!(indices, [2, 5, 8]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StepIter {
+    start: usize,
+    step: usize,
+    front: usize,
+    back: usize,
+}
+
+impl StepIter {
+    #[inline]
+    pub(crate) const fn new(range: Range, step: usize) -> Self {
+        assert!(step != 0, "step must be non-zero");
+        Self {
+            start: range.0,
+            step,
+            front: 0,
+            back: range.len().div_ceil(step),
+        }
+    }
+}
+
+impl Iterator for StepIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let index = self.start + self.front * self.step;
+            self.front += 1;
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.back - self.front {
+            self.front += n;
+            self.next()
+        } else {
+            self.front = self.back;
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for StepIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.start + self.back * self.step)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for StepIter {}
+
+impl FusedIterator for StepIter {}
+
+/// An iterator over overlapping sub-ranges of a [`Range`], each exactly `size`
+/// long, stepping by one.
+///
+/// Created by [`Range::windows`].
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// let windows: Vec<Range> = Range::new(0, 4).windows(2).collect();
+/// assert_eq!(windows, [Range(0, 2), Range(1, 3), Range(2, 4)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Windows {
+    /// Start positions of the windows not yet yielded.
+    starts: Iter,
+    size: usize,
+}
+
+impl Windows {
+    #[inline]
+    pub(crate) const fn new(range: Range, size: usize) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        let count = if size <= range.len() {
+            range.len() - size + 1
+        } else {
+            0
+        };
+        Self {
+            starts: Iter::new(Range(range.0, range.0 + count)),
+            size,
+        }
+    }
+}
+
+impl Iterator for Windows {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.starts.next().map(|s| Range(s, s + self.size))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.starts.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.starts.nth(n).map(|s| Range(s, s + self.size))
+    }
+}
+
+impl DoubleEndedIterator for Windows {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.starts.next_back().map(|s| Range(s, s + self.size))
+    }
+}
+
+impl ExactSizeIterator for Windows {}
+
+impl FusedIterator for Windows {}
+
+impl IntoIterator for Range {
+    type Item = usize;
+    type IntoIter = Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self)
+    }
+}
+
+impl IntoIterator for &Range {
+    type Item = usize;
+    type IntoIter = Iter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+
+    #[test]
+    fn iterates_indices_in_order() {
+        let r = Range::new(2, 5);
+        let mut iter = r.into_iter();
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.next(), None);
+        // Fused: keeps returning None.
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn iterates_by_ref() {
+        let r = Range::new(0, 3);
+        let mut sum = 0;
+        for i in &r {
+            sum += i;
+        }
+        assert_eq!(sum, 3);
+        // `r` is still usable afterwards.
+        assert_eq!(r.len(), 3);
+    }
+
+    #[test]
+    fn double_ended_and_exact_size() {
+        let mut iter = Range::new(10, 14).iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(13));
+        assert_eq!(iter.next(), Some(10));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next_back(), Some(12));
+        assert_eq!(iter.next_back(), Some(11));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn inverted_and_empty_ranges_yield_nothing() {
+        let mut inverted = Range::new(5, 3).iter();
+        assert_eq!(inverted.len(), 0);
+        assert_eq!(inverted.next(), None);
+        assert_eq!(inverted.next_back(), None);
+
+        assert_eq!(Range::new(4, 4).iter().count(), 0);
+    }
+
+    #[test]
+    fn nth_skips_and_exhausts() {
+        let mut iter = Range::new(0, 5).iter();
+        assert_eq!(iter.nth(2), Some(2));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn chunks_tile_the_range() {
+        for len in 0..20 {
+            for size in 1..8 {
+                let r = Range::new(5, 5 + len);
+                let chunks = r.chunks(size);
+                assert_eq!(chunks.len(), len.div_ceil(size));
+
+                let mut expected_start = r.start();
+                for chunk in chunks {
+                    assert_eq!(chunk.start(), expected_start);
+                    assert!(!chunk.is_empty());
+                    assert!(chunk.len() <= size);
+                    expected_start = chunk.end();
+                }
+                assert_eq!(expected_start, r.end());
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_double_ended() {
+        let r = Range::new(0, 10);
+        let mut chunks = r.chunks(4);
+        assert_eq!(chunks.next_back(), Some(Range(8, 10)));
+        assert_eq!(chunks.next(), Some(Range(0, 4)));
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks.next_back(), Some(Range(4, 8)));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next_back(), None);
+
+        assert!(
+            r.chunks(3)
+                .rev()
+                .eq([Range(9, 10), Range(6, 9), Range(3, 6), Range(0, 3)])
+        );
+    }
+
+    #[test]
+    fn chunks_of_empty_or_inverted_range() {
+        assert_eq!(Range::new(3, 3).chunks(2).next(), None);
+        assert_eq!(Range::new(7, 3).chunks(2).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_zero_size_panics() {
+        let _ = Range::new(0, 4).chunks(0);
+    }
+
+    #[test]
+    fn chunks_exact_and_remainder_tile_the_range() {
+        for len in 0..30 {
+            for size in 1..8 {
+                let r = Range::new(5, 5 + len);
+                let chunks = r.chunks_exact(size);
+                let remainder = chunks.remainder();
+                assert_eq!(chunks.len(), len / size);
+                assert_eq!(remainder.len(), len % size);
+                assert_eq!(remainder.end(), r.end());
+
+                let mut cursor = r.start();
+                for chunk in chunks.clone() {
+                    assert_eq!(chunk.start(), cursor);
+                    assert_eq!(chunk.len(), size);
+                    cursor = chunk.end();
+                }
+                assert_eq!(cursor, remainder.start());
+
+                let mut exhausted = chunks.clone();
+                exhausted.by_ref().for_each(drop);
+                assert_eq!(exhausted.remainder(), remainder);
+                assert!(
+                    chunks
+                        .clone()
+                        .rev()
+                        .eq(chunks.collect::<std::vec::Vec<_>>().into_iter().rev())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_exact_double_ended() {
+        let mut chunks = Range::new(0, 10).chunks_exact(3);
+        assert_eq!(chunks.next_back(), Some(Range(6, 9)));
+        assert_eq!(chunks.next(), Some(Range(0, 3)));
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks.next_back(), Some(Range(3, 6)));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next_back(), None);
+        assert_eq!(chunks.remainder(), Range(9, 10));
+
+        let mut inverted = Range::new(7, 3).chunks_exact(2);
+        assert_eq!(inverted.next(), None);
+        assert!(inverted.remainder().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_exact_zero_size_panics() {
+        let _ = Range::new(0, 4).chunks_exact(0);
+    }
+
+    #[test]
+    fn rchunks_mirror_slice_rchunks() {
+        use std::vec::Vec;
+
+        let data: Vec<usize> = (0..20).collect();
+        for len in 0..20 {
+            for size in 1..7 {
+                let r = Range::new(0, len);
+                let ours: Vec<&[usize]> = r.rchunks(size).map(|c| &data.as_slice()[c]).collect();
+                let core: Vec<&[usize]> = data[..len].rchunks(size).collect();
+                assert_eq!(ours, core);
+                assert!(
+                    r.rchunks(size)
+                        .rev()
+                        .eq(r.rchunks(size).collect::<Vec<_>>().into_iter().rev())
+                );
+                assert_eq!(r.rchunks(size).len(), r.chunks(size).len());
+            }
+        }
+    }
+
+    #[test]
+    fn rchunks_differ_from_reversed_chunks() {
+        use std::vec::Vec;
+
+        // With a remainder, the short chunk moves from the end to the start.
+        let r = Range::new(0, 10);
+        let reversed: Vec<Range> = r.chunks(4).rev().collect();
+        assert_eq!(reversed, [Range(8, 10), Range(4, 8), Range(0, 4)]);
+        let rchunks: Vec<Range> = r.rchunks(4).collect();
+        assert_eq!(rchunks, [Range(6, 10), Range(2, 6), Range(0, 2)]);
+        // They only agree when the size divides the length.
+        assert!(r.rchunks(5).eq(r.chunks(5).rev()));
+
+        let mut it = Range::new(3, 10).rchunks(3);
+        assert_eq!(it.next_back(), Some(Range(3, 4)));
+        assert_eq!(it.next(), Some(Range(7, 10)));
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.next_back(), Some(Range(4, 7)));
+        assert_eq!(it.next(), None);
+        assert_eq!(Range::new(7, 3).rchunks(2).next(), None);
+    }
+
+    #[test]
+    fn split_evenly_properties() {
+        for len in 0..40 {
+            for n in 1..12 {
+                let r = Range::new(3, 3 + len);
+                let parts = r.split_evenly(n);
+                assert_eq!(parts.len(), n);
+
+                let mut expected_start = r.start();
+                let mut min = usize::MAX;
+                let mut max = 0;
+                for part in parts {
+                    assert_eq!(part.start(), expected_start);
+                    assert!(part.start() <= part.end());
+                    min = min.min(part.len());
+                    max = max.max(part.len());
+                    expected_start = part.end();
+                }
+                assert_eq!(expected_start, r.end());
+                assert!(max - min <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn split_evenly_more_parts_than_len() {
+        let parts: std::vec::Vec<_> = Range::new(0, 2).split_evenly(4).collect();
+        assert_eq!(parts, [Range(0, 1), Range(1, 2), Range(2, 2), Range(2, 2)]);
+    }
+
+    #[test]
+    fn split_evenly_double_ended() {
+        let r = Range::new(0, 11);
+        let forward: std::vec::Vec<_> = r.split_evenly(4).collect();
+        let mut backward: std::vec::Vec<_> = r.split_evenly(4).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(
+            forward,
+            [Range(0, 3), Range(3, 6), Range(6, 9), Range(9, 11)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "number of parts must be non-zero")]
+    fn split_evenly_zero_parts_panics() {
+        let _ = Range::new(0, 4).split_evenly(0);
+    }
+
+    #[test]
+    fn iter_step_matches_core_step_by() {
+        for start in 0..5 {
+            for len in 0..20 {
+                for step in 1..7 {
+                    let r = Range::new(start, start + len);
+                    let iter = r.iter_step(step);
+                    assert_eq!(iter.len(), (start..start + len).step_by(step).len());
+                    assert!(iter.clone().eq((start..start + len).step_by(step)));
+                    assert!(iter.rev().eq((start..start + len).step_by(step).rev()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn iter_step_larger_than_range_and_empty() {
+        let mut iter = Range::new(4, 7).iter_step(10);
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(4));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(Range::new(4, 4).iter_step(2).len(), 0);
+        assert_eq!(Range::new(9, 4).iter_step(2).next(), None);
+    }
+
+    #[test]
+    fn iter_step_near_usize_max() {
+        let r = Range::new(usize::MAX - 5, usize::MAX);
+        assert!(
+            r.iter_step(2)
+                .eq([usize::MAX - 5, usize::MAX - 3, usize::MAX - 1])
+        );
+        assert_eq!(r.iter_step(2).next_back(), Some(usize::MAX - 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be non-zero")]
+    fn iter_step_zero_panics() {
+        let _ = Range::new(0, 4).iter_step(0);
+    }
+
+    #[test]
+    fn windows_match_slice_windows() {
+        let owned: std::vec::Vec<usize> = (0..12).collect();
+        let buf = owned.as_slice();
+        for s in 0..4 {
+            for e in s..buf.len() {
+                for size in 1..6 {
+                    let r = Range::new(s, e);
+                    let windows = r.windows(size);
+                    let expected = buf[r].windows(size);
+                    assert_eq!(windows.len(), expected.len());
+                    for (w, x) in windows.clone().zip(expected) {
+                        assert_eq!(&buf[w], x);
+                    }
+                    assert!(
+                        windows
+                            .rev()
+                            .map(|w| &buf[w])
+                            .eq(buf[r].windows(size).rev())
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn windows_larger_than_range_or_inverted() {
+        assert_eq!(Range::new(0, 3).windows(4).next(), None);
+        assert_eq!(Range::new(0, 3).windows(3).len(), 1);
+        assert_eq!(Range::new(5, 2).windows(1).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn windows_zero_size_panics() {
+        let _ = Range::new(0, 4).windows(0);
+    }
+
+    #[test]
+    fn matches_core_range() {
+        let r = Range::new(3, 9);
+        assert!(r.iter().eq(3..9));
+        assert!(r.iter().rev().eq((3..9).rev()));
+    }
+}
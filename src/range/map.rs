@@ -0,0 +1,607 @@
+//! [`RangeMap`], an interval map from disjoint [`Range`]s to values.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use super::{InsertError, Range};
+
+/// A map from non-overlapping ranges to values, sorted by start.
+///
+/// Each entry associates every index of its (non-empty) range with a value.
+/// Lookups by index are binary searches. Adjacent entries are kept separate, even
+/// when their values are equal.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, RangeMap};
+///
+/// let mut styles = RangeMap::new();
+/// styles.insert(Range(0, 5), "keyword").unwrap();
+/// styles.insert(Range(6, 11), "ident").unwrap();
+/// assert_eq!(styles.get(2), Some(&"keyword"));
+/// assert_eq!(styles.get(5), None);
+/// assert_eq!(styles.get_key_value(8), Some((Range(6, 11), &"ident")));
+/// assert!(styles.insert(Range(4, 7), "string").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<V> {
+    entries: Vec<(Range, V)>,
+}
+
+impl<V> Default for RangeMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> RangeMap<V> {
+    /// Creates an empty map.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes every entry from the map.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the index in `entries` of the first entry ending after `index`.
+    fn first_ending_after(&self, index: usize) -> usize {
+        self.entries.partition_point(|(r, _)| r.end() <= index)
+    }
+
+    /// Maps every index of `range` to `value`.
+    ///
+    /// # Errors
+    /// Returns [`InsertError::Empty`] if `range` is empty or inverted, and
+    /// [`InsertError::Overlap`] if it overlaps an existing entry. The map is left
+    /// unchanged in both cases.
+    pub fn insert(&mut self, range: Range, value: V) -> Result<(), InsertError> {
+        if range.is_empty() {
+            return Err(InsertError::Empty(range));
+        }
+        let i = self.first_ending_after(range.start());
+        if let Some(&(existing, _)) = self.entries.get(i)
+            && existing.start() < range.end()
+        {
+            return Err(InsertError::Overlap { range, existing });
+        }
+        self.entries.insert(i, (range, value));
+        Ok(())
+    }
+
+    /// Returns the value of the entry covering `index`.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&V> {
+        self.get_key_value(index).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value of the entry covering `index`.
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut V> {
+        let i = self.first_ending_after(index);
+        match self.entries.get_mut(i) {
+            Some((r, v)) if r.start() <= index => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the entry covering `index`, with its range.
+    #[must_use]
+    pub fn get_key_value(&self, index: usize) -> Option<(Range, &V)> {
+        let i = self.first_ending_after(index);
+        match self.entries.get(i) {
+            Some((r, v)) if r.start() <= index => Some((*r, v)),
+            _ => None,
+        }
+    }
+
+    /// Removes the entry whose range is exactly `range`, returning its value.
+    pub fn remove(&mut self, range: Range) -> Option<V> {
+        let i = self
+            .entries
+            .binary_search_by_key(&range.start(), |(r, _)| r.start())
+            .ok()?;
+        if self.entries[i].0 == range {
+            Some(self.entries.remove(i).1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the positions in `entries` of the entries overlapping `query`.
+    fn overlapping_positions(&self, query: Range) -> core::ops::Range<usize> {
+        if query.is_empty() {
+            return 0..0;
+        }
+        let first = self.first_ending_after(query.start());
+        let len = self.entries[first..].partition_point(|(r, _)| r.start() < query.end());
+        first..first + len
+    }
+
+    /// Returns the entries whose ranges overlap `query`, in order.
+    ///
+    /// This costs `O(log n + k)` for `k` matches. Empty and inverted queries
+    /// overlap nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeMap};
+    /// let mut map = RangeMap::new();
+    /// map.insert(Range(0, 10), 'a').unwrap();
+    /// map.insert(Range(10, 20), 'b').unwrap();
+    /// map.insert(Range(30, 40), 'c').unwrap();
+    /// let visible: Vec<char> = map.overlapping(Range(5, 30)).map(|(_, v)| *v).collect();
+    /// assert_eq!(visible, ['a', 'b']);
+    /// ```
+    pub fn overlapping(&self, query: Range) -> impl DoubleEndedIterator<Item = (Range, &V)> {
+        self.entries[self.overlapping_positions(query)]
+            .iter()
+            .map(|(r, v)| (*r, v))
+    }
+
+    /// Returns the entries whose ranges overlap `query`, in order, with mutable
+    /// references to their values.
+    pub fn overlapping_mut(
+        &mut self,
+        query: Range,
+    ) -> impl DoubleEndedIterator<Item = (Range, &mut V)> {
+        let positions = self.overlapping_positions(query);
+        self.entries[positions].iter_mut().map(|(r, v)| (*r, v))
+    }
+
+    /// Returns the first entry whose range overlaps `query`.
+    #[must_use]
+    pub fn first_overlap(&self, query: Range) -> Option<(Range, &V)> {
+        self.overlapping(query).next()
+    }
+
+    /// Returns the maximal sub-ranges of `within` that no entry covers, in order.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeMap};
+    /// let mut map = RangeMap::new();
+    /// map.insert(Range(2, 4), ()).unwrap();
+    /// map.insert(Range(6, 12), ()).unwrap();
+    /// let gaps: Vec<Range> = map.gaps(Range(0, 10)).collect();
+    /// assert_eq!(gaps, [Range(0, 2), Range(4, 6)]);
+    /// ```
+    pub fn gaps(&self, within: Range) -> impl Iterator<Item = Range> + '_ {
+        let mut cursor = within.start();
+        self.overlapping(within)
+            .map(|(r, _)| r)
+            .chain([Range(within.end(), within.end())])
+            .filter_map(move |r| {
+                let gap = Range(cursor, r.start());
+                cursor = r.end();
+                (!gap.is_empty()).then_some(gap)
+            })
+    }
+
+    /// Returns an iterator over the entries in order of their ranges.
+    pub fn iter(&self) -> MapIter<'_, V> {
+        MapIter {
+            entries: self.entries.iter(),
+        }
+    }
+}
+
+impl<V: Clone + PartialEq> RangeMap<V> {
+    /// Maps every index of `range` to `value`, overwriting what was there.
+    ///
+    /// Existing entries are truncated, or split in two when `range` falls inside
+    /// one. The new entry is merged with neighbors it touches that hold an equal
+    /// value, so a map built only with this method never has two adjacent entries
+    /// with equal values. Empty and inverted ranges are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeMap};
+    /// let mut styles = RangeMap::new();
+    /// styles.insert_replace(Range(0, 20), "plain");
+    /// styles.insert_replace(Range(5, 15), "bold");
+    /// let entries: Vec<_> = styles.iter().collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     [(Range(0, 5), &"plain"), (Range(5, 15), &"bold"), (Range(15, 20), &"plain")]
+    /// );
+    /// styles.insert_replace(Range(5, 15), "plain");
+    /// assert_eq!(styles.iter().collect::<Vec<_>>(), [(Range(0, 20), &"plain")]);
+    /// ```
+    pub fn insert_replace(&mut self, range: Range, value: V) {
+        if range.is_empty() {
+            return;
+        }
+        let core::ops::Range { mut start, mut end } = self.overlapping_positions(range);
+        let mut merged = range;
+        let mut head = None;
+        let mut tail = None;
+
+        // Entries sticking out on either side are truncated, or absorbed if equal.
+        if start < end {
+            let (r, v) = &self.entries[start];
+            if r.start() < range.start() {
+                if *v == value {
+                    merged.0 = r.start();
+                } else {
+                    head = Some((Range(r.start(), range.start()), v.clone()));
+                }
+            }
+            let (r, v) = &self.entries[end - 1];
+            if r.end() > range.end() {
+                if *v == value {
+                    merged.1 = r.end();
+                } else {
+                    tail = Some((Range(range.end(), r.end()), v.clone()));
+                }
+            }
+        }
+        // Touching neighbors with an equal value are merged.
+        if head.is_none()
+            && let Some((r, v)) = start.checked_sub(1).map(|i| &self.entries[i])
+            && r.end() == merged.start()
+            && *v == value
+        {
+            merged.0 = r.start();
+            start -= 1;
+        }
+        if tail.is_none()
+            && let Some((r, v)) = self.entries.get(end)
+            && r.start() == merged.end()
+            && *v == value
+        {
+            merged.1 = r.end();
+            end += 1;
+        }
+
+        let replacement = head.into_iter().chain([(merged, value)]).chain(tail);
+        self.entries.splice(start..end, replacement);
+    }
+}
+
+impl<'a, V> IntoIterator for &'a RangeMap<V> {
+    type Item = (Range, &'a V);
+    type IntoIter = MapIter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`RangeMap`], in order.
+///
+/// Created by [`RangeMap::iter`].
+#[derive(Debug, Clone)]
+pub struct MapIter<'a, V> {
+    entries: core::slice::Iter<'a, (Range, V)>,
+}
+
+impl<'a, V> Iterator for MapIter<'a, V> {
+    type Item = (Range, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(r, v)| (*r, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<V> DoubleEndedIterator for MapIter<'_, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(|(r, v)| (*r, v))
+    }
+}
+
+impl<V> ExactSizeIterator for MapIter<'_, V> {}
+
+impl<V> FusedIterator for MapIter<'_, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    #[test]
+    fn insert_and_lookup() {
+        let mut map = RangeMap::new();
+        assert!(map.is_empty());
+        map.insert(Range(10, 20), 'b').unwrap();
+        map.insert(Range(0, 5), 'a').unwrap();
+        map.insert(Range(30, 31), 'c').unwrap();
+        assert_eq!(map.len(), 3);
+
+        for (index, expected) in [
+            (0, Some('a')),
+            (4, Some('a')),
+            (5, None),
+            (9, None),
+            (10, Some('b')),
+            (19, Some('b')),
+            (20, None),
+            (30, Some('c')),
+            (31, None),
+        ] {
+            assert_eq!(map.get(index).copied(), expected, "index {index}");
+        }
+        assert_eq!(map.get_key_value(15), Some((Range(10, 20), &'b')));
+        *map.get_mut(12).unwrap() = 'B';
+        assert_eq!(map.get(10), Some(&'B'));
+        assert!(map.get_mut(25).is_none());
+    }
+
+    #[test]
+    fn insert_rejects_overlaps_and_empty_ranges() {
+        let mut map = RangeMap::new();
+        map.insert(Range(10, 20), 0).unwrap();
+        for range in [Range(5, 11), Range(19, 25), Range(12, 15), Range(0, 30)] {
+            assert_eq!(
+                map.insert(range, 1),
+                Err(InsertError::Overlap {
+                    range,
+                    existing: Range(10, 20)
+                })
+            );
+        }
+        assert_eq!(
+            map.insert(Range(3, 3), 1),
+            Err(InsertError::Empty(Range(3, 3)))
+        );
+        assert_eq!(
+            map.insert(Range(8, 3), 1),
+            Err(InsertError::Empty(Range(8, 3)))
+        );
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn adjacent_entries_stay_separate() {
+        let mut map = RangeMap::new();
+        map.insert(Range(0, 5), "plain").unwrap();
+        map.insert(Range(5, 10), "bold").unwrap();
+        map.insert(Range(10, 15), "bold").unwrap();
+        let entries: Vec<(Range, &&str)> = map.iter().collect();
+        assert_eq!(
+            entries,
+            [
+                (Range(0, 5), &"plain"),
+                (Range(5, 10), &"bold"),
+                (Range(10, 15), &"bold"),
+            ]
+        );
+        assert_eq!(map.get(4), Some(&"plain"));
+        assert_eq!(map.get(5), Some(&"bold"));
+        assert_eq!(map.get_key_value(10), Some((Range(10, 15), &"bold")));
+    }
+
+    #[test]
+    fn remove_exact_entries() {
+        let mut map = RangeMap::new();
+        map.insert(Range(0, 5), 1).unwrap();
+        map.insert(Range(5, 10), 2).unwrap();
+        assert_eq!(map.remove(Range(0, 4)), None);
+        assert_eq!(map.remove(Range(1, 5)), None);
+        assert_eq!(map.remove(Range(5, 10)), Some(2));
+        assert_eq!(map.remove(Range(5, 10)), None);
+        assert_eq!(map.get(7), None);
+        assert_eq!(map.iter().len(), 1);
+        map.insert(Range(3, 8), 3).unwrap_err();
+        map.insert(Range(5, 8), 3).unwrap();
+        assert_eq!(map.iter().next_back(), Some((Range(5, 8), &3)));
+        map.clear();
+        assert!(map.is_empty());
+    }
+
+    fn sample() -> RangeMap<usize> {
+        let mut map = RangeMap::new();
+        for i in 0..10 {
+            map.insert(Range(i * 10, i * 10 + 5), i).unwrap();
+        }
+        map
+    }
+
+    fn overlapping_values(map: &RangeMap<usize>, query: Range) -> Vec<usize> {
+        map.overlapping(query).map(|(_, v)| *v).collect()
+    }
+
+    #[test]
+    fn overlapping_queries() {
+        let map = sample();
+        // Starting inside an entry.
+        assert_eq!(overlapping_values(&map, Range(13, 22)), [1, 2]);
+        // Ending exactly at an entry's start does not match it.
+        assert_eq!(overlapping_values(&map, Range(5, 20)), [1]);
+        assert!(overlapping_values(&map, Range(15, 20)).is_empty());
+        // Starting exactly at an entry's end does not match it.
+        assert_eq!(overlapping_values(&map, Range(25, 31)), [3]);
+        // Spanning many entries.
+        assert_eq!(
+            overlapping_values(&map, Range(0, 100)),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert_eq!(overlapping_values(&map, Range(34, 72)), [3, 4, 5, 6, 7]);
+        assert!(overlapping_values(&map, Range(95, 200)).is_empty());
+        // Empty and inverted queries.
+        assert!(overlapping_values(&map, Range(12, 12)).is_empty());
+        assert!(overlapping_values(&map, Range(40, 10)).is_empty());
+        assert!(overlapping_values(&RangeMap::new(), Range(0, 10)).is_empty());
+
+        assert_eq!(map.first_overlap(Range(3, 50)), Some((Range(0, 5), &0)));
+        assert_eq!(map.first_overlap(Range(48, 50)), None);
+        assert_eq!(map.first_overlap(Range(48, 51)), Some((Range(50, 55), &5)));
+        let last = map.overlapping(Range(0, 100)).next_back();
+        assert_eq!(last, Some((Range(90, 95), &9)));
+    }
+
+    #[test]
+    fn overlapping_mut_updates_matches_only() {
+        let mut map = sample();
+        for (r, v) in map.overlapping_mut(Range(24, 41)) {
+            assert!(r.overlaps(&Range(24, 41)));
+            *v += 100;
+        }
+        assert_eq!(overlapping_values(&map, Range(10, 50)), [1, 102, 103, 104]);
+    }
+
+    #[test]
+    fn overlapping_matches_brute_force() {
+        let map = sample();
+        for start in 0..100 {
+            for end in start..105 {
+                let query = Range(start, end);
+                let expected: Vec<usize> = map
+                    .iter()
+                    .filter(|(r, _)| r.intersect(&query).is_some())
+                    .map(|(_, v)| *v)
+                    .collect();
+                assert_eq!(overlapping_values(&map, query), expected, "{query:?}");
+            }
+        }
+    }
+
+    fn entries<V: Clone>(map: &RangeMap<V>) -> Vec<(Range, V)> {
+        map.iter().map(|(r, v)| (r, v.clone())).collect()
+    }
+
+    #[test]
+    fn insert_replace_splits_and_truncates() {
+        let mut map = RangeMap::new();
+        map.insert_replace(Range(0, 20), 'p');
+        map.insert_replace(Range(5, 15), 'b');
+        assert_eq!(
+            entries(&map),
+            [
+                (Range(0, 5), 'p'),
+                (Range(5, 15), 'b'),
+                (Range(15, 20), 'p')
+            ]
+        );
+        // Truncating both neighbors at once.
+        map.insert_replace(Range(3, 17), 'i');
+        assert_eq!(
+            entries(&map),
+            [
+                (Range(0, 3), 'p'),
+                (Range(3, 17), 'i'),
+                (Range(17, 20), 'p')
+            ]
+        );
+        // Covering everything, and past the end.
+        map.insert_replace(Range(0, 25), 'x');
+        assert_eq!(entries(&map), [(Range(0, 25), 'x')]);
+        // Empty ranges are ignored.
+        map.insert_replace(Range(4, 4), 'y');
+        map.insert_replace(Range(9, 4), 'y');
+        assert_eq!(entries(&map), [(Range(0, 25), 'x')]);
+    }
+
+    #[test]
+    fn insert_replace_coalesces_equal_neighbors() {
+        let mut map = RangeMap::new();
+        map.insert_replace(Range(0, 5), 'a');
+        map.insert_replace(Range(10, 15), 'a');
+        map.insert_replace(Range(5, 10), 'a');
+        assert_eq!(entries(&map), [(Range(0, 15), 'a')]);
+
+        map.insert_replace(Range(15, 20), 'b');
+        map.insert_replace(Range(3, 7), 'a');
+        assert_eq!(entries(&map), [(Range(0, 15), 'a'), (Range(15, 20), 'b')]);
+
+        // Equal to a truncated neighbor, but not to the adjacent one.
+        map.insert_replace(Range(12, 18), 'b');
+        assert_eq!(entries(&map), [(Range(0, 12), 'a'), (Range(12, 20), 'b')]);
+
+        // Different values stay separate even when adjacent.
+        map.insert_replace(Range(20, 22), 'c');
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn gaps_within_bounds() {
+        let mut map = RangeMap::new();
+        map.insert(Range(5, 10), ()).unwrap();
+        map.insert(Range(10, 12), ()).unwrap();
+        map.insert(Range(20, 30), ()).unwrap();
+        let gaps = |within| map.gaps(within).collect::<Vec<_>>();
+        assert_eq!(
+            gaps(Range(0, 40)),
+            [Range(0, 5), Range(12, 20), Range(30, 40)]
+        );
+        assert_eq!(gaps(Range(7, 25)), [Range(12, 20)]);
+        assert_eq!(gaps(Range(13, 15)), [Range(13, 15)]);
+        assert!(gaps(Range(5, 12)).is_empty());
+        assert!(gaps(Range(21, 22)).is_empty());
+        assert!(gaps(Range(3, 3)).is_empty());
+        assert!(gaps(Range(15, 3)).is_empty());
+        assert_eq!(
+            RangeMap::<()>::new().gaps(Range(2, 8)).collect::<Vec<_>>(),
+            [Range(2, 8)]
+        );
+    }
+
+    #[test]
+    fn randomized_insert_replace_against_dense_model() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        const LEN: usize = 64;
+        for seed in 0..300 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut map = RangeMap::new();
+            let mut model: Vec<Option<u8>> = std::vec![None; LEN];
+            for _ in 0..rng.random_range(1..20) {
+                let start = rng.random_range(0..=LEN);
+                let end = rng.random_range(start..=LEN);
+                let value = rng.random_range(0..3u8);
+                map.insert_replace(Range(start, end), value);
+                model[start..end].fill(Some(value));
+
+                for (index, expected) in model.iter().enumerate() {
+                    assert_eq!(
+                        map.get(index),
+                        expected.as_ref(),
+                        "seed {seed} index {index}"
+                    );
+                }
+                for pair in map.entries.windows(2) {
+                    let ((a, x), (b, y)) = (&pair[0], &pair[1]);
+                    assert!(a.end() <= b.start());
+                    assert!(a.end() < b.start() || x != y, "uncoalesced {pair:?}");
+                }
+                let expected_gaps: Vec<Range> = Range(0, LEN)
+                    .iter()
+                    .filter(|&i| model[i].is_none())
+                    .map(|i| Range(i, i + 1))
+                    .fold(Vec::new(), |mut acc: Vec<Range>, r| {
+                        match acc.last_mut() {
+                            Some(last) if last.end() == r.start() => last.1 = r.end(),
+                            _ => acc.push(r),
+                        }
+                        acc
+                    });
+                assert_eq!(map.gaps(Range(0, LEN)).collect::<Vec<_>>(), expected_gaps);
+            }
+        }
+    }
+}
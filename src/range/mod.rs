@@ -0,0 +1,3430 @@
+//! This module provides a compact, copyable `Range` tuple struct designed to be
+//! convenient for storing, hashing, ordering and using directly as a slice index.
+//!
+//!
+//! # Example
+//! ```
+//! // Basic indexing (start inclusive, end exclusive):
+//! let a = [0, 1, 2, 3, 4];
+//! let r = ars::range::Range::new(1, 4);
+//! assert_eq!(&a[r], &[1, 2, 3]);
+//! ```
+
+mod aligned;
+#[cfg(feature = "alloc")]
+mod allocator;
+#[cfg(feature = "alloc")]
+pub mod anchor;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impl;
+#[cfg(feature = "alloc")]
+mod coalesce;
+mod compact;
+mod coverage;
+mod cursor;
+pub mod edit;
+mod error;
+mod fmt;
+mod gaps;
+#[cfg(feature = "alloc")]
+mod interval_tree;
+mod iter;
+#[cfg(feature = "alloc")]
+mod map;
+mod non_empty;
+mod ops;
+pub mod ord;
+#[cfg(feature = "rand")]
+mod rand_impl;
+mod rect;
+mod relation;
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+mod search;
+#[cfg(feature = "alloc")]
+mod selection;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "alloc")]
+mod set;
+mod span;
+#[cfg(feature = "alloc")]
+mod span_stack;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+mod strided;
+
+pub use aligned::AlignedParts;
+#[cfg(feature = "alloc")]
+pub use allocator::RangeAllocator;
+#[cfg(feature = "alloc")]
+pub use coalesce::{coalesce, coalesce_iter};
+pub use compact::{Range16, Range32};
+pub use coverage::covered_len_sorted;
+#[cfg(feature = "alloc")]
+pub use coverage::{covered_fraction, covered_len};
+pub use cursor::Cursor;
+pub use error::{
+    EmptyRangeError, InvalidRange, ParseRangeError, TryFromRangeError, TryFromRangeInclusiveError,
+};
+#[cfg(feature = "alloc")]
+pub use error::{FreeError, InsertError, UnclosedSpans};
+pub use gaps::{Gaps, gaps};
+#[cfg(feature = "alloc")]
+pub use interval_tree::{IntervalQuery, IntervalTree};
+pub use iter::{Chunks, ChunksExact, Iter, RChunks, SplitEvenly, StepIter, Windows};
+#[cfg(feature = "alloc")]
+pub use map::{MapIter, RangeMap};
+pub use non_empty::NonEmptyRange;
+#[cfg(feature = "rand")]
+pub use rand_impl::UniformIndex;
+pub use rect::{Rect, RowRanges};
+pub use relation::IntervalRelation;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::ArchivedRange;
+pub use search::{find_containing, find_overlapping};
+#[cfg(feature = "alloc")]
+pub use selection::Selection;
+#[cfg(feature = "alloc")]
+pub use set::{Indices, RangeSet};
+pub use span::Span;
+#[cfg(feature = "alloc")]
+pub use span_stack::SpanStack;
+pub use strided::StridedRange;
+
+/// A compact, copyable index range holding a `start` (inclusive) and `end` (exclusive).
+///
+/// This is a lightweight alternative to [`core::ops::Range<usize>`] where you may
+/// want the range to implement traits like `Copy`, `Hash`, `Ord`, and `Debug` in
+/// a trivial tuple form. It also provides a few convenience methods on top of the
+/// raw tuple so common operations are ergonomic.
+///
+/// `Debug` prints range notation like core ranges (`2..5`), so collections of
+/// ranges stay compact. The alternate form (`{:#?}`) prints the verbose
+/// `Range { start: 2, end: 5 }` struct form instead; note that `{:#?}` on a
+/// containing value also selects this form for every nested `Range`.
+///
+/// # Inverted ranges
+/// Nothing prevents `start > end`, which easily happens when bounds come from
+/// subtraction. Use [`is_valid`](Self::is_valid) to detect such ranges and
+/// [`normalize`](Self::normalize) to fix them. Where methods accept them,
+/// inverted ranges behave as empty: [`len`](Self::len) is 0,
+/// [`is_empty`](Self::is_empty) is `true` and nothing is contained. Indexing a
+/// slice with an inverted range panics, like indexing with `5..2` does.
+///
+/// # Layout
+/// `Range` is `#[repr(C)]`: `start` followed by `end`, with the size and alignment
+/// of `[usize; 2]` and no padding. With the `bytemuck` feature it implements
+/// `Pod` and `Zeroable`, so buffers of ranges can be cast from and to bytes.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C)]
+pub struct Range(pub usize, pub usize);
+
+impl Range {
+    /// Construct a new `Range` from `start` and `end` (start inclusive, end exclusive).
+    ///
+    /// The values are not validated — callers should ensure `start <= end` if that
+    /// invariant matters for their use case, or use [`try_new`](Self::try_new) to
+    /// reject inverted ranges at construction. Many methods (like `len`) handle
+    /// inverted ranges sensibly (returning 0).
+    #[must_use]
+    #[inline]
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self(start, end)
+    }
+
+    /// Construct a new `Range`, returning an [`InvalidRange`] error if `start > end`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range::try_new(2, 5), Ok(Range(2, 5)));
+    /// let err = Range::try_new(5, 2).unwrap_err();
+    /// assert_eq!((err.start(), err.end()), (5, 2));
+    /// ```
+    #[inline]
+    pub const fn try_new(start: usize, end: usize) -> Result<Self, InvalidRange> {
+        if start <= end {
+            Ok(Self(start, end))
+        } else {
+            Err(InvalidRange { start, end })
+        }
+    }
+
+    /// Construct a new `Range` from a `start` and a `len`.
+    ///
+    /// Like [`offset`](Self::offset) this does not check for overflow; use
+    /// [`checked_from_start_len`](Self::checked_from_start_len) when `start + len`
+    /// may exceed `usize::MAX`.
+    #[must_use]
+    #[inline]
+    pub const fn from_start_len(start: usize, len: usize) -> Self {
+        Self(start, start + len)
+    }
+
+    /// Construct a new `Range` from a `start` and a `len`, returning `None` if the
+    /// end would overflow.
+    #[must_use]
+    #[inline]
+    pub const fn checked_from_start_len(start: usize, len: usize) -> Option<Self> {
+        match start.checked_add(len) {
+            Some(end) => Some(Self(start, end)),
+            None => None,
+        }
+    }
+
+    /// Returns the range of the page at `page_index` when items are split into
+    /// pages of `page_size`, i.e. `page_index * page_size` onwards.
+    ///
+    /// The result is not clamped to any total; see [`pages`](Self::pages).
+    ///
+    /// # Panics
+    /// Panics if `page_size` is 0 or the end of the page overflows.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range::page(2, 25), Range(50, 75));
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn page(page_index: usize, page_size: usize) -> Self {
+        assert!(page_size != 0, "page size must be non-zero");
+        Self::checked_page(page_index, page_size).expect("page range overflows usize")
+    }
+
+    /// Returns the range of the page at `page_index`, or `None` if `page_size` is 0
+    /// or the end of the page overflows.
+    #[must_use]
+    #[inline]
+    pub const fn checked_page(page_index: usize, page_size: usize) -> Option<Self> {
+        if page_size == 0 {
+            return None;
+        }
+        match page_index.checked_mul(page_size) {
+            Some(start) => Self::checked_from_start_len(start, page_size),
+            None => None,
+        }
+    }
+
+    /// Returns an iterator over the pages covering `0..total_len`, the last one
+    /// clamped to `total_len`.
+    ///
+    /// # Panics
+    /// Panics if `page_size` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let pages: Vec<Range> = Range::pages(7, 3).collect();
+    /// assert_eq!(pages, [Range(0, 3), Range(3, 6), Range(6, 7)]);
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn pages(total_len: usize, page_size: usize) -> Chunks {
+        assert!(page_size != 0, "page size must be non-zero");
+        Self(0, total_len).chunks(page_size)
+    }
+
+    /// Returns the number of pages of `page_size` needed for `total_len` items,
+    /// rounding up.
+    ///
+    /// # Panics
+    /// Panics if `page_size` is 0.
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn num_pages(total_len: usize, page_size: usize) -> usize {
+        assert!(page_size != 0, "page size must be non-zero");
+        total_len.div_ceil(page_size)
+    }
+
+    /// Returns a range of the same length moved to begin at `start`.
+    ///
+    /// Overflow is not checked, see [`from_start_len`](Self::from_start_len).
+    #[must_use]
+    #[inline]
+    pub const fn with_start(&self, start: usize) -> Self {
+        Self::from_start_len(start, self.len())
+    }
+
+    /// Returns a range of the same length moved to finish at `end`.
+    ///
+    /// Underflow is not checked; `end` must be at least `self.len()`.
+    #[must_use]
+    #[inline]
+    pub const fn with_end(&self, end: usize) -> Self {
+        Self(end - self.len(), end)
+    }
+
+    /// Returns a range with the same start and the given `len`.
+    ///
+    /// Overflow is not checked, see [`from_start_len`](Self::from_start_len).
+    #[must_use]
+    #[inline]
+    pub const fn with_len(&self, len: usize) -> Self {
+        Self::from_start_len(self.0, len)
+    }
+
+    /// Convert an inclusive range into a `Range`, returning `None` if its end is
+    /// `usize::MAX` (the exclusive end would overflow).
+    ///
+    /// An empty inclusive range (including an exhausted one) becomes an empty range
+    /// positioned at its start.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range::from_inclusive(2..=5), Some(Range(2, 6)));
+    /// assert_eq!(Range::from_inclusive(2..=usize::MAX), None);
+    /// ```
+    #[must_use]
+    pub fn from_inclusive(r: core::ops::RangeInclusive<usize>) -> Option<Self> {
+        if r.is_empty() {
+            return Some(Self(*r.start(), *r.start()));
+        }
+        r.end().checked_add(1).map(|end| Self(*r.start(), end))
+    }
+
+    /// Convert into an inclusive range, returning `None` if the range is empty (or
+    /// inverted) since an inclusive range cannot represent that position.
+    #[must_use]
+    pub const fn to_inclusive(&self) -> Option<core::ops::RangeInclusive<usize>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0..=self.1 - 1)
+        }
+    }
+
+    /// Encodes the range as 16 bytes: `start` then `end`, each as a little-endian
+    /// `u64` regardless of the platform's `usize` width.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let bytes = Range(1, 0x0203).to_le_bytes();
+    /// assert_eq!(bytes, [1, 0, 0, 0, 0, 0, 0, 0, 3, 2, 0, 0, 0, 0, 0, 0]);
+    /// assert_eq!(Range::from_le_bytes(bytes), Range(1, 0x0203));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_le_bytes(&self) -> [u8; 16] {
+        let mut out = [0; 16];
+        let (start, end) = out.split_at_mut(8);
+        start.copy_from_slice(&(self.0 as u64).to_le_bytes());
+        end.copy_from_slice(&(self.1 as u64).to_le_bytes());
+        out
+    }
+
+    /// Encodes the range like [`to_le_bytes`](Self::to_le_bytes), but with
+    /// big-endian `u64`s.
+    #[must_use]
+    #[inline]
+    pub const fn to_be_bytes(&self) -> [u8; 16] {
+        let mut out = [0; 16];
+        let (start, end) = out.split_at_mut(8);
+        start.copy_from_slice(&(self.0 as u64).to_be_bytes());
+        end.copy_from_slice(&(self.1 as u64).to_be_bytes());
+        out
+    }
+
+    /// Decodes a range written by [`to_le_bytes`](Self::to_le_bytes).
+    ///
+    /// # Panics
+    /// Panics if a bound does not fit in `usize`, which can only happen on targets
+    /// where `usize` is narrower than 64 bits; see
+    /// [`try_from_le_bytes`](Self::try_from_le_bytes).
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub fn from_le_bytes(bytes: [u8; 16]) -> Self {
+        Self::try_from_le_bytes(bytes).expect("encoded range bound does not fit in usize")
+    }
+
+    /// Decodes a range written by [`to_be_bytes`](Self::to_be_bytes).
+    ///
+    /// # Panics
+    /// Panics if a bound does not fit in `usize`; see
+    /// [`try_from_be_bytes`](Self::try_from_be_bytes).
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Self {
+        Self::try_from_be_bytes(bytes).expect("encoded range bound does not fit in usize")
+    }
+
+    /// Decodes a range written by [`to_le_bytes`](Self::to_le_bytes).
+    ///
+    /// # Errors
+    /// Fails if a bound does not fit in `usize` on this target.
+    #[inline]
+    pub fn try_from_le_bytes(bytes: [u8; 16]) -> Result<Self, core::num::TryFromIntError> {
+        let (start, end) = split_bounds(bytes);
+        Ok(Self(
+            u64::from_le_bytes(start).try_into()?,
+            u64::from_le_bytes(end).try_into()?,
+        ))
+    }
+
+    /// Decodes a range written by [`to_be_bytes`](Self::to_be_bytes).
+    ///
+    /// # Errors
+    /// Fails if a bound does not fit in `usize` on this target.
+    #[inline]
+    pub fn try_from_be_bytes(bytes: [u8; 16]) -> Result<Self, core::num::TryFromIntError> {
+        let (start, end) = split_bounds(bytes);
+        Ok(Self(
+            u64::from_be_bytes(start).try_into()?,
+            u64::from_be_bytes(end).try_into()?,
+        ))
+    }
+
+    /// Returns the smallest range containing every index yielded by `indices`.
+    ///
+    /// Returns `None` if the iterator is empty, or if it yields `usize::MAX`, whose
+    /// exclusive end would overflow (like [`from_inclusive`](Self::from_inclusive)).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range::from_indices([7, 3, 9, 4]), Some(Range(3, 10)));
+    /// assert_eq!(Range::from_indices([]), None);
+    /// ```
+    #[must_use]
+    pub fn from_indices(indices: impl IntoIterator<Item = usize>) -> Option<Self> {
+        let mut iter = indices.into_iter();
+        let first = iter.next()?;
+        let (min, max) = iter.fold((first, first), |(min, max), i| (min.min(i), max.max(i)));
+        max.checked_add(1).map(|end| Self(min, end))
+    }
+
+    /// Returns the [`hull`](Self::hull) of every range yielded by `ranges`, the
+    /// smallest range covering all of them.
+    ///
+    /// Empty and inverted ranges are skipped; `None` is returned if no non-empty
+    /// range remains.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let hits = [Range(8, 10), Range(0, 0), Range(2, 4)];
+    /// assert_eq!(Range::from_ranges(hits), Some(Range(2, 10)));
+    /// assert_eq!(Range::from_ranges([Range(5, 5)]), None);
+    /// ```
+    #[must_use]
+    pub fn from_ranges(ranges: impl IntoIterator<Item = Self>) -> Option<Self> {
+        ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .reduce(|acc, r| acc.hull(&r))
+    }
+
+    /// Returns the [`intersect`](Self::intersect)ion of every range yielded by
+    /// `ranges`, the indices they all share.
+    ///
+    /// Returns `None` if the iterator is empty, since there is no range covering
+    /// everything to start from, or as soon as the intersection becomes empty; the
+    /// remaining ranges are not consumed. See
+    /// [`intersect_many`](Self::intersect_many) to start from a given range.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let (viewport, selection) = (Range(100, 200), Range(150, 400));
+    /// assert_eq!(Range::intersect_all([viewport, selection]), Some(Range(150, 200)));
+    /// assert_eq!(Range::intersect_all([]), None);
+    /// ```
+    #[must_use]
+    pub fn intersect_all(ranges: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut ranges = ranges.into_iter();
+        ranges.next()?.intersect_many(ranges)
+    }
+
+    /// Returns the intersection of `self` with every range yielded by `others`.
+    ///
+    /// With no other ranges this is `self`, or `None` if `self` is empty. Iteration
+    /// stops as soon as the intersection becomes empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let span = Range(0, 50);
+    /// assert_eq!(span.intersect_many([Range(10, 60), Range(0, 40)]), Some(Range(10, 40)));
+    /// assert_eq!(span.intersect_many([Range(10, 20), Range(30, 40)]), None);
+    /// ```
+    #[must_use]
+    pub fn intersect_many(&self, others: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let acc = if self.is_empty() { None } else { Some(*self) };
+        others
+            .into_iter()
+            .try_fold(acc?, |acc, r| acc.intersect(&r))
+    }
+
+    /// Returns the start (inclusive) of the range.
+    #[must_use]
+    #[inline]
+    pub const fn start(&self) -> usize {
+        self.0
+    }
+
+    /// Returns the end (exclusive) of the range.
+    #[must_use]
+    #[inline]
+    pub const fn end(&self) -> usize {
+        self.1
+    }
+
+    /// Returns the length of the range, saturating at 0 if `end < start`.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.1.saturating_sub(self.0)
+    }
+
+    /// Returns `true` if the range contains no elements (i.e. `start >= end`).
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0 >= self.1
+    }
+
+    /// Returns the length of the range, or `None` if it is empty or inverted.
+    ///
+    /// Handy when the length is used as a divisor.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let r = Range(10, 14);
+    /// let per_item = r.len_nonzero().map_or(0, |len| 100 / len);
+    /// assert_eq!(per_item, 25);
+    /// assert_eq!(Range(4, 4).len_nonzero(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn len_nonzero(&self) -> Option<core::num::NonZeroUsize> {
+        core::num::NonZeroUsize::new(self.len())
+    }
+
+    /// Returns `true` if the range is not inverted, i.e. `start <= end`.
+    ///
+    /// Empty ranges like `Range(3, 3)` are valid.
+    #[must_use]
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        self.0 <= self.1
+    }
+
+    /// Returns the range with its bounds swapped if it is inverted, and unchanged
+    /// otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(5, 2).normalize(), Range(2, 5));
+    /// assert_eq!(Range(2, 5).normalize(), Range(2, 5));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn normalize(&self) -> Self {
+        if self.0 > self.1 {
+            Self(self.1, self.0)
+        } else {
+            *self
+        }
+    }
+
+    /// Returns `true` if the provided index is inside the range.
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, index: usize) -> bool {
+        index >= self.0 && index < self.1
+    }
+
+    /// Returns the index inside the range nearest to `index`, clamping it into
+    /// `start..=end - 1`, or `None` if the range is empty.
+    ///
+    /// The result is always a valid element index: `index == end` clamps to
+    /// `end - 1`. For positions that may sit one past the last element, such as
+    /// insertion points, use
+    /// [`clamp_index_inclusive_end`](Self::clamp_index_inclusive_end).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let visible = Range(10, 20);
+    /// assert_eq!(visible.clamp_index(3), Some(10));
+    /// assert_eq!(visible.clamp_index(15), Some(15));
+    /// assert_eq!(visible.clamp_index(20), Some(19));
+    /// assert_eq!(Range(5, 5).clamp_index(5), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn clamp_index(&self, index: usize) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(clamp(index, self.0, self.1 - 1))
+        }
+    }
+
+    /// Clamps `index` into `start..=end`, allowing the position one past the last
+    /// element.
+    ///
+    /// Unlike [`clamp_index`](Self::clamp_index) this is defined for empty ranges,
+    /// where it returns `start`, and `index == end` stays `end`. Inverted ranges
+    /// are treated as empty at `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let line = Range(10, 20);
+    /// assert_eq!(line.clamp_index_inclusive_end(20), 20);
+    /// assert_eq!(line.clamp_index_inclusive_end(25), 20);
+    /// assert_eq!(Range(5, 5).clamp_index_inclusive_end(0), 5);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn clamp_index_inclusive_end(&self, index: usize) -> usize {
+        let hi = if self.1 < self.0 { self.0 } else { self.1 };
+        clamp(index, self.0, hi)
+    }
+
+    /// Returns the range as a pair of [`Bound`](core::ops::Bound)s.
+    ///
+    /// This is handy for APIs like [`slice::get`] that accept a bound pair but not
+    /// arbitrary [`RangeBounds`](core::ops::RangeBounds) implementors.
+    ///
+    /// # Example
+    /// ```
+    /// let a = [1, 2, 3];
+    /// let r = ars::range::Range::new(1, 3);
+    /// assert_eq!(a.get(r.bounds()), Some(&[2, 3][..]));
+    /// assert_eq!(a.get(ars::range::Range::new(2, 4).bounds()), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn bounds(&self) -> (core::ops::Bound<usize>, core::ops::Bound<usize>) {
+        (
+            core::ops::Bound::Included(self.0),
+            core::ops::Bound::Excluded(self.1),
+        )
+    }
+
+    /// Returns an iterator over the indices `start, start + step, ...` below `end`.
+    ///
+    /// This is the `Range` counterpart of [`Iterator::step_by`] on core ranges.
+    ///
+    /// # Panics
+    /// Panics if `step` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn iter_step(self, step: usize) -> StepIter {
+        StepIter::new(self, step)
+    }
+
+    /// Returns an iterator over consecutive sub-ranges of length `size`, with a
+    /// shorter final chunk if `size` does not divide the length.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0, like [`slice::chunks`].
+    #[must_use]
+    #[inline]
+    pub const fn chunks(self, size: usize) -> Chunks {
+        Chunks::new(self, size)
+    }
+
+    /// Returns an iterator over consecutive sub-ranges of exactly `size`
+    /// elements, mirroring [`slice::chunks_exact`]. The shorter tail is available
+    /// from [`ChunksExact::remainder`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn chunks_exact(self, size: usize) -> ChunksExact {
+        ChunksExact::new(self, size)
+    }
+
+    /// Returns an iterator over consecutive sub-ranges of length `size` starting
+    /// from the end, with a shorter final chunk at the start if `size` does not
+    /// divide the length, like [`slice::rchunks`].
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn rchunks(self, size: usize) -> RChunks {
+        RChunks::new(self, size)
+    }
+
+    /// Returns an iterator over all sub-ranges of exactly `size` elements, stepping
+    /// by one like [`slice::windows`].
+    ///
+    /// Yields nothing if `size` is larger than the range.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn windows(self, size: usize) -> Windows {
+        Windows::new(self, size)
+    }
+
+    /// Returns an iterator dividing the range into `n` contiguous parts whose
+    /// lengths differ by at most one.
+    ///
+    /// The first `len % n` parts are one element longer than the rest. If `n` is
+    /// larger than the length, the trailing parts are empty.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn split_evenly(self, n: usize) -> SplitEvenly {
+        SplitEvenly::new(self, n)
+    }
+
+    /// Returns `true` if `other` lies entirely within `self`.
+    ///
+    /// An empty (or inverted) `other` is treated as the empty position at its
+    /// start: it is contained if that position lies within `self.start()..=self.end()`,
+    /// so `Range(2, 5)` contains `Range(5, 5)` but not `Range(6, 6)`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert!(Range(2, 5).contains_range(&Range(2, 5)));
+    /// assert!(Range(2, 5).contains_range(&Range(3, 4)));
+    /// assert!(!Range(2, 5).contains_range(&Range(4, 6)));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn contains_range(&self, other: &Self) -> bool {
+        if other.is_empty() {
+            self.0 <= other.0 && other.0 <= self.1
+        } else {
+            self.0 <= other.0 && other.1 <= self.1
+        }
+    }
+
+    /// Returns `true` if `self` lies entirely within `other`.
+    ///
+    /// This is the inverse of [`contains_range`](Self::contains_range) and uses the
+    /// same rules for empty ranges.
+    #[must_use]
+    #[inline]
+    pub const fn is_subset_of(&self, other: &Self) -> bool {
+        other.contains_range(self)
+    }
+
+    /// Returns an iterator over the indices covered by the range.
+    ///
+    /// Inverted ranges produce an empty iterator.
+    #[must_use]
+    #[inline]
+    pub const fn iter(&self) -> Iter {
+        Iter::new(*self)
+    }
+
+    /// Returns a new `Range` clamped to the provided `len`.
+    ///
+    /// This is useful when you want to safely apply a range to a slice without
+    /// panicking from out-of-bounds indexing.
+    ///
+    /// # Example
+    /// ```
+    /// let a = &[1, 2, 3, 4];
+    /// let r = ars::range::Range(2, 10);
+    /// let clamped = r.clamp_to(a.len());
+    /// assert_eq!(&a[clamped], &[3, 4]);
+    /// ```
+    ///
+    /// Both bounds are clamped independently, so an inverted range stays inverted
+    /// unless both bounds reach `len`, in which case it becomes `Range(len, len)`.
+    #[must_use]
+    #[inline]
+    pub const fn clamp_to(&self, len: usize) -> Self {
+        let s = if self.0 < len { self.0 } else { len };
+        let e = if self.1 < len { self.1 } else { len };
+        Self(s, e)
+    }
+
+    /// Returns a new `Range` with both ends moved into `bounds.start()..=bounds.end()`.
+    ///
+    /// Unlike [`intersect`](Self::intersect) this never returns `None`: a range
+    /// entirely outside `bounds` becomes an empty range positioned at the nearest
+    /// bound, which is still meaningful as an insertion point. Inverted `bounds` are
+    /// treated as the empty range at `bounds.start()`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let bounds = Range(10, 20);
+    /// assert_eq!(Range(5, 15).clamp_to_range(&bounds), Range(10, 15));
+    /// assert_eq!(Range(0, 5).clamp_to_range(&bounds), Range(10, 10));
+    /// assert_eq!(Range(25, 30).clamp_to_range(&bounds), Range(20, 20));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn clamp_to_range(&self, bounds: &Self) -> Self {
+        let lo = bounds.0;
+        let hi = if bounds.1 < bounds.0 {
+            bounds.0
+        } else {
+            bounds.1
+        };
+        Self(clamp(self.0, lo, hi), clamp(self.1, lo, hi))
+    }
+
+    /// Splits the range into `start..index` and `index..end`, mirroring
+    /// [`slice::split_at`].
+    ///
+    /// Unlike `slice::split_at` this never panics: `index` is clamped into
+    /// `start..=end`, so an index before the range yields an empty first half and
+    /// an index past the end yields an empty second half. Use
+    /// [`try_split_at`](Self::try_split_at) to reject such indices instead.
+    ///
+    /// # Example
+    /// ```
+    /// let buf = [1, 2, 3, 4, 5, 6];
+    /// let r = ars::range::Range::new(1, 5);
+    /// let (left, right) = r.split_at(3);
+    /// assert_eq!(&buf[left], &[2, 3]);
+    /// assert_eq!(&buf[right], &[4, 5]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn split_at(&self, index: usize) -> (Self, Self) {
+        let index = if index < self.0 {
+            self.0
+        } else if index > self.1 {
+            // Inverted ranges clamp to the start, keeping the first half empty.
+            if self.1 < self.0 { self.0 } else { self.1 }
+        } else {
+            index
+        };
+        (Self(self.0, index), Self(index, self.1))
+    }
+
+    /// Splits the range at `index` like [`split_at`](Self::split_at), returning
+    /// `None` if `index` is not within `start..=end`.
+    #[must_use]
+    #[inline]
+    pub const fn try_split_at(&self, index: usize) -> Option<(Self, Self)> {
+        if self.0 <= index && index <= self.1 {
+            Some((Self(self.0, index), Self(index, self.1)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the intersection of `self` and `other`, or `None` if they don't overlap.
+    ///
+    /// Empty and inverted ranges intersect nothing, so either one yields `None`.
+    #[must_use]
+    #[inline]
+    pub const fn intersect(&self, other: &Self) -> Option<Self> {
+        let s = if self.0 > other.0 { self.0 } else { other.0 };
+        let e = if self.1 < other.1 { self.1 } else { other.1 };
+        if s < e { Some(Self(s, e)) } else { None }
+    }
+
+    /// Returns `true` if `self` and `other` share at least one index.
+    ///
+    /// Empty and inverted ranges overlap nothing.
+    #[must_use]
+    #[inline]
+    pub const fn overlaps(&self, other: &Self) -> bool {
+        self.0 < other.1 && other.0 < self.1 && !self.is_empty() && !other.is_empty()
+    }
+
+    /// Returns `true` if one range ends exactly where the other starts, in either
+    /// order.
+    ///
+    /// Both ranges must be non-empty; an empty range is never adjacent to anything.
+    #[must_use]
+    #[inline]
+    pub const fn is_adjacent(&self, other: &Self) -> bool {
+        !self.is_empty() && !other.is_empty() && (self.1 == other.0 || other.1 == self.0)
+    }
+
+    /// Returns the number of indices shared by `self` and `other`, or 0 if they are
+    /// disjoint.
+    #[must_use]
+    #[inline]
+    pub const fn overlap_len(&self, other: &Self) -> usize {
+        let s = if self.0 > other.0 { self.0 } else { other.0 };
+        let e = if self.1 < other.1 { self.1 } else { other.1 };
+        e.saturating_sub(s)
+    }
+
+    /// Returns the index halfway through the range, rounding down toward the start.
+    ///
+    /// For an even length this is the first index of the second half, e.g.
+    /// `Range(2, 6).midpoint() == 4`; for an odd length it is the middle element.
+    /// Empty and inverted ranges return their start.
+    #[must_use]
+    #[inline]
+    pub const fn midpoint(&self) -> usize {
+        self.0 + self.len() / 2
+    }
+
+    /// Returns the range strictly between `self` and `other`, or `None` if they
+    /// overlap or touch.
+    ///
+    /// The order of the two ranges does not matter, and the result can be used to
+    /// slice the separating region directly. Empty ranges act as a position, and
+    /// inverted ranges are treated as empty at their start.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let text = b"foo, bar";
+    /// let gap = Range(0, 3).gap_to(&Range(5, 8)).unwrap();
+    /// assert_eq!(&text[gap], b", ");
+    /// ```
+    #[must_use]
+    pub const fn gap_to(&self, other: &Self) -> Option<Self> {
+        let self_end = if self.1 < self.0 { self.0 } else { self.1 };
+        let other_end = if other.1 < other.0 { other.0 } else { other.1 };
+        if self_end < other.0 {
+            Some(Self(self_end, other.0))
+        } else if other_end < self.0 {
+            Some(Self(other_end, self.0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of indices strictly between `self` and `other`, or 0 if
+    /// they overlap or touch.
+    ///
+    /// This is the length of [`gap_to`](Self::gap_to).
+    #[must_use]
+    #[inline]
+    pub const fn distance_to(&self, other: &Self) -> usize {
+        match self.gap_to(other) {
+            Some(gap) => gap.len(),
+            None => 0,
+        }
+    }
+
+    /// Returns the merged range if `self` and `other` overlap or are adjacent, or
+    /// `None` if merging them would swallow a gap.
+    ///
+    /// Empty (or inverted) ranges contribute nothing, so the union with an empty
+    /// range is the other range.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(0, 3).union(&Range(3, 5)), Some(Range(0, 5)));
+    /// assert_eq!(Range(0, 3).union(&Range(4, 5)), None);
+    /// ```
+    #[must_use]
+    pub const fn union(&self, other: &Self) -> Option<Self> {
+        if other.is_empty() {
+            Some(*self)
+        } else if self.is_empty() {
+            Some(*other)
+        } else if self.0 <= other.1 && other.0 <= self.1 {
+            Some(self.hull(other))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest range covering both `self` and `other`, including any
+    /// gap between them.
+    ///
+    /// Empty (or inverted) ranges are ignored, so the hull with an empty range is
+    /// the other range. If both are empty, `self` is returned.
+    #[must_use]
+    pub const fn hull(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            *self
+        } else if self.is_empty() {
+            *other
+        } else {
+            let s = if self.0 < other.0 { self.0 } else { other.0 };
+            let e = if self.1 > other.1 { self.1 } else { other.1 };
+            Self(s, e)
+        }
+    }
+
+    /// Returns the smallest range covering both `self` and `index`.
+    ///
+    /// An empty (or inverted) range contributes nothing, so including an index in
+    /// it yields `index..index + 1` regardless of its stale position. This matches
+    /// folding indices with [`from_indices`](Self::from_indices).
+    ///
+    /// # Panics
+    /// Panics if `index` is `usize::MAX`, since the exclusive end would overflow;
+    /// see [`checked_including`](Self::checked_including).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(2, 5).including(8), Range(2, 9));
+    /// assert_eq!(Range(2, 5).including(0), Range(0, 5));
+    /// assert_eq!(Range(4, 4).including(9), Range(9, 10));
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn including(&self, index: usize) -> Self {
+        self.checked_including(index)
+            .expect("cannot include usize::MAX in an exclusive range")
+    }
+
+    /// Like [`including`](Self::including), but returns `None` if `index` is
+    /// `usize::MAX`.
+    #[must_use]
+    #[inline]
+    pub const fn checked_including(&self, index: usize) -> Option<Self> {
+        let Some(end) = index.checked_add(1) else {
+            return None;
+        };
+        Some(self.hull(&Self(index, end)))
+    }
+
+    /// Grows the range in place so that it [`contains`](Self::contains) `index`;
+    /// see [`including`](Self::including).
+    ///
+    /// # Panics
+    /// Panics if `index` is `usize::MAX`.
+    #[inline]
+    #[track_caller]
+    pub const fn extend_to_include(&mut self, index: usize) {
+        *self = self.including(index);
+    }
+
+    /// Removes `other` from `self`, returning the part of `self` before `other`
+    /// and the part after it.
+    ///
+    /// Pieces that would be empty are returned as `None`, so the result never
+    /// contains empty or inverted ranges. An empty `other` removes nothing and the
+    /// whole of `self` is returned as the first piece.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let r = Range(0, 10);
+    /// assert_eq!(r.difference(&Range(3, 5)), (Some(Range(0, 3)), Some(Range(5, 10))));
+    /// assert_eq!(r.difference(&Range(0, 4)), (None, Some(Range(4, 10))));
+    /// ```
+    #[must_use]
+    pub const fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+        if other.is_empty() {
+            return (Some(*self), None);
+        }
+
+        let before_end = if self.1 < other.0 { self.1 } else { other.0 };
+        let after_start = if self.0 > other.1 { self.0 } else { other.1 };
+
+        let before = if self.0 < before_end {
+            Some(Self(self.0, before_end))
+        } else {
+            None
+        };
+        let after = if after_start < self.1 {
+            Some(Self(after_start, self.1))
+        } else {
+            None
+        };
+        (before, after)
+    }
+
+    /// Returns the parts of `0..len` not covered by the range: the leading gap
+    /// before it and the trailing gap after it.
+    ///
+    /// The range is clamped to `len` first. Gaps that would be empty are `None`, so
+    /// a range covering everything yields `(None, None)`. An empty range covers
+    /// nothing, so the whole buffer is returned as the leading piece, matching
+    /// [`difference`](Self::difference).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let buf = *b"hello world";
+    /// let (before, after) = Range(4, 7).complement(buf.len());
+    /// assert_eq!(&buf[before.unwrap()], b"hell");
+    /// assert_eq!(&buf[after.unwrap()], b"orld");
+    /// ```
+    #[must_use]
+    pub const fn complement(&self, len: usize) -> (Option<Self>, Option<Self>) {
+        Self(0, len).difference(&self.clamp_to(len))
+    }
+
+    /// Expands the range so both bounds are multiples of `align`, rounding the start
+    /// down and the end up.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two, or if rounding the end up overflows
+    /// `usize`; see [`checked_align_outward`](Self::checked_align_outward).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(100, 5000).align_outward(4096), Range(0, 8192));
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn align_outward(&self, align: usize) -> Self {
+        self.checked_align_outward(align)
+            .expect("rounding the range end up to `align` overflowed")
+    }
+
+    /// Expands the range so both bounds are multiples of `align`, returning `None`
+    /// if rounding the end up overflows `usize`.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn checked_align_outward(&self, align: usize) -> Option<Self> {
+        assert!(align.is_power_of_two(), "`align` must be a power of two");
+        let mask = align - 1;
+        match self.1.checked_add(mask) {
+            Some(e) => Some(Self(self.0 & !mask, e & !mask)),
+            None => None,
+        }
+    }
+
+    /// Shrinks the range to the aligned blocks it fully contains, rounding the start
+    /// up and the end down to multiples of `align`.
+    ///
+    /// If no whole block fits, the result is empty. It is the aligned empty range
+    /// when the rounded bounds meet, and otherwise the empty range at the original
+    /// start, so the result always stays within the original bounds.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(100, 9000).align_inward(4096), Range(4096, 8192));
+    /// assert!(Range(100, 4000).align_inward(4096).is_empty());
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn align_inward(&self, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "`align` must be a power of two");
+        let mask = align - 1;
+        let e = self.1 & !mask;
+        match self.0.checked_add(mask) {
+            Some(s) if s & !mask <= e => Self(s & !mask, e),
+            _ => Self(self.0, self.0),
+        }
+    }
+
+    /// Maps the range to a different resolution by multiplying both bounds by
+    /// `numerator / denominator`.
+    ///
+    /// The start is rounded down and the end up, so the scaled range always covers
+    /// everything the original covered. Empty ranges stay empty.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0 or a scaled bound does not fit in `usize`; see
+    /// [`checked_scale`](Self::checked_scale).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// // 44.1 kHz sample indices to 48 kHz.
+    /// assert_eq!(Range(441, 882).scale(480, 441), Range(480, 960));
+    /// assert_eq!(Range(1, 2).scale(1, 3), Range(0, 1));
+    /// ```
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn scale(&self, numerator: usize, denominator: usize) -> Self {
+        self.checked_scale(numerator, denominator)
+            .expect("scaled range bound does not fit in usize")
+    }
+
+    /// Like [`scale`](Self::scale), but returns `None` if a scaled bound does not fit
+    /// in `usize`.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0.
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn checked_scale(&self, numerator: usize, denominator: usize) -> Option<Self> {
+        assert!(denominator != 0, "scale denominator must be non-zero");
+        if self.is_empty() {
+            // Keep empty ranges empty (rounding the end up could otherwise grow
+            // them), positioned at the scaled start.
+            return match scaled_bound(self.0, numerator, denominator, false) {
+                Some(s) => Some(Self(s, s)),
+                None => None,
+            };
+        }
+        match (
+            scaled_bound(self.0, numerator, denominator, false),
+            scaled_bound(self.1, numerator, denominator, true),
+        ) {
+            (Some(s), Some(e)) => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Multiplies both bounds by `factor`, returning `None` if either overflows.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(2, 5).scale_by(4), Some(Range(8, 20)));
+    /// assert_eq!(Range(0, usize::MAX).scale_by(2), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn scale_by(&self, factor: usize) -> Option<Self> {
+        match (self.0.checked_mul(factor), self.1.checked_mul(factor)) {
+            (Some(s), Some(e)) => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Converts a range of `T` element indices into the byte range they occupy
+    /// in a `[T]`, returning `None` on overflow.
+    ///
+    /// Zero-sized types occupy no bytes, so they map to `Range(0, 0)`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(1, 3).elements_to_bytes::<u32>(), Some(Range(4, 12)));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn elements_to_bytes<T>(&self) -> Option<Self> {
+        self.scale_by(core::mem::size_of::<T>())
+    }
+
+    /// Converts a byte range within a `[T]` into the range of element indices it
+    /// covers, the inverse of [`elements_to_bytes`](Self::elements_to_bytes).
+    ///
+    /// Returns `None` if either bound is not a multiple of `size_of::<T>()`, or if
+    /// `T` is zero-sized.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(4, 12).bytes_to_elements::<u32>(), Some(Range(1, 3)));
+    /// assert_eq!(Range(4, 10).bytes_to_elements::<u32>(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn bytes_to_elements<T>(&self) -> Option<Self> {
+        let size = core::mem::size_of::<T>();
+        if size == 0 || !self.0.is_multiple_of(size) || !self.1.is_multiple_of(size) {
+            return None;
+        }
+        Some(Self(self.0 / size, self.1 / size))
+    }
+
+    /// Returns a mask with bits `start..end` set, or `None` if `end > 64`.
+    ///
+    /// Empty and inverted ranges yield `Some(0)` as long as `end` fits.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(2, 5).to_bitmask_u64(), Some(0b11100));
+    /// assert_eq!(Range(0, 64).to_bitmask_u64(), Some(u64::MAX));
+    /// assert_eq!(Range(0, 65).to_bitmask_u64(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn to_bitmask_u64(&self) -> Option<u64> {
+        if self.1 > 64 {
+            return None;
+        }
+        if self.0 >= self.1 {
+            return Some(0);
+        }
+        // `1 <= len <= 64` and `start < 64`, so neither shift overflows.
+        Some((u64::MAX >> (64 - (self.1 - self.0))) << self.0)
+    }
+
+    /// Returns a mask with bits `start..end` set, or `None` if `end > 128`.
+    ///
+    /// The `u128` counterpart of [`to_bitmask_u64`](Self::to_bitmask_u64).
+    #[must_use]
+    #[inline]
+    pub const fn to_bitmask_u128(&self) -> Option<u128> {
+        if self.1 > 128 {
+            return None;
+        }
+        if self.0 >= self.1 {
+            return Some(0);
+        }
+        Some((u128::MAX >> (128 - (self.1 - self.0))) << self.0)
+    }
+
+    /// Returns the range of set bits in `mask`, or `None` if they are not
+    /// contiguous. A zero mask yields the empty range `Range(0, 0)`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range::from_bitmask_u64(0b11100), Some(Range(2, 5)));
+    /// assert_eq!(Range::from_bitmask_u64(0b10100), None);
+    /// assert_eq!(Range::from_bitmask_u64(0), Some(Range(0, 0)));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_bitmask_u64(mask: u64) -> Option<Self> {
+        if mask == 0 {
+            return Some(Self(0, 0));
+        }
+        let start = mask.trailing_zeros();
+        let run = mask >> start;
+        // A run of ones plus one has no bits in common with it (wrapping for a
+        // full mask).
+        if run & run.wrapping_add(1) != 0 {
+            return None;
+        }
+        let start = start as usize;
+        Some(Self(start, start + run.trailing_ones() as usize))
+    }
+
+    /// Returns the range of set bits in `mask`, or `None` if they are not
+    /// contiguous.
+    ///
+    /// The `u128` counterpart of [`from_bitmask_u64`](Self::from_bitmask_u64).
+    #[must_use]
+    #[inline]
+    pub const fn from_bitmask_u128(mask: u128) -> Option<Self> {
+        if mask == 0 {
+            return Some(Self(0, 0));
+        }
+        let start = mask.trailing_zeros();
+        let run = mask >> start;
+        if run & run.wrapping_add(1) != 0 {
+            return None;
+        }
+        let start = start as usize;
+        Some(Self(start, start + run.trailing_ones() as usize))
+    }
+
+    /// Returns the index at fraction `t` of the way through the range, e.g. the
+    /// first visible line for a scrollbar at `t`.
+    ///
+    /// `t` is clamped to `[0, 1]` (NaN counts as 0) and `start + t * len` is
+    /// rounded to the nearest index, halves rounding up. The result is clamped to
+    /// the last index, so `t = 1` maps to `end - 1`; an empty range always yields
+    /// `start`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let lines = Range(0, 200);
+    /// assert_eq!(lines.lerp(0.0), 0);
+    /// assert_eq!(lines.lerp(0.25), 50);
+    /// assert_eq!(lines.lerp(1.0), 199);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn lerp(&self, t: f64) -> usize {
+        if self.is_empty() {
+            return self.0;
+        }
+        let len = self.len();
+        // Float-to-int casts saturate, and map NaN to 0.
+        let offset = (t.clamp(0.0, 1.0) * len as f64 + 0.5) as usize;
+        self.0 + offset.min(len - 1)
+    }
+
+    /// Returns the fractional position of `index` within the range: 0 at
+    /// `start`, approaching 1 towards `end`.
+    ///
+    /// Indices outside the range are clamped to it, so `end` and beyond map to 1.
+    /// An empty range yields 0. This is the inverse of [`lerp`](Self::lerp) up to
+    /// rounding: `r.lerp(r.position_of(i)) == i` for every `i` in the range, as
+    /// long as the length is exactly representable as an `f64` (below 2^53).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(100, 200).position_of(150), 0.5);
+    /// assert_eq!(Range(100, 200).position_of(50), 0.0);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn position_of(&self, index: usize) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        let offset = index.clamp(self.0, self.1) - self.0;
+        // Subtracting first keeps huge offsets exact until the conversion.
+        offset as f64 / self.len() as f64
+    }
+
+    /// Applies `f` to both bounds, first to `start` and then to `end`.
+    ///
+    /// The result is not re-validated, so a non-monotonic `f` can produce an
+    /// inverted range; see [`map_bounds_checked`](Self::map_bounds_checked).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// // Element indices to byte offsets of `u32`s after a 16-byte header.
+    /// assert_eq!(Range(1, 3).map_bounds(|i| 16 + i * 4), Range(20, 28));
+    /// ```
+    #[must_use]
+    pub fn map_bounds(self, mut f: impl FnMut(usize) -> usize) -> Self {
+        let start = f(self.0);
+        Self(start, f(self.1))
+    }
+
+    /// Applies the fallible `f` to both bounds, first to `start` and then to
+    /// `end`, returning `None` if either call does.
+    ///
+    /// `f` is not called for `end` if it already failed for `start`. Like
+    /// [`map_bounds`](Self::map_bounds), the result is not re-validated.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let table = [0, 10, 25, 40];
+    /// assert_eq!(Range(1, 3).try_map_bounds(|i| table.get(i).copied()), Some(Range(10, 40)));
+    /// assert_eq!(Range(1, 4).try_map_bounds(|i| table.get(i).copied()), None);
+    /// ```
+    #[must_use]
+    pub fn try_map_bounds(self, mut f: impl FnMut(usize) -> Option<usize>) -> Option<Self> {
+        let start = f(self.0)?;
+        Some(Self(start, f(self.1)?))
+    }
+
+    /// Like [`map_bounds`](Self::map_bounds), but returns `None` if the mapped
+    /// range would be inverted.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(2, 5).map_bounds_checked(|i| i * 2), Some(Range(4, 10)));
+    /// assert_eq!(Range(2, 5).map_bounds_checked(|i| 10 - i), None);
+    /// ```
+    #[must_use]
+    pub fn map_bounds_checked(self, f: impl FnMut(usize) -> usize) -> Option<Self> {
+        let r = self.map_bounds(f);
+        r.is_valid().then_some(r)
+    }
+
+    /// Shrinks the byte range to the nearest `char` boundaries of `s`, moving the
+    /// start forward and the end backward, so `&s[r]` cannot panic.
+    ///
+    /// Bounds past the end of `s` are clamped to `s.len()` first. If no whole
+    /// character remains, for example when the range lies inside a single
+    /// multibyte character, the result is an empty range at the snapped start.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let s = "aé€b"; // 'é' is 2 bytes, '€' is 3
+    /// assert_eq!(Range(2, 5).snap_to_char_boundaries(s), Range(3, 3));
+    /// assert_eq!(Range(0, 5).snap_to_char_boundaries(s), Range(0, 3));
+    /// ```
+    #[must_use]
+    pub fn snap_to_char_boundaries(&self, s: &str) -> Self {
+        let r = self.clamp_to(s.len());
+        let start = s.ceil_char_boundary(r.0);
+        let end = s.floor_char_boundary(r.1);
+        if start <= end {
+            Self(start, end)
+        } else {
+            Self(start, start)
+        }
+    }
+
+    /// Grows the byte range to the nearest `char` boundaries of `s`, moving the
+    /// start backward and the end forward, so the result covers every character
+    /// the range touches.
+    ///
+    /// Bounds past the end of `s` are clamped to `s.len()` first.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let s = "aé€b";
+    /// assert_eq!(Range(2, 5).snap_to_char_boundaries_outward(s), Range(1, 6));
+    /// ```
+    #[must_use]
+    pub fn snap_to_char_boundaries_outward(&self, s: &str) -> Self {
+        let r = self.clamp_to(s.len());
+        let start = s.floor_char_boundary(r.0);
+        let end = s.ceil_char_boundary(r.1);
+        if start <= end {
+            Self(start, end)
+        } else {
+            Self(start, start)
+        }
+    }
+
+    /// Shift the range by `delta` (adds to both start and end).
+    ///
+    /// This is also available as `range + delta`.
+    ///
+    /// Note: this does not check overflow — callers should ensure shifting is safe,
+    /// or use [`checked_offset`](Self::checked_offset) /
+    /// [`checked_sub_offset`](Self::checked_sub_offset) instead.
+    #[must_use]
+    #[inline]
+    pub const fn offset(&self, delta: usize) -> Self {
+        Self(self.0 + delta, self.1 + delta)
+    }
+
+    /// Shift the range by a signed `delta`; negative values move it toward zero.
+    ///
+    /// Like [`offset`](Self::offset) this does not check for overflow or underflow.
+    /// See [`checked_translate`](Self::checked_translate) and
+    /// [`saturating_translate`](Self::saturating_translate).
+    #[must_use]
+    #[inline]
+    pub const fn translate(&self, delta: isize) -> Self {
+        let abs = delta.unsigned_abs();
+        if delta >= 0 {
+            Self(self.0 + abs, self.1 + abs)
+        } else {
+            Self(self.0 - abs, self.1 - abs)
+        }
+    }
+
+    /// Shift the range by a signed `delta`, returning `None` if either bound would
+    /// go below zero or overflow `usize`.
+    #[must_use]
+    #[inline]
+    pub const fn checked_translate(&self, delta: isize) -> Option<Self> {
+        match (
+            self.0.checked_add_signed(delta),
+            self.1.checked_add_signed(delta),
+        ) {
+            (Some(s), Some(e)) => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Shift the range by a signed `delta`, stopping once a bound hits `0` or
+    /// `usize::MAX`.
+    ///
+    /// Both bounds always move by the same amount, so the length is preserved: the
+    /// range slides as far as it can in the requested direction and stops there.
+    ///
+    /// # Example
+    /// ```
+    /// let r = ars::range::Range::new(3, 7);
+    /// assert_eq!(r.saturating_translate(-10), ars::range::Range::new(0, 4));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn saturating_translate(&self, delta: isize) -> Self {
+        let abs = delta.unsigned_abs();
+        if delta >= 0 {
+            let hi = if self.0 > self.1 { self.0 } else { self.1 };
+            let abs = if abs > usize::MAX - hi {
+                usize::MAX - hi
+            } else {
+                abs
+            };
+            Self(self.0 + abs, self.1 + abs)
+        } else {
+            let lo = if self.0 < self.1 { self.0 } else { self.1 };
+            let abs = if abs > lo { lo } else { abs };
+            Self(self.0 - abs, self.1 - abs)
+        }
+    }
+
+    /// Attempt to shrink the range from the start and/or end by provided amounts.
+    ///
+    /// This will saturate at empty (i.e. not underflow): if the bounds would cross,
+    /// the result is the empty range at `start + start_shrink`, clamped to the
+    /// original end. The result thus stays within the original bounds, and is a
+    /// valid index into any slice the original was. An inverted input always
+    /// yields the empty range at its start.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// assert_eq!(Range(2, 7).shrink(1, 2), Range(3, 5));
+    /// assert_eq!(Range(2, 7).shrink(10, 0), Range(7, 7));
+    /// assert_eq!(Range(2, 7).shrink(1, 10), Range(3, 3));
+    /// ```
+    #[must_use]
+    pub const fn shrink(&self, start_shrink: usize, end_shrink: usize) -> Self {
+        // Use saturating arithmetic to avoid panic.
+        let s = self.0.saturating_add(start_shrink);
+        let e = self.1.saturating_sub(end_shrink);
+        if s < e {
+            return Self(s, e);
+        }
+        // Collapse to empty, but never past the original end (or past the start
+        // of an inverted range).
+        let max = if self.0 > self.1 { self.0 } else { self.1 };
+        let s = if s < max { s } else { max };
+        Self(s, s)
+    }
+
+    /// Grow the range outward, moving the start left by `start_grow` and the end
+    /// right by `end_grow`.
+    ///
+    /// The start saturates at 0 and the end at `usize::MAX`. See
+    /// [`checked_grow`](Self::checked_grow) for a variant that reports overflow.
+    #[must_use]
+    #[inline]
+    pub const fn grow(&self, start_grow: usize, end_grow: usize) -> Self {
+        Self(
+            self.0.saturating_sub(start_grow),
+            self.1.saturating_add(end_grow),
+        )
+    }
+
+    /// Grow the range like [`grow`](Self::grow) and then clamp the result to
+    /// `0..bound_len`.
+    ///
+    /// This is useful for adding context around a span before slicing.
+    ///
+    /// # Example
+    /// ```
+    /// let text = b"hello world";
+    /// let m = ars::range::Range::new(6, 11);
+    /// let padded = m.grow_within(3, 3, text.len());
+    /// assert_eq!(&text[padded], b"lo world");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn grow_within(&self, start_grow: usize, end_grow: usize, bound_len: usize) -> Self {
+        self.grow(start_grow, end_grow).clamp_to(bound_len)
+    }
+
+    /// Shift the range right by `delta`, returning `None` if either bound overflows.
+    #[must_use]
+    #[inline]
+    pub const fn checked_offset(&self, delta: usize) -> Option<Self> {
+        match (self.0.checked_add(delta), self.1.checked_add(delta)) {
+            (Some(s), Some(e)) => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Shift the range left by `delta`, returning `None` if either bound underflows.
+    #[must_use]
+    #[inline]
+    pub const fn checked_sub_offset(&self, delta: usize) -> Option<Self> {
+        match (self.0.checked_sub(delta), self.1.checked_sub(delta)) {
+            (Some(s), Some(e)) => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Shrink the range from the start and end by the provided amounts.
+    ///
+    /// Returns `None` if the arithmetic overflows or the result would be inverted.
+    /// Shrinking down to exactly empty is allowed.
+    #[must_use]
+    #[inline]
+    pub const fn checked_shrink(&self, start_shrink: usize, end_shrink: usize) -> Option<Self> {
+        match (
+            self.0.checked_add(start_shrink),
+            self.1.checked_sub(end_shrink),
+        ) {
+            (Some(s), Some(e)) if s <= e => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+
+    /// Grow the range outward by moving the start left and the end right.
+    ///
+    /// Returns `None` if the start would underflow, the end would overflow, or the
+    /// range is inverted to begin with.
+    #[must_use]
+    #[inline]
+    pub const fn checked_grow(&self, start_grow: usize, end_grow: usize) -> Option<Self> {
+        match (self.0.checked_sub(start_grow), self.1.checked_add(end_grow)) {
+            (Some(s), Some(e)) if s <= e => Some(Self(s, e)),
+            _ => None,
+        }
+    }
+}
+
+/// Splits an encoded range into the bytes of its start and end.
+const fn split_bounds(bytes: [u8; 16]) -> ([u8; 8], [u8; 8]) {
+    let mut start = [0; 8];
+    let mut end = [0; 8];
+    start.copy_from_slice(bytes.split_at(8).0);
+    end.copy_from_slice(bytes.split_at(8).1);
+    (start, end)
+}
+
+/// Clamps `x` into `lo..=hi`, usable in const contexts.
+#[inline]
+const fn clamp(x: usize, lo: usize, hi: usize) -> usize {
+    if x < lo {
+        lo
+    } else if x > hi {
+        hi
+    } else {
+        x
+    }
+}
+
+/// Computes `x * numerator / denominator` without intermediate overflow.
+#[inline]
+const fn scaled_bound(
+    x: usize,
+    numerator: usize,
+    denominator: usize,
+    round_up: bool,
+) -> Option<usize> {
+    let product = x as u128 * numerator as u128;
+    let d = denominator as u128;
+    let v = if round_up {
+        product.div_ceil(d)
+    } else {
+        product / d
+    };
+    if v > usize::MAX as u128 {
+        None
+    } else {
+        Some(v as usize)
+    }
+}
+
+impl<T> core::ops::Index<Range> for [T] {
+    type Output = [T];
+
+    fn index(&self, index: Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+impl<T> core::ops::Index<&Range> for [T] {
+    type Output = [T];
+
+    fn index(&self, index: &Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+impl<T> core::ops::IndexMut<Range> for [T] {
+    fn index_mut(&mut self, index: Range) -> &mut Self::Output {
+        &mut self[index.0..index.1]
+    }
+}
+
+impl<T> core::ops::IndexMut<&Range> for [T] {
+    fn index_mut(&mut self, index: &Range) -> &mut Self::Output {
+        &mut self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::Index<Range> for alloc::vec::Vec<T> {
+    type Output = [T];
+
+    fn index(&self, index: Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::Index<&Range> for alloc::vec::Vec<T> {
+    type Output = [T];
+
+    fn index(&self, index: &Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::IndexMut<Range> for alloc::vec::Vec<T> {
+    fn index_mut(&mut self, index: Range) -> &mut Self::Output {
+        &mut self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::IndexMut<&Range> for alloc::vec::Vec<T> {
+    fn index_mut(&mut self, index: &Range) -> &mut Self::Output {
+        &mut self[index.0..index.1]
+    }
+}
+
+impl core::ops::Index<Range> for str {
+    type Output = str;
+
+    fn index(&self, index: Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+impl core::ops::Index<&Range> for str {
+    type Output = str;
+
+    fn index(&self, index: &Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::ops::Index<Range> for alloc::string::String {
+    type Output = str;
+
+    fn index(&self, index: Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::ops::Index<&Range> for alloc::string::String {
+    type Output = str;
+
+    fn index(&self, index: &Range) -> &Self::Output {
+        &self[index.0..index.1]
+    }
+}
+
+impl core::ops::RangeBounds<usize> for Range {
+    fn start_bound(&self) -> core::ops::Bound<&usize> {
+        core::ops::Bound::Included(&self.0)
+    }
+
+    fn end_bound(&self) -> core::ops::Bound<&usize> {
+        core::ops::Bound::Excluded(&self.1)
+    }
+}
+
+impl core::ops::RangeBounds<usize> for &Range {
+    fn start_bound(&self) -> core::ops::Bound<&usize> {
+        core::ops::Bound::Included(&self.0)
+    }
+
+    fn end_bound(&self) -> core::ops::Bound<&usize> {
+        core::ops::Bound::Excluded(&self.1)
+    }
+}
+
+impl From<core::ops::Range<usize>> for Range {
+    fn from(r: core::ops::Range<usize>) -> Self {
+        Self(r.start, r.end)
+    }
+}
+
+impl From<Range> for core::ops::Range<usize> {
+    fn from(r: Range) -> Self {
+        r.0..r.1
+    }
+}
+
+/// Converts `start..=end` into `start..end + 1`, like
+/// [`Range::from_inclusive`].
+///
+/// Fails with [`TryFromRangeInclusiveError`] if `end` is `usize::MAX`, since the
+/// exclusive end would overflow. Empty inclusive ranges, including ones exhausted
+/// by iteration, convert to an empty range at their start rather than failing.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// assert_eq!(Range::try_from(2..=5), Ok(Range(2, 6)));
+/// assert!(Range::try_from(0..=usize::MAX).is_err());
+///
+/// let mut iter = 2..=3;
+/// iter.by_ref().for_each(drop);
+/// assert!(Range::try_from(iter).unwrap().is_empty());
+/// ```
+impl TryFrom<core::ops::RangeInclusive<usize>> for Range {
+    type Error = TryFromRangeInclusiveError;
+
+    fn try_from(r: core::ops::RangeInclusive<usize>) -> Result<Self, Self::Error> {
+        Self::from_inclusive(r).ok_or(TryFromRangeInclusiveError(()))
+    }
+}
+
+/// Parses `start..end` or `start..=end`.
+///
+/// Inverted input like `5..2` is rejected with [`ParseRangeError::InvalidRange`].
+/// Whitespace is not allowed anywhere in the input, matching the integer parsing
+/// of [`usize::from_str`](core::str::FromStr::from_str); trim the input first if
+/// needed. The output of the [`Display`](core::fmt::Display) impl parses back to
+/// the same range for every non-inverted range.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// assert_eq!("128..4096".parse(), Ok(Range(128, 4096)));
+/// assert_eq!("2..=5".parse(), Ok(Range(2, 6)));
+/// assert!("5..".parse::<Range>().is_err());
+/// ```
+impl core::str::FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, rest) = s
+            .split_once("..")
+            .ok_or(ParseRangeError::MissingSeparator)?;
+        let start = start.parse()?;
+        match rest.strip_prefix('=') {
+            Some(end) => {
+                let end = end.parse()?;
+                if start > end {
+                    return Err(InvalidRange { start, end }.into());
+                }
+                Ok(Self::try_from(start..=end)?)
+            }
+            None => Ok(Self::try_new(start, rest.parse()?)?),
+        }
+    }
+}
+
+impl From<(usize, usize)> for Range {
+    fn from(t: (usize, usize)) -> Self {
+        Self(t.0, t.1)
+    }
+}
+
+impl From<Range> for (usize, usize) {
+    fn from(r: Range) -> (usize, usize) {
+        (r.0, r.1)
+    }
+}
+
+impl PartialEq<core::ops::Range<usize>> for Range {
+    fn eq(&self, other: &core::ops::Range<usize>) -> bool {
+        self.0 == other.start && self.1 == other.end
+    }
+}
+
+impl PartialEq<Range> for core::ops::Range<usize> {
+    fn eq(&self, other: &Range) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<(usize, usize)> for Range {
+    fn eq(&self, other: &(usize, usize)) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+impl PartialEq<Range> for (usize, usize) {
+    fn eq(&self, other: &Range) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::collections::hash_map::DefaultHasher;
+    use std::format;
+    use std::hash::{Hash, Hasher};
+
+    #[test]
+    fn index_with_range_by_value() {
+        let s: &[i32] = &[10, 20, 30, 40, 50];
+        let r = Range::new(1, 4);
+        assert_eq!(&s[r], &[20, 30, 40]);
+    }
+
+    #[test]
+    fn index_with_range_by_ref() {
+        let s: &[i32] = &[1, 2, 3, 4];
+        let r = Range::new(0, 2);
+        assert_eq!(&s[&r], &[1, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn index_vec() {
+        let s = std::vec::Vec::from(&[1, 2, 3, 4]);
+        let r = Range::new(0, 2);
+        assert_eq!(&s[&r], &[1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_out_of_bounds_panics() {
+        let s: &[i32] = &[1, 2, 3];
+        // End > len should panic when used directly as an index.
+        let _ = &s[Range::new(2, 10)];
+    }
+
+    #[test]
+    fn index_mut_with_range_by_value() {
+        let mut buf = [0; 5];
+        let r = Range::new(1, 4);
+        buf[r].copy_from_slice(&[7, 8, 9]);
+        assert_eq!(buf, [0, 7, 8, 9, 0]);
+    }
+
+    #[test]
+    fn index_mut_with_range_by_ref() {
+        let mut buf = [0; 4];
+        let r = Range::new(2, 4);
+        buf[&r].fill(1);
+        assert_eq!(buf, [0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn index_mut_empty_and_full_ranges() {
+        let mut buf = [1, 2, 3];
+        buf[Range::new(1, 1)].copy_from_slice(&[]);
+        assert_eq!(buf, [1, 2, 3]);
+
+        let full = Range::new(0, buf.len());
+        buf[full].reverse();
+        assert_eq!(buf, [3, 2, 1]);
+        buf[&full].copy_from_slice(&[4, 5, 6]);
+        assert_eq!(buf, [4, 5, 6]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn index_mut_vec() {
+        let mut v = std::vec![1, 2, 3, 4];
+        v[Range::new(0, 2)].copy_from_slice(&[9, 9]);
+        v[&Range::new(3, 4)][0] = 0;
+        assert_eq!(v, [9, 9, 3, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_mut_out_of_bounds_panics() {
+        let mut buf = [0; 3];
+        buf[Range::new(1, 5)].fill(1);
+    }
+
+    #[test]
+    fn index_str() {
+        let s = "hello world";
+        assert_eq!(&s[Range::new(6, 11)], "world");
+        assert_eq!(&s[&Range::new(0, 5)], "hello");
+        assert_eq!(&s[Range::new(3, 3)], "");
+    }
+
+    #[test]
+    fn index_str_multibyte() {
+        // 'é' is 2 bytes, '€' is 3 bytes.
+        let s = "é-x-€";
+        assert_eq!(&s[Range::new(0, 2)], "é");
+        assert_eq!(&s[Range::new(2, 5)], "-x-");
+        assert_eq!(&s[Range::new(5, 8)], "€");
+        assert_eq!(&s[Range::new(0, s.len())], s);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_str_start_inside_char_panics() {
+        let s = "é-x-€";
+        let _ = &s[Range::new(1, 2)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_str_end_inside_char_panics() {
+        let s = "é-x-€";
+        let _ = &s[Range::new(2, 7)];
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn index_string() {
+        let s = std::string::String::from("ab€cd");
+        assert_eq!(&s[Range::new(2, 5)], "€");
+        assert_eq!(&s[&Range::new(5, 7)], "cd");
+    }
+
+    #[test]
+    fn range_bounds() {
+        use core::ops::{Bound, RangeBounds};
+
+        let r = Range::new(2, 5);
+        assert_eq!(r.start_bound(), Bound::Included(&2));
+        assert_eq!(r.end_bound(), Bound::Excluded(&5));
+        fn bounds_of(b: impl RangeBounds<usize>) -> (Bound<usize>, Bound<usize>) {
+            (b.start_bound().cloned(), b.end_bound().cloned())
+        }
+        let by_ref: &Range = &r;
+        assert_eq!(bounds_of(by_ref), r.bounds());
+        assert_eq!(bounds_of(r), r.bounds());
+        assert!(RangeBounds::contains(&r, &4));
+        assert!(!RangeBounds::contains(&r, &5));
+        assert!(!RangeBounds::contains(&Range::new(3, 3), &3));
+    }
+
+    #[test]
+    fn slice_get_with_bounds() {
+        let s = [1, 2, 3, 4];
+        assert_eq!(s.get(Range::new(1, 3).bounds()), Some(&[2, 3][..]));
+        assert_eq!(s.get(Range::new(4, 4).bounds()), Some(&[][..]));
+        assert_eq!(s.get(Range::new(3, 5).bounds()), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn range_bounds_with_std_apis() {
+        use std::collections::BTreeMap;
+        use std::string::String;
+        use std::vec::Vec;
+
+        let mut v = std::vec![0, 1, 2, 3, 4];
+        let drained: Vec<_> = v.drain(Range::new(1, 3)).collect();
+        assert_eq!(drained, [1, 2]);
+        assert_eq!(v, [0, 3, 4]);
+        let empty = Range::new(1, 1);
+        let by_ref: &Range = &empty;
+        assert_eq!(v.drain(by_ref).count(), 0);
+        assert_eq!(v, [0, 3, 4]);
+
+        let map: BTreeMap<usize, char> = [(1, 'a'), (3, 'b'), (5, 'c')].into_iter().collect();
+        let hits: Vec<_> = map.range(Range::new(2, 6)).map(|(_, c)| *c).collect();
+        assert_eq!(hits, ['b', 'c']);
+        assert_eq!(map.range(Range::new(2, 2)).count(), 0);
+
+        let mut s = String::from("hello world");
+        s.replace_range(Range::new(0, 5), "howdy");
+        assert_eq!(s, "howdy world");
+    }
+
+    #[test]
+    fn traits_and_hashing() {
+        let a = Range::new(2, 5);
+        let b = a; // Copy
+        #[allow(clippy::clone_on_copy)]
+        let c = a.clone(); // Clone
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert!(a >= Range::new(1, 4));
+        assert!(Range::new(0, 1) < Range::new(1, 2));
+
+        // Hash equality for equal values
+        let mut h1 = DefaultHasher::new();
+        a.hash(&mut h1);
+        let mut h2 = DefaultHasher::new();
+        c.hash(&mut h2);
+        assert_eq!(h1.finish(), h2.finish());
+
+        // Debug formatting uses range notation
+        let debug = format!("{:?}", a);
+        assert!(debug.contains("2"));
+        assert!(debug.contains("5"));
+    }
+
+    #[test]
+    fn len_nonzero_rejects_empty_and_inverted() {
+        assert_eq!(Range(3, 7).len_nonzero().map(|n| n.get()), Some(4));
+        assert_eq!(
+            Range(0, usize::MAX).len_nonzero().map(|n| n.get()),
+            Some(usize::MAX)
+        );
+        assert_eq!(Range(5, 5).len_nonzero(), None);
+        assert_eq!(Range(7, 3).len_nonzero(), None);
+        assert_eq!(Range(usize::MAX, 0).len_nonzero(), None);
+        const LEN: Option<core::num::NonZeroUsize> = Range(1, 2).len_nonzero();
+        assert!(LEN.is_some());
+    }
+
+    #[test]
+    fn helpers_len_empty_contains() {
+        assert_eq!(Range::new(2, 5).len(), 3);
+        assert!(Range::new(2, 2).is_empty());
+        assert!(Range::new(3, 4).contains(3));
+        assert!(!Range::new(3, 4).contains(4));
+        // inverted range yields zero length
+        assert_eq!(Range::new(5, 3).len(), 0);
+        assert!(Range::new(5, 3).is_empty());
+    }
+
+    #[test]
+    fn contains_range_boundaries() {
+        let r = Range(2, 5);
+        assert!(r.contains_range(&Range(2, 5)));
+        assert!(r.contains_range(&Range(2, 3)));
+        assert!(r.contains_range(&Range(4, 5)));
+        assert!(!r.contains_range(&Range(1, 5)));
+        assert!(!r.contains_range(&Range(2, 6)));
+        assert!(!r.contains_range(&Range(0, 10)));
+
+        assert!(Range(3, 4).is_subset_of(&r));
+        assert!(r.is_subset_of(&r));
+        assert!(!Range(0, 10).is_subset_of(&r));
+    }
+
+    #[test]
+    fn contains_range_empty() {
+        let r = Range(2, 5);
+        assert!(r.contains_range(&Range(2, 2)));
+        assert!(r.contains_range(&Range(5, 5)));
+        assert!(r.contains_range(&Range(3, 1)));
+        assert!(!r.contains_range(&Range(1, 1)));
+        assert!(!r.contains_range(&Range(6, 6)));
+
+        assert!(Range(3, 3).contains_range(&Range(3, 3)));
+        assert!(!Range(3, 3).contains_range(&Range(3, 4)));
+        assert!(!Range(5, 3).contains_range(&Range(4, 4)));
+    }
+
+    #[test]
+    fn clamp_to_range_positions() {
+        let bounds = Range(10, 20);
+        // Entirely left / right.
+        assert_eq!(Range(0, 5).clamp_to_range(&bounds), Range(10, 10));
+        assert_eq!(Range(25, 30).clamp_to_range(&bounds), Range(20, 20));
+        // Straddling either side or both.
+        assert_eq!(Range(5, 15).clamp_to_range(&bounds), Range(10, 15));
+        assert_eq!(Range(15, 25).clamp_to_range(&bounds), Range(15, 20));
+        assert_eq!(Range(0, 30).clamp_to_range(&bounds), bounds);
+        // Inside stays unchanged.
+        assert_eq!(Range(12, 18).clamp_to_range(&bounds), Range(12, 18));
+        // Inverted bounds collapse onto their start.
+        assert_eq!(Range(0, 30).clamp_to_range(&Range(8, 4)), Range(8, 8));
+    }
+
+    #[test]
+    fn clamp_to_range_agrees_with_intersect_when_overlapping() {
+        let bounds = Range(3, 9);
+        for s in 0..12 {
+            for e in s..12 {
+                let r = Range(s, e);
+                let clamped = r.clamp_to_range(&bounds);
+                assert!(bounds.contains_range(&clamped));
+                if let Some(i) = r.intersect(&bounds) {
+                    assert_eq!(clamped, i);
+                } else {
+                    assert!(clamped.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_at_reassembles() {
+        let buf = [10, 11, 12, 13, 14, 15, 16];
+        let r = Range(1, 6);
+        for index in r.start()..=r.end() {
+            let (left, right) = r.split_at(index);
+            assert_eq!(left.end(), right.start());
+            assert_eq!(left.len() + right.len(), r.len());
+            let mut joined = [0; 5];
+            joined[..left.len()].copy_from_slice(&buf[left]);
+            joined[left.len()..].copy_from_slice(&buf[right]);
+            assert_eq!(joined, buf[r]);
+            assert_eq!(r.try_split_at(index), Some((left, right)));
+        }
+    }
+
+    #[test]
+    fn split_at_clamps_out_of_range_index() {
+        let r = Range(2, 5);
+        assert_eq!(r.split_at(0), (Range(2, 2), Range(2, 5)));
+        assert_eq!(r.split_at(9), (Range(2, 5), Range(5, 5)));
+        assert_eq!(r.try_split_at(1), None);
+        assert_eq!(r.try_split_at(6), None);
+        assert_eq!(Range(5, 2).split_at(9), (Range(5, 5), Range(5, 2)));
+        assert_eq!(Range(5, 2).try_split_at(3), None);
+    }
+
+    #[test]
+    fn clamp_and_intersect() {
+        let r = Range::new(2, 10);
+        assert_eq!(r.clamp_to(5), Range::new(2, 5));
+        assert_eq!(r.clamp_to(1), Range::new(1, 1));
+
+        let a = Range::new(0, 5);
+        let b = Range::new(3, 8);
+        assert_eq!(a.intersect(&b), Some(Range::new(3, 5)));
+        assert_eq!(Range::new(0, 2).intersect(&Range::new(2, 4)), None);
+    }
+
+    #[test]
+    fn overlap_predicates() {
+        let a = Range(0, 5);
+        assert!(a.overlaps(&Range(4, 8)));
+        assert!(Range(4, 8).overlaps(&a));
+        assert!(a.overlaps(&Range(1, 2)));
+        assert!(!a.overlaps(&Range(5, 8)));
+        assert_eq!(a.overlap_len(&Range(3, 8)), 2);
+        assert_eq!(a.overlap_len(&Range(1, 2)), 1);
+        assert_eq!(a.overlap_len(&Range(5, 8)), 0);
+        assert_eq!(a.overlap_len(&Range(7, 9)), 0);
+
+        assert!(a.is_adjacent(&Range(5, 8)));
+        assert!(Range(5, 8).is_adjacent(&a));
+        assert!(!a.is_adjacent(&Range(6, 8)));
+        assert!(!a.is_adjacent(&Range(4, 8)));
+    }
+
+    #[test]
+    fn overlap_predicates_with_empty() {
+        let a = Range(0, 5);
+        assert!(!a.overlaps(&Range(2, 2)));
+        assert!(!Range(2, 2).overlaps(&a));
+        assert!(!a.overlaps(&Range(4, 1)));
+        assert_eq!(a.overlap_len(&Range(2, 2)), 0);
+        assert_eq!(a.overlap_len(&Range(4, 1)), 0);
+        assert!(!a.is_adjacent(&Range(5, 5)));
+        assert!(!Range(0, 0).is_adjacent(&a));
+    }
+
+    #[test]
+    fn overlap_predicates_agree_with_intersect() {
+        for a in 0..6 {
+            for b in 0..6 {
+                for c in 0..6 {
+                    for d in 0..6 {
+                        let x = Range(a, b);
+                        let y = Range(c, d);
+                        let i = x.intersect(&y);
+                        assert_eq!(x.overlaps(&y), i.is_some());
+                        assert_eq!(x.overlap_len(&y), i.map_or(0, |r| r.len()));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn midpoint_rounding() {
+        assert_eq!(Range(2, 6).midpoint(), 4);
+        assert_eq!(Range(2, 7).midpoint(), 4);
+        assert_eq!(Range(2, 3).midpoint(), 2);
+        assert_eq!(Range(5, 5).midpoint(), 5);
+        assert_eq!(Range(8, 3).midpoint(), 8);
+        assert_eq!(Range(0, usize::MAX).midpoint(), usize::MAX / 2);
+        assert_eq!(Range(usize::MAX - 1, usize::MAX).midpoint(), usize::MAX - 1);
+    }
+
+    #[test]
+    fn gap_and_distance() {
+        let buf = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let a = Range(1, 3);
+        let b = Range(6, 9);
+        assert_eq!(a.gap_to(&b), Some(Range(3, 6)));
+        assert_eq!(b.gap_to(&a), Some(Range(3, 6)));
+        assert_eq!(&buf[a.gap_to(&b).unwrap()], &[3, 4, 5]);
+        assert_eq!(a.distance_to(&b), 3);
+
+        // Touching and overlapping.
+        assert_eq!(a.gap_to(&Range(3, 5)), None);
+        assert_eq!(a.distance_to(&Range(3, 5)), 0);
+        assert_eq!(a.gap_to(&Range(2, 5)), None);
+        assert_eq!(a.distance_to(&Range(0, 9)), 0);
+        // Identical.
+        assert_eq!(a.gap_to(&a), None);
+        assert_eq!(a.distance_to(&a), 0);
+    }
+
+    #[test]
+    fn gap_and_distance_with_empty() {
+        let a = Range(1, 3);
+        assert_eq!(a.gap_to(&Range(5, 5)), Some(Range(3, 5)));
+        assert_eq!(Range(5, 5).gap_to(&a), Some(Range(3, 5)));
+        assert_eq!(a.gap_to(&Range(3, 3)), None);
+        assert_eq!(a.gap_to(&Range(2, 2)), None);
+        assert_eq!(Range(4, 4).gap_to(&Range(4, 4)), None);
+        assert_eq!(Range(4, 4).gap_to(&Range(7, 7)), Some(Range(4, 7)));
+        // Inverted ranges act as empty at their start.
+        assert_eq!(a.gap_to(&Range(6, 2)), Some(Range(3, 6)));
+        assert_eq!(a.distance_to(&Range(6, 2)), 3);
+    }
+
+    #[test]
+    fn union_and_hull() {
+        // Adjacent but not overlapping.
+        assert_eq!(Range(0, 3).union(&Range(3, 6)), Some(Range(0, 6)));
+        assert_eq!(Range(3, 6).union(&Range(0, 3)), Some(Range(0, 6)));
+        // Overlapping.
+        assert_eq!(Range(0, 4).union(&Range(2, 6)), Some(Range(0, 6)));
+        // Nested.
+        assert_eq!(Range(0, 10).union(&Range(2, 4)), Some(Range(0, 10)));
+        assert_eq!(Range(2, 4).hull(&Range(0, 10)), Range(0, 10));
+        // Disjoint.
+        assert_eq!(Range(0, 2).union(&Range(5, 7)), None);
+        assert_eq!(Range(0, 2).hull(&Range(5, 7)), Range(0, 7));
+        assert_eq!(Range(5, 7).hull(&Range(0, 2)), Range(0, 7));
+        // Identical.
+        assert_eq!(Range(2, 5).union(&Range(2, 5)), Some(Range(2, 5)));
+        assert_eq!(Range(2, 5).hull(&Range(2, 5)), Range(2, 5));
+    }
+
+    #[test]
+    fn union_and_hull_with_empty() {
+        let r = Range(2, 5);
+        assert_eq!(r.hull(&Range(9, 9)), r);
+        assert_eq!(Range(9, 9).hull(&r), r);
+        assert_eq!(r.union(&Range(9, 9)), Some(r));
+        assert_eq!(Range(9, 9).union(&r), Some(r));
+        assert_eq!(Range(7, 3).hull(&r), r);
+        assert_eq!(Range(1, 1).hull(&Range(4, 4)), Range(1, 1));
+    }
+
+    #[test]
+    fn difference_no_overlap() {
+        let r = Range(3, 6);
+        assert_eq!(r.difference(&Range(0, 2)), (None, Some(r)));
+        assert_eq!(r.difference(&Range(6, 9)), (Some(r), None));
+        assert_eq!(r.difference(&Range(0, 3)), (None, Some(r)));
+    }
+
+    #[test]
+    fn difference_covering() {
+        let r = Range(3, 6);
+        assert_eq!(r.difference(&Range(0, 10)), (None, None));
+        assert_eq!(r.difference(&r), (None, None));
+    }
+
+    #[test]
+    fn difference_strictly_inside() {
+        let r = Range(0, 10);
+        assert_eq!(
+            r.difference(&Range(4, 6)),
+            (Some(Range(0, 4)), Some(Range(6, 10)))
+        );
+    }
+
+    #[test]
+    fn difference_partial_overlap() {
+        let r = Range(3, 8);
+        assert_eq!(r.difference(&Range(0, 5)), (None, Some(Range(5, 8))));
+        assert_eq!(r.difference(&Range(6, 12)), (Some(Range(3, 6)), None));
+    }
+
+    #[test]
+    fn difference_with_empty() {
+        let r = Range(3, 8);
+        assert_eq!(r.difference(&Range(5, 5)), (Some(r), None));
+        assert_eq!(Range(4, 4).difference(&r), (None, None));
+        assert_eq!(Range(8, 2).difference(&Range(0, 1)), (None, None));
+    }
+
+    #[test]
+    fn complement_reassembles_buffer() {
+        let buf = [0, 1, 2, 3, 4, 5, 6, 7];
+        for s in 0..=buf.len() {
+            for e in s..=buf.len() + 2 {
+                let r = Range(s, e);
+                let (before, after) = r.complement(buf.len());
+                let mut joined = [0; 8];
+                let mut n = 0;
+                let clamped = r.clamp_to(buf.len());
+                for piece in [before, Some(clamped), after].into_iter().flatten() {
+                    joined[n..n + piece.len()].copy_from_slice(&buf[piece]);
+                    n += piece.len();
+                }
+                assert_eq!(n, buf.len());
+                assert_eq!(joined, buf);
+                assert!(before.is_none_or(|p| !p.is_empty()));
+                assert!(after.is_none_or(|p| !p.is_empty()));
+            }
+        }
+    }
+
+    #[test]
+    fn complement_edge_cases() {
+        assert_eq!(Range(0, 5).complement(5), (None, None));
+        assert_eq!(Range(0, 9).complement(5), (None, None));
+        assert_eq!(Range(2, 2).complement(5), (Some(Range(0, 5)), None));
+        assert_eq!(Range(4, 1).complement(5), (Some(Range(0, 5)), None));
+        assert_eq!(Range(7, 9).complement(5), (Some(Range(0, 5)), None));
+        assert_eq!(Range(0, 2).complement(5), (None, Some(Range(2, 5))));
+        assert_eq!(Range(3, 5).complement(5), (Some(Range(0, 3)), None));
+        assert_eq!(Range(1, 2).complement(0), (None, None));
+    }
+
+    #[test]
+    fn align_outward_rounds_away() {
+        let r = Range(100, 5000);
+        assert_eq!(r.align_outward(1), r);
+        assert_eq!(r.align_outward(4096), Range(0, 8192));
+        assert_eq!(r.align_outward(4), Range(100, 5000));
+        assert_eq!(Range(101, 4999).align_outward(4), Range(100, 5000));
+        assert_eq!(Range(4096, 8192).align_outward(4096), Range(4096, 8192));
+        assert_eq!(Range(5, 5).align_outward(8), Range(0, 8));
+        assert_eq!(Range(8, 8).align_outward(8), Range(8, 8));
+
+        assert_eq!(Range(0, usize::MAX).checked_align_outward(4096), None);
+        assert_eq!(
+            Range(0, usize::MAX).checked_align_outward(1),
+            Some(Range(0, usize::MAX))
+        );
+        assert_eq!(r.checked_align_outward(4096), Some(Range(0, 8192)));
+    }
+
+    #[test]
+    fn align_inward_rounds_toward() {
+        let r = Range(100, 9000);
+        assert_eq!(r.align_inward(1), r);
+        assert_eq!(r.align_inward(4096), Range(4096, 8192));
+        assert_eq!(Range(101, 4999).align_inward(4), Range(104, 4996));
+        assert_eq!(Range(4096, 8192).align_inward(4096), Range(4096, 8192));
+        // No whole block fits.
+        assert_eq!(Range(100, 4000).align_inward(4096), Range(100, 100));
+        assert_eq!(Range(4000, 4100).align_inward(4096), Range(4096, 4096));
+        assert_eq!(
+            Range(usize::MAX - 2, usize::MAX).align_inward(4096),
+            Range(usize::MAX - 2, usize::MAX - 2)
+        );
+        for a in [1, 2, 8, 4096] {
+            for s in [0, 1, 7, 4095, 4097] {
+                for e in [s, s + 1, s + 9000] {
+                    let inward = Range(s, e).align_inward(a);
+                    let outward = Range(s, e).align_outward(a);
+                    assert!(Range(s, e).contains_range(&inward));
+                    assert!(outward.contains_range(&Range(s, e)));
+                    assert_eq!(outward.start() % a, 0);
+                    assert_eq!(outward.end() % a, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`align` must be a power of two")]
+    fn align_non_power_of_two_panics() {
+        let _ = Range(0, 10).align_outward(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn align_outward_overflow_panics() {
+        let _ = Range(0, usize::MAX).align_outward(2);
+    }
+
+    #[test]
+    fn scale_rounds_outward() {
+        assert_eq!(Range(2, 5).scale(2, 1), Range(4, 10));
+        assert_eq!(Range(4, 10).scale(1, 2), Range(2, 5));
+        assert_eq!(Range(3, 7).scale(1, 2), Range(1, 4));
+        assert_eq!(Range(1, 2).scale(1, 3), Range(0, 1));
+        assert_eq!(Range(3, 3).scale(1, 2), Range(1, 1));
+        assert_eq!(Range(3, 3).scale(10, 1), Range(30, 30));
+        assert_eq!(Range(5, 2).scale(2, 1), Range(10, 10));
+    }
+
+    #[test]
+    fn scale_up_then_down_keeps_coverage() {
+        for (num, den) in [(3, 2), (480, 441), (7, 3), (1, 1), (5, 8)] {
+            for s in 0..30 {
+                for e in s..30 {
+                    let r = Range(s, e);
+                    let round_trip = r.scale(num, den).scale(den, num);
+                    if !r.is_empty() {
+                        assert!(round_trip.contains_range(&r), "{r} -> {round_trip}");
+                    }
+                    assert_eq!(r.scale(num, den).is_empty(), r.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checked_scale_overflow() {
+        assert_eq!(Range(0, usize::MAX).checked_scale(2, 1), None);
+        assert_eq!(
+            Range(0, usize::MAX).checked_scale(usize::MAX, usize::MAX),
+            Some(Range(0, usize::MAX))
+        );
+        assert_eq!(
+            Range(0, usize::MAX).checked_scale(1, 2),
+            Some(Range(0, usize::MAX / 2 + 1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "scale denominator must be non-zero")]
+    fn scale_zero_denominator_panics() {
+        let _ = Range(0, 4).scale(1, 0);
+    }
+
+    #[test]
+    fn offset_and_shrink() {
+        let r = Range::new(2, 7);
+        assert_eq!(r.offset(3), Range::new(5, 10));
+        assert_eq!(r.shrink(1, 2), Range::new(3, 5));
+        // shrinking more than length yields empty, clamped to the original end
+        assert!(r.shrink(10, 0).is_empty());
+        assert_eq!(r.shrink(10, 0), Range::new(7, 7));
+        assert_eq!(r.shrink(4, 4), Range::new(6, 6));
+        assert_eq!(r.shrink(usize::MAX, usize::MAX), Range::new(7, 7));
+    }
+
+    #[test]
+    fn shrink_result_indexes_any_slice_the_original_did() {
+        let buf = [0u8; 12];
+        for start in 0..=12 {
+            for end in start..=12 {
+                let r = Range(start, end);
+                for a in 0..15 {
+                    for b in 0..15 {
+                        let s = r.shrink(a, b);
+                        assert!(start <= s.start() && s.end() <= end, "{r:?} {a} {b}");
+                        assert!(
+                            buf[..end].get(s.start()..s.end()).is_some(),
+                            "{r:?} {a} {b}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checked_offsets() {
+        let r = Range::new(2, 7);
+        assert_eq!(r.checked_offset(3), Some(Range::new(5, 10)));
+        assert_eq!(r.checked_sub_offset(2), Some(Range::new(0, 5)));
+        assert_eq!(r.checked_sub_offset(3), None);
+
+        assert_eq!(Range::new(0, usize::MAX).checked_offset(1), None);
+        assert_eq!(
+            Range::new(0, usize::MAX - 1).checked_offset(1),
+            Some(Range::new(1, usize::MAX))
+        );
+        assert_eq!(
+            Range::new(usize::MAX, usize::MAX).checked_offset(0),
+            Some(Range::new(usize::MAX, usize::MAX))
+        );
+        assert_eq!(
+            Range::new(usize::MAX, usize::MAX).checked_sub_offset(usize::MAX),
+            Some(Range::new(0, 0))
+        );
+    }
+
+    #[test]
+    fn checked_shrink_and_grow() {
+        let r = Range::new(2, 7);
+        assert_eq!(r.checked_shrink(1, 2), Some(Range::new(3, 5)));
+        assert_eq!(r.checked_shrink(3, 2), Some(Range::new(5, 5)));
+        assert_eq!(r.checked_shrink(4, 2), None);
+        assert_eq!(r.checked_shrink(0, 8), None);
+        assert_eq!(
+            Range::new(1, usize::MAX).checked_shrink(usize::MAX, 0),
+            None
+        );
+
+        assert_eq!(r.checked_grow(2, 3), Some(Range::new(0, 10)));
+        assert_eq!(r.checked_grow(3, 0), None);
+        assert_eq!(Range::new(0, usize::MAX).checked_grow(0, 1), None);
+        assert_eq!(
+            Range::new(0, usize::MAX - 1).checked_grow(0, 1),
+            Some(Range::new(0, usize::MAX))
+        );
+        assert_eq!(Range::new(5, 3).checked_grow(0, 0), None);
+    }
+
+    #[test]
+    fn translate_both_directions() {
+        let r = Range::new(5, 8);
+        assert_eq!(r.translate(2), Range::new(7, 10));
+        assert_eq!(r.translate(-5), Range::new(0, 3));
+        assert_eq!(r.translate(0), r);
+
+        assert_eq!(r.checked_translate(-5), Some(Range::new(0, 3)));
+        assert_eq!(r.checked_translate(-6), None);
+        assert_eq!(Range::new(0, usize::MAX).checked_translate(1), None);
+        assert_eq!(Range::new(1, 2).checked_translate(isize::MIN), None);
+    }
+
+    #[test]
+    fn saturating_translate_keeps_length() {
+        let r = Range::new(3, 7);
+        assert_eq!(r.saturating_translate(-2), Range::new(1, 5));
+        assert_eq!(r.saturating_translate(-10), Range::new(0, 4));
+        assert_eq!(r.saturating_translate(isize::MIN), Range::new(0, 4));
+
+        let high = Range::new(usize::MAX - 4, usize::MAX - 1);
+        assert_eq!(
+            high.saturating_translate(isize::MAX),
+            Range::new(usize::MAX - 3, usize::MAX)
+        );
+        assert_eq!(high.saturating_translate(1), high.translate(1));
+    }
+
+    #[test]
+    fn translate_round_trips_when_not_clamped() {
+        let starts = [0, 1, 7, 1000, usize::MAX / 2, usize::MAX - 10];
+        let lens = [0, 1, 3, 10];
+        let deltas = [0, 1, -1, 5, -5, 999, -999, isize::MAX, -isize::MAX];
+        for &start in &starts {
+            for &len in &lens {
+                let r = Range::from((start, start + len));
+                for &d in &deltas {
+                    if let Some(moved) = r.checked_translate(d) {
+                        assert_eq!(moved, r.translate(d));
+                        assert_eq!(moved, r.saturating_translate(d));
+                        assert_eq!(moved.translate(-d), r);
+                        assert_eq!(moved.checked_translate(-d), Some(r));
+                        assert_eq!(moved.len(), r.len());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn try_new_validates() {
+        assert_eq!(Range::try_new(2, 5), Ok(Range(2, 5)));
+        assert_eq!(Range::try_new(3, 3), Ok(Range(3, 3)));
+        let err = Range::try_new(5, 2).unwrap_err();
+        assert_eq!(err.start(), 5);
+        assert_eq!(err.end(), 2);
+        assert_eq!(
+            format!("{err}"),
+            "invalid range 5..2: start is greater than end"
+        );
+        let _: &dyn core::error::Error = &err;
+    }
+
+    #[test]
+    fn from_start_len_and_setters() {
+        for (s, l) in [(0, 0), (3, 4), (10, 0), (usize::MAX - 5, 5)] {
+            assert_eq!(Range::from_start_len(s, l).len(), l);
+            assert_eq!(Range::from_start_len(s, l).start(), s);
+            assert_eq!(
+                Range::checked_from_start_len(s, l),
+                Some(Range::from_start_len(s, l))
+            );
+        }
+        assert_eq!(Range::checked_from_start_len(usize::MAX, 1), None);
+        assert_eq!(
+            Range::checked_from_start_len(usize::MAX, 0),
+            Some(Range(usize::MAX, usize::MAX))
+        );
+        assert_eq!(Range::checked_from_start_len(1, usize::MAX), None);
+
+        let r = Range(2, 6);
+        assert_eq!(r.with_start(10), Range(10, 14));
+        assert_eq!(r.with_end(4), Range(0, 4));
+        assert_eq!(r.with_len(1), Range(2, 3));
+        assert_eq!(r.with_len(0), Range(2, 2));
+        // Inverted ranges have length 0.
+        assert_eq!(Range(6, 2).with_start(1), Range(1, 1));
+    }
+
+    #[test]
+    fn inclusive_conversions() {
+        assert_eq!(Range::from_inclusive(2..=5), Some(Range(2, 6)));
+        assert_eq!(Range::from_inclusive(0..=0), Some(Range(0, 1)));
+        assert_eq!(Range::from_inclusive(0..=usize::MAX), None);
+        assert_eq!(
+            Range::from_inclusive(usize::MAX - 1..=usize::MAX - 1),
+            Some(Range(usize::MAX - 1, usize::MAX))
+        );
+        // Empty inclusive ranges become an empty range at their start.
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = 5..=2;
+        assert_eq!(Range::from_inclusive(inverted), Some(Range(5, 5)));
+
+        assert_eq!(Range(2, 6).to_inclusive(), Some(2..=5));
+        assert_eq!(
+            Range(0, usize::MAX).to_inclusive(),
+            Some(0..=usize::MAX - 1)
+        );
+        assert_eq!(Range(3, 3).to_inclusive(), None);
+        assert_eq!(Range(4, 1).to_inclusive(), None);
+    }
+
+    #[test]
+    fn try_from_inclusive() {
+        assert_eq!(Range::try_from(1..=3), Ok(Range(1, 4)));
+        let err = Range::try_from(1..=usize::MAX).unwrap_err();
+        assert_eq!(err, TryFromRangeInclusiveError(()));
+        assert!(format!("{err}").contains("overflow"));
+        assert_eq!(Range::try_from(usize::MAX..=usize::MAX), Err(err));
+        assert_eq!(Range::try_from(0..=0), Ok(Range(0, 1)));
+    }
+
+    #[test]
+    fn try_from_partially_iterated_inclusive() {
+        let mut iter = 2..=5;
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(Range::try_from(iter.clone()), Ok(Range(3, 5)));
+        assert_eq!(
+            Range::try_from(iter.clone())
+                .unwrap()
+                .iter()
+                .collect::<std::vec::Vec<_>>(),
+            iter.collect::<std::vec::Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn try_from_exhausted_inclusive_is_empty() {
+        let mut iter = 2..=4;
+        iter.by_ref().for_each(drop);
+        // `start` and `end` are now both 4, but the range is exhausted.
+        assert_eq!((*iter.start(), *iter.end()), (4, 4));
+        let r = Range::try_from(iter).unwrap();
+        assert!(r.is_empty());
+        assert_eq!(r, Range(4, 4));
+
+        // Exhausted at usize::MAX is empty, not an overflow error.
+        let mut iter = usize::MAX - 1..=usize::MAX;
+        iter.by_ref().for_each(drop);
+        assert_eq!(Range::try_from(iter).map(|r| r.is_empty()), Ok(true));
+    }
+
+    #[test]
+    fn grow_saturates() {
+        let r = Range(5, 8);
+        assert_eq!(r.grow(2, 3), Range(3, 11));
+        assert_eq!(r.grow(10, 0), Range(0, 8));
+        assert_eq!(r.grow(0, usize::MAX), Range(5, usize::MAX));
+        assert_eq!(r.grow(0, 0), r);
+        assert_eq!(r.grow(2, 3).shrink(2, 3), r);
+    }
+
+    #[test]
+    fn grow_within_stays_in_bounds() {
+        let buf = [1, 2, 3, 4, 5];
+        let r = Range(1, 3);
+        let padded = r.grow_within(4, 4, buf.len());
+        assert_eq!(padded, Range(0, 5));
+        assert_eq!(&buf[padded], &buf);
+        assert_eq!(&buf[r.grow_within(1, 1, buf.len())], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn from_str_exclusive_and_inclusive() {
+        assert_eq!("2..5".parse(), Ok(Range(2, 5)));
+        assert_eq!("3..3".parse(), Ok(Range(3, 3)));
+        assert_eq!("2..=5".parse(), Ok(Range(2, 6)));
+        assert_eq!("0..=0".parse(), Ok(Range(0, 1)));
+        let max = format!("0..{}", usize::MAX);
+        assert_eq!(max.parse(), Ok(Range(0, usize::MAX)));
+    }
+
+    #[test]
+    fn from_str_round_trips_display() {
+        for r in [Range(0, 0), Range(2, 5), Range(usize::MAX, usize::MAX)] {
+            assert_eq!(format!("{r}").parse(), Ok(r));
+        }
+    }
+
+    #[test]
+    fn from_str_errors() {
+        use core::str::FromStr;
+
+        assert_eq!(
+            Range::from_str("25"),
+            Err(ParseRangeError::MissingSeparator)
+        );
+        assert_eq!(Range::from_str(""), Err(ParseRangeError::MissingSeparator));
+        assert!(matches!(
+            Range::from_str("..5"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("5.."),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("5..="),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("a..b"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str(" 1..2"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("1 .. 2"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("1...2"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+        assert!(matches!(
+            Range::from_str("0..100000000000000000000"),
+            Err(ParseRangeError::InvalidInteger(_))
+        ));
+
+        assert_eq!(
+            Range::from_str("5..2"),
+            Err(ParseRangeError::InvalidRange(InvalidRange {
+                start: 5,
+                end: 2
+            }))
+        );
+        assert_eq!(
+            Range::from_str("5..=3"),
+            Err(ParseRangeError::InvalidRange(InvalidRange {
+                start: 5,
+                end: 3
+            }))
+        );
+        assert_eq!(
+            Range::from_str("5..=4"),
+            Err(ParseRangeError::InvalidRange(InvalidRange {
+                start: 5,
+                end: 4
+            }))
+        );
+
+        let inclusive_max = format!("5..={}", usize::MAX);
+        assert_eq!(
+            Range::from_str(&inclusive_max),
+            Err(ParseRangeError::InclusiveEndOverflow)
+        );
+    }
+
+    #[test]
+    fn conversions_roundtrip() {
+        let core: core::ops::Range<usize> = 1..4;
+        let r: Range = core.into();
+        assert_eq!(r, Range::new(1, 4));
+
+        let back: core::ops::Range<usize> = r.into();
+        assert_eq!(back, 1..4);
+
+        let t = (2usize, 6usize);
+        let rr: Range = t.into();
+        assert_eq!(rr, Range::new(2, 6));
+        let tup: (usize, usize) = rr.into();
+        assert_eq!(tup, (2, 6));
+    }
+
+    #[test]
+    fn is_valid_and_normalize() {
+        assert!(Range(2, 5).is_valid());
+        assert!(Range(3, 3).is_valid());
+        assert!(!Range(5, 2).is_valid());
+        assert_eq!(Range(5, 2).normalize(), Range(2, 5));
+        assert_eq!(Range(3, 3).normalize(), Range(3, 3));
+        assert_eq!(Range(usize::MAX, 0).normalize(), Range(0, usize::MAX));
+        assert!(Range(9, 1).normalize().is_valid());
+    }
+
+    #[test]
+    fn inverted_ranges_behave_as_empty() {
+        let r = Range(5, 2);
+        assert_eq!(r.len(), 0);
+        assert!(r.is_empty());
+        assert!(!r.contains(3));
+        assert!(!r.contains(5));
+    }
+
+    #[test]
+    fn inverted_input_to_intersect_clamp_to_and_shrink() {
+        // intersect: inverted on either side never intersects.
+        assert_eq!(Range(5, 2).intersect(&Range(0, 10)), None);
+        assert_eq!(Range(0, 10).intersect(&Range(5, 2)), None);
+        assert_eq!(Range(5, 2).intersect(&Range(5, 2)), None);
+        // clamp_to: bounds are clamped independently.
+        assert_eq!(Range(5, 2).clamp_to(10), Range(5, 2));
+        assert_eq!(Range(5, 2).clamp_to(3), Range(3, 2));
+        assert_eq!(Range(5, 4).clamp_to(3), Range(3, 3));
+        // shrink: always empty at the start.
+        assert_eq!(Range(5, 2).shrink(0, 0), Range(5, 5));
+        assert_eq!(Range(5, 2).shrink(1, 1), Range(5, 5));
+    }
+
+    #[test]
+    fn from_indices_covers_all() {
+        assert_eq!(Range::from_indices([5]), Some(Range(5, 6)));
+        assert_eq!(Range::from_indices([9, 0, 4]), Some(Range(0, 10)));
+        assert_eq!(Range::from_indices([3, 3, 3]), Some(Range(3, 4)));
+        assert_eq!(Range::from_indices(core::iter::empty()), None);
+        assert_eq!(
+            Range::from_indices([usize::MAX - 1]),
+            Some(Range(usize::MAX - 1, usize::MAX))
+        );
+        assert_eq!(Range::from_indices([0, usize::MAX]), None);
+
+        let hits = [2usize, 7, 5];
+        let r = Range::from_indices(hits).unwrap();
+        assert!(hits.iter().all(|&i| r.contains(i)));
+    }
+
+    #[test]
+    fn scale_by_checks_overflow() {
+        assert_eq!(Range(3, 7).scale_by(0), Some(Range(0, 0)));
+        assert_eq!(Range(3, 7).scale_by(1), Some(Range(3, 7)));
+        let max = usize::MAX;
+        assert_eq!(Range(0, max / 3).scale_by(3), Some(Range(0, max / 3 * 3)));
+        assert_eq!(Range(0, max / 3 + 1).scale_by(3), None);
+        assert_eq!(Range(max / 2 + 1, 0).scale_by(2), None);
+    }
+
+    #[test]
+    fn bitmask_round_trips() {
+        assert_eq!(Range(0, 64).to_bitmask_u64(), Some(u64::MAX));
+        assert_eq!(Range(0, 128).to_bitmask_u128(), Some(u128::MAX));
+        assert_eq!(Range(63, 64).to_bitmask_u64(), Some(1 << 63));
+        assert_eq!(Range(127, 128).to_bitmask_u128(), Some(1 << 127));
+        assert_eq!(Range(0, 1).to_bitmask_u64(), Some(1));
+        assert_eq!(Range(0, 65).to_bitmask_u64(), None);
+        assert_eq!(Range(64, 65).to_bitmask_u64(), None);
+        assert_eq!(Range(0, 129).to_bitmask_u128(), None);
+        assert_eq!(Range(7, 7).to_bitmask_u64(), Some(0));
+        assert_eq!(Range(40, 3).to_bitmask_u64(), Some(0));
+        assert_eq!(Range(64, 64).to_bitmask_u64(), Some(0));
+
+        assert_eq!(Range::from_bitmask_u64(u64::MAX), Some(Range(0, 64)));
+        assert_eq!(Range::from_bitmask_u128(u128::MAX), Some(Range(0, 128)));
+        assert_eq!(Range::from_bitmask_u64(1 << 63), Some(Range(63, 64)));
+        assert_eq!(Range::from_bitmask_u128(1 << 127), Some(Range(127, 128)));
+        assert_eq!(Range::from_bitmask_u64(u64::MAX << 1), Some(Range(1, 64)));
+        assert_eq!(Range::from_bitmask_u64(0), Some(Range(0, 0)));
+        assert_eq!(Range::from_bitmask_u128(0), Some(Range(0, 0)));
+        assert_eq!(Range::from_bitmask_u64(0b101), None);
+        assert_eq!(Range::from_bitmask_u64(1 | 1 << 63), None);
+        assert_eq!(Range::from_bitmask_u128(1 | 1 << 127), None);
+        assert_eq!(Range::from_bitmask_u128(u128::MAX ^ 1 << 64), None);
+
+        for start in 0..=64 {
+            for end in start..=64 {
+                let mask = Range(start, end).to_bitmask_u64().unwrap();
+                assert_eq!(mask.count_ones() as usize, end - start);
+                let expected = if start == end {
+                    Range(0, 0)
+                } else {
+                    Range(start, end)
+                };
+                assert_eq!(Range::from_bitmask_u64(mask), Some(expected));
+            }
+        }
+        for start in (0..=128).step_by(7) {
+            for end in start..=128 {
+                let mask = Range(start, end).to_bitmask_u128().unwrap();
+                let expected = if start == end {
+                    Range(0, 0)
+                } else {
+                    Range(start, end)
+                };
+                assert_eq!(Range::from_bitmask_u128(mask), Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn elements_and_bytes() {
+        let max = usize::MAX;
+        let limit = max / 8;
+        assert_eq!(
+            Range(0, limit).elements_to_bytes::<u64>(),
+            Some(Range(0, limit * 8))
+        );
+        assert_eq!(Range(0, limit + 1).elements_to_bytes::<u64>(), None);
+        assert_eq!(Range(2, 4).elements_to_bytes::<u8>(), Some(Range(2, 4)));
+        assert_eq!(
+            Range(2, 4).elements_to_bytes::<[u16; 3]>(),
+            Some(Range(12, 24))
+        );
+        assert_eq!(Range(2, 4).elements_to_bytes::<()>(), Some(Range(0, 0)));
+
+        for r in [
+            Range(0, 0),
+            Range(3, 9),
+            Range(0, limit),
+            Range(limit, limit),
+        ] {
+            let bytes = r.elements_to_bytes::<u64>().unwrap();
+            assert_eq!(bytes.bytes_to_elements::<u64>(), Some(r));
+        }
+        assert_eq!(Range(8, 17).bytes_to_elements::<u64>(), None);
+        assert_eq!(Range(7, 16).bytes_to_elements::<u64>(), None);
+        assert_eq!(Range(0, 0).bytes_to_elements::<()>(), None);
+
+        // The byte range covers the same elements in the byte view.
+        let words = [1u32, 2, 3, 4].map(u32::to_ne_bytes);
+        let bytes = words.as_flattened();
+        let b = Range(1, 3).elements_to_bytes::<u32>().unwrap();
+        assert_eq!(&bytes[b], words[1..3].as_flattened());
+    }
+
+    #[test]
+    fn lerp_and_position_of() {
+        let r = Range(10, 20);
+        assert_eq!(r.lerp(0.0), 10);
+        assert_eq!(r.lerp(0.5), 15);
+        assert_eq!(r.lerp(0.04), 10);
+        assert_eq!(r.lerp(0.05), 11);
+        assert_eq!(r.lerp(0.99), 19);
+        assert_eq!(r.lerp(1.0), 19);
+        assert_eq!(r.lerp(-3.0), 10);
+        assert_eq!(r.lerp(7.0), 19);
+        assert_eq!(r.lerp(f64::NAN), 10);
+        assert_eq!(r.lerp(f64::INFINITY), 19);
+
+        assert_eq!(r.position_of(10), 0.0);
+        assert_eq!(r.position_of(15), 0.5);
+        assert_eq!(r.position_of(19), 0.9);
+        assert_eq!(r.position_of(20), 1.0);
+        assert_eq!(r.position_of(0), 0.0);
+        assert_eq!(r.position_of(usize::MAX), 1.0);
+
+        // Empty ranges.
+        assert_eq!(Range(5, 5).lerp(0.7), 5);
+        assert_eq!(Range(9, 3).lerp(0.7), 9);
+        assert_eq!(Range(5, 5).position_of(5), 0.0);
+    }
+
+    #[test]
+    fn lerp_inverts_position_of() {
+        for len in 1..60 {
+            let r = Range(1000, 1000 + len);
+            for i in r {
+                assert_eq!(r.lerp(r.position_of(i)), i, "{r:?} {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn lerp_on_huge_ranges_is_exact_at_the_ends() {
+        let huge = Range(0, usize::MAX);
+        assert_eq!(huge.lerp(0.0), 0);
+        assert_eq!(huge.lerp(1.0), usize::MAX - 1);
+        assert_eq!(huge.position_of(0), 0.0);
+        assert_eq!(huge.position_of(usize::MAX), 1.0);
+
+        let high = Range(usize::MAX - 10, usize::MAX);
+        assert_eq!(high.lerp(0.0), usize::MAX - 10);
+        assert_eq!(high.lerp(0.5), usize::MAX - 5);
+        assert_eq!(high.lerp(1.0), usize::MAX - 1);
+        assert_eq!(high.position_of(usize::MAX - 5), 0.5);
+        for i in high {
+            assert_eq!(high.lerp(high.position_of(i)), i);
+        }
+    }
+
+    #[test]
+    fn pagination() {
+        extern crate std;
+        use std::vec::Vec;
+
+        // Exact multiple.
+        let pages: Vec<Range> = Range::pages(6, 3).collect();
+        assert_eq!(pages, [Range(0, 3), Range(3, 6)]);
+        assert_eq!(Range::num_pages(6, 3), 2);
+        // With a remainder.
+        let pages: Vec<Range> = Range::pages(7, 3).collect();
+        assert_eq!(pages, [Range(0, 3), Range(3, 6), Range(6, 7)]);
+        assert_eq!(Range::num_pages(7, 3), 3);
+        // No items.
+        assert_eq!(Range::pages(0, 10).next(), None);
+        assert_eq!(Range::num_pages(0, 10), 0);
+        assert_eq!(Range::num_pages(usize::MAX, 2), usize::MAX / 2 + 1);
+
+        for total in 0..40 {
+            for size in 1..9 {
+                let pages: Vec<Range> = Range::pages(total, size).collect();
+                assert_eq!(pages.len(), Range::num_pages(total, size));
+                for (i, page) in pages.iter().enumerate() {
+                    assert_eq!(*page, Range::page(i, size).clamp_to(total));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn page_overflow_and_zero_size() {
+        assert_eq!(Range::checked_page(0, 5), Some(Range(0, 5)));
+        assert_eq!(Range::checked_page(usize::MAX, 2), None);
+        assert_eq!(Range::checked_page(usize::MAX / 2, 2), None);
+        assert_eq!(
+            Range::checked_page(usize::MAX / 2 - 1, 2),
+            Some(Range(usize::MAX - 3, usize::MAX - 1))
+        );
+        assert_eq!(Range::checked_page(3, 0), None);
+
+        let cases: [fn(); 3] = [
+            || _ = Range::page(1, 0),
+            || _ = Range::pages(10, 0),
+            || _ = Range::num_pages(10, 0),
+        ];
+        for f in cases {
+            let err = std::panic::catch_unwind(f).unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<&str>(),
+                Some(&"page size must be non-zero")
+            );
+        }
+    }
+
+    #[test]
+    fn intersect_many_folds_and_short_circuits() {
+        let span = Range(10, 50);
+        assert_eq!(span.intersect_many([]), Some(span));
+        assert_eq!(Range(5, 5).intersect_many([]), None);
+        assert_eq!(
+            span.intersect_many([Range(0, 40), Range(20, 60), Range(15, 45)]),
+            Some(Range(20, 40))
+        );
+        assert_eq!(span.intersect_many([Range(50, 60)]), None);
+        assert_eq!(Range::intersect_all([Range(3, 8)]), Some(Range(3, 8)));
+        assert_eq!(Range::intersect_all([Range(3, 8), Range(8, 3)]), None);
+        assert_eq!(Range::intersect_all(core::iter::empty()), None);
+
+        // Ranges after the intersection becomes empty are never pulled.
+        let mut pulled = 0;
+        let ranges = [Range(0, 10), Range(5, 15), Range(20, 30), Range(0, 100)];
+        let result = Range::intersect_all(ranges.into_iter().inspect(|_| pulled += 1));
+        assert_eq!(result, None);
+        assert_eq!(pulled, 3);
+
+        let mut rest = [Range(0, 5), Range(50, 60), Range(0, 5)].into_iter();
+        assert_eq!(span.intersect_many(&mut rest), None);
+        assert_eq!(rest.len(), 2);
+    }
+
+    #[test]
+    fn from_ranges_is_hull_skipping_empty() {
+        assert_eq!(Range::from_ranges([Range(4, 6)]), Some(Range(4, 6)));
+        assert_eq!(
+            Range::from_ranges([Range(4, 6), Range(1, 2), Range(9, 9), Range(20, 3)]),
+            Some(Range(1, 6))
+        );
+        // Empty ranges don't stretch the hull even when far away.
+        assert_eq!(
+            Range::from_ranges([Range(0, 0), Range(4, 6), Range(100, 100)]),
+            Some(Range(4, 6))
+        );
+        assert_eq!(Range::from_ranges([Range(1, 1), Range(3, 2)]), None);
+        assert_eq!(Range::from_ranges(core::iter::empty()), None);
+    }
+
+    #[test]
+    fn including_grows_to_cover_index() {
+        assert_eq!(Range(2, 5).including(3), Range(2, 5));
+        assert_eq!(Range(2, 5).including(5), Range(2, 6));
+        assert_eq!(Range(2, 5).including(1), Range(1, 5));
+        // Empty and inverted ranges don't keep their stale start.
+        assert_eq!(Range(0, 0).including(7), Range(7, 8));
+        assert_eq!(Range(9, 3).including(5), Range(5, 6));
+        assert_eq!(Range(0, 0).checked_including(usize::MAX), None);
+        assert_eq!(
+            Range(0, 0).checked_including(usize::MAX - 1),
+            Some(Range(usize::MAX - 1, usize::MAX))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot include usize::MAX")]
+    fn including_usize_max_panics() {
+        let _ = Range(0, 1).including(usize::MAX);
+    }
+
+    #[test]
+    fn extend_to_include_matches_from_indices() {
+        let indices = [40, 3, 17, 3, 99, 0, 58];
+        let mut r = Range(50, 50);
+        for (n, &i) in indices.iter().enumerate() {
+            r.extend_to_include(i);
+            assert!(r.contains(i));
+            assert_eq!(Some(r), Range::from_indices(indices[..=n].iter().copied()));
+        }
+        assert_eq!(r, Range(0, 100));
+    }
+
+    #[test]
+    fn map_bounds_applies_start_then_end() {
+        let mut calls = std::vec::Vec::new();
+        let r = Range(3, 7).map_bounds(|x| {
+            calls.push(x);
+            x * 10
+        });
+        assert_eq!(r, Range(30, 70));
+        assert_eq!(calls, [3, 7]);
+        // Not re-validated.
+        assert_eq!(Range(1, 2).map_bounds(|x| 5 - x), Range(4, 3));
+    }
+
+    #[test]
+    fn try_map_bounds_short_circuits() {
+        let mut calls = 0;
+        let r = Range(1, 9).try_map_bounds(|x| {
+            calls += 1;
+            x.checked_sub(5)
+        });
+        assert_eq!(r, None);
+        assert_eq!(calls, 1);
+        assert_eq!(
+            Range(6, 9).try_map_bounds(|x| x.checked_sub(5)),
+            Some(Range(1, 4))
+        );
+        assert_eq!(
+            Range(6, 4).try_map_bounds(|x| x.checked_sub(1)),
+            Some(Range(5, 3))
+        );
+    }
+
+    #[test]
+    fn map_bounds_checked_rejects_inverted() {
+        assert_eq!(Range(1, 2).map_bounds_checked(|x| 5 - x), None);
+        assert_eq!(Range(2, 2).map_bounds_checked(|_| 0), Some(Range(0, 0)));
+        assert_eq!(Range(1, 4).map_bounds_checked(|x| x + 1), Some(Range(2, 5)));
+    }
+
+    // Evaluated at compile time; fails to build if any of these stop being const.
+    const TABLE: [Range; 6] = [
+        Range(2, 10).clamp_to(4),
+        match Range(0, 8).intersect(&Range(4, 12)) {
+            Some(r) => r,
+            None => Range(0, 0),
+        },
+        Range(1, 3).offset(2),
+        Range(1, 9).shrink(1, 2),
+        Range(2, 3).grow_within(4, 4, 6),
+        Range(3, 5).complement(8).1.unwrap(),
+    ];
+
+    #[test]
+    fn const_helpers() {
+        assert_eq!(
+            TABLE,
+            [
+                Range(2, 4),
+                Range(4, 8),
+                Range(3, 5),
+                Range(2, 7),
+                Range(0, 6),
+                Range(5, 8)
+            ]
+        );
+        const _: Option<Range> = Range(0, 1).intersect(&Range(1, 2));
+        const _: Range = Range(1, 4).translate(-1).hull(&Range(8, 9)).normalize();
+    }
+
+    #[test]
+    fn eq_with_core_ranges_and_tuples() {
+        let r = Range(2, 5);
+        assert_eq!(r, 2..5);
+        assert_eq!(2..5, r);
+        assert_ne!(r, 2..6);
+        assert_ne!(3..5, r);
+        assert_eq!(r, (2, 5));
+        assert_eq!((2, 5), r);
+        assert_ne!(r, (5, 2));
+        assert_ne!((2, 4), r);
+        // Inverted ranges compare by bounds, not as empty.
+        let inverted = core::ops::Range { start: 5, end: 2 };
+        assert_eq!(Range(5, 2), inverted);
+        assert_ne!(Range(4, 1), inverted);
+    }
+
+    #[test]
+    fn eq_interop_is_symmetric_and_hash_unaffected() {
+        let hash = |r: &Range| {
+            let mut h = DefaultHasher::new();
+            r.hash(&mut h);
+            h.finish()
+        };
+        for a in 0..4 {
+            for b in 0..4 {
+                let r = Range(a, b);
+                for c in 0..4 {
+                    for d in 0..4 {
+                        let core = c..d;
+                        assert_eq!(r == core, core == r);
+                        assert_eq!(r == (c, d), (c, d) == r);
+                        assert_eq!(r == core, r == Range(c, d));
+                        if r == core {
+                            assert_eq!(hash(&r), hash(&Range::from(core)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn byte_encoding_round_trips() {
+        for r in [
+            Range(0, 0),
+            Range(3, 9),
+            Range(9, 3),
+            Range(0x1234_5678, 0x9abc_def0),
+            Range(usize::MAX - 1, usize::MAX),
+        ] {
+            assert_eq!(Range::from_le_bytes(r.to_le_bytes()), r);
+            assert_eq!(Range::from_be_bytes(r.to_be_bytes()), r);
+            assert_eq!(Range::try_from_le_bytes(r.to_le_bytes()), Ok(r));
+            assert_eq!(Range::try_from_be_bytes(r.to_be_bytes()), Ok(r));
+        }
+    }
+
+    #[test]
+    fn byte_encoding_layout_is_pinned() {
+        let r = Range(0x0102_0304, 0x0a0b);
+        assert_eq!(
+            r.to_le_bytes(),
+            [4, 3, 2, 1, 0, 0, 0, 0, 0x0b, 0x0a, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            r.to_be_bytes(),
+            [0, 0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0, 0, 0, 0x0a, 0x0b]
+        );
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn byte_encoding_usize_max() {
+        let r = Range(0, usize::MAX);
+        let mut expected = [0; 16];
+        expected[8..].fill(0xff);
+        assert_eq!(r.to_le_bytes(), expected);
+        assert_eq!(r.to_be_bytes(), expected);
+        assert_eq!(
+            Range::from_le_bytes([0xff; 16]),
+            Range(usize::MAX, usize::MAX)
+        );
+    }
+
+    #[cfg(not(target_pointer_width = "64"))]
+    #[test]
+    fn byte_encoding_rejects_wide_bounds() {
+        assert!(Range::try_from_le_bytes([0xff; 16]).is_err());
+        assert!(Range::try_from_be_bytes([0xff; 16]).is_err());
+    }
+
+    #[test]
+    fn clamp_index_into_elements() {
+        let r = Range(10, 20);
+        assert_eq!(r.clamp_index(0), Some(10));
+        assert_eq!(r.clamp_index(10), Some(10));
+        assert_eq!(r.clamp_index(19), Some(19));
+        assert_eq!(r.clamp_index(20), Some(19));
+        assert_eq!(r.clamp_index(usize::MAX), Some(19));
+        assert_eq!(Range(4, 5).clamp_index(5), Some(4));
+        assert_eq!(Range(4, 4).clamp_index(4), None);
+        assert_eq!(Range(6, 4).clamp_index(5), None);
+        for i in 0..30 {
+            assert!(r.contains(r.clamp_index(i).unwrap()));
+        }
+    }
+
+    #[test]
+    fn clamp_index_allowing_end() {
+        let r = Range(10, 20);
+        assert_eq!(r.clamp_index_inclusive_end(0), 10);
+        assert_eq!(r.clamp_index_inclusive_end(19), 19);
+        assert_eq!(r.clamp_index_inclusive_end(20), 20);
+        assert_eq!(r.clamp_index_inclusive_end(21), 20);
+        assert_eq!(Range(4, 4).clamp_index_inclusive_end(4), 4);
+        assert_eq!(Range(4, 4).clamp_index_inclusive_end(9), 4);
+        assert_eq!(Range(6, 4).clamp_index_inclusive_end(0), 6);
+        assert_eq!(Range(6, 4).clamp_index_inclusive_end(9), 6);
+    }
+}
@@ -0,0 +1,199 @@
+//! [`NonEmptyRange`], a [`Range`] guaranteed to contain at least one index.
+
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+
+use super::{EmptyRangeError, Range};
+
+/// A [`Range`] with `start < end`, so it always contains at least one index.
+///
+/// It can only be constructed through [`new`](Self::new),
+/// [`from_range`](Self::from_range) or [`TryFrom<Range>`], which reject empty
+/// and inverted ranges. It dereferences to [`Range`] for all read-only methods,
+/// while methods that could produce an empty result return `Option<Self>`.
+///
+/// # Example
+/// ```
+/// use ars::range::{NonEmptyRange, Range};
+///
+/// let r = NonEmptyRange::new(2, 5).unwrap();
+/// assert_eq!((r.first(), r.last(), r.len().get()), (2, 4, 3));
+/// assert!(r.contains(3)); // via `Deref<Target = Range>`
+/// assert!(NonEmptyRange::try_from(Range(4, 4)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyRange(Range);
+
+impl NonEmptyRange {
+    /// Creates a range over `start..end`, or `None` if it would be empty or
+    /// inverted.
+    #[must_use]
+    #[inline]
+    pub const fn new(start: usize, end: usize) -> Option<Self> {
+        Self::from_range(Range(start, end))
+    }
+
+    /// Wraps `range`, or returns `None` if it is empty or inverted.
+    #[must_use]
+    #[inline]
+    pub const fn from_range(range: Range) -> Option<Self> {
+        if range.is_empty() {
+            None
+        } else {
+            Some(Self(range))
+        }
+    }
+
+    /// Returns the wrapped [`Range`].
+    #[must_use]
+    #[inline]
+    pub const fn get(&self) -> Range {
+        self.0
+    }
+
+    /// Returns the first index, `start`.
+    #[must_use]
+    #[inline]
+    pub const fn first(&self) -> usize {
+        self.0.0
+    }
+
+    /// Returns the last index, `end - 1`.
+    #[must_use]
+    #[inline]
+    pub const fn last(&self) -> usize {
+        self.0.1 - 1
+    }
+
+    /// Returns the number of indices, which is never zero.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> NonZeroUsize {
+        match NonZeroUsize::new(self.0.len()) {
+            Some(len) => len,
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the intersection with `other`, or `None` if they don't overlap.
+    #[must_use]
+    #[inline]
+    pub const fn intersect(&self, other: &Range) -> Option<Self> {
+        match self.0.intersect(other) {
+            Some(r) => Some(Self(r)),
+            None => None,
+        }
+    }
+
+    /// Shrinks the range like [`Range::shrink`], returning `None` if nothing would
+    /// remain.
+    #[must_use]
+    #[inline]
+    pub const fn shrink(&self, start_shrink: usize, end_shrink: usize) -> Option<Self> {
+        Self::from_range(self.0.shrink(start_shrink, end_shrink))
+    }
+}
+
+impl Deref for NonEmptyRange {
+    type Target = Range;
+
+    #[inline]
+    fn deref(&self) -> &Range {
+        &self.0
+    }
+}
+
+impl AsRef<Range> for NonEmptyRange {
+    #[inline]
+    fn as_ref(&self) -> &Range {
+        &self.0
+    }
+}
+
+impl TryFrom<Range> for NonEmptyRange {
+    type Error = EmptyRangeError;
+
+    fn try_from(range: Range) -> Result<Self, Self::Error> {
+        Self::from_range(range).ok_or(EmptyRangeError(()))
+    }
+}
+
+impl From<NonEmptyRange> for Range {
+    fn from(r: NonEmptyRange) -> Self {
+        r.0
+    }
+}
+
+impl<T> core::ops::Index<NonEmptyRange> for [T] {
+    type Output = [T];
+
+    fn index(&self, index: NonEmptyRange) -> &Self::Output {
+        &self[index.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+
+    #[test]
+    fn construction_rejects_empty_and_inverted() {
+        assert_eq!(NonEmptyRange::new(2, 2), None);
+        assert_eq!(NonEmptyRange::new(5, 2), None);
+        assert_eq!(NonEmptyRange::from_range(Range(0, 0)), None);
+        assert_eq!(
+            NonEmptyRange::try_from(Range(3, 1)),
+            Err(EmptyRangeError(()))
+        );
+        assert_eq!(std::format!("{}", EmptyRangeError(())), "range is empty");
+
+        let r = NonEmptyRange::new(2, 3).unwrap();
+        assert_eq!(NonEmptyRange::try_from(Range(2, 3)), Ok(r));
+        assert_eq!(NonEmptyRange::from_range(Range(2, 3)), Some(r));
+        const CONST: Option<NonEmptyRange> = NonEmptyRange::new(0, 1);
+        assert!(CONST.is_some());
+    }
+
+    #[test]
+    fn converts_back_to_range() {
+        let r = NonEmptyRange::new(4, 9).unwrap();
+        assert_eq!(Range::from(r), Range(4, 9));
+        assert_eq!(r.get(), Range(4, 9));
+        assert_eq!(*r, Range(4, 9));
+        assert_eq!(r.as_ref(), &Range(4, 9));
+        // Round trip.
+        assert_eq!(NonEmptyRange::try_from(Range::from(r)), Ok(r));
+    }
+
+    #[test]
+    fn accessors() {
+        let r = NonEmptyRange::new(4, 9).unwrap();
+        assert_eq!(r.first(), 4);
+        assert_eq!(r.last(), 8);
+        assert_eq!(r.len(), NonZeroUsize::new(5).unwrap());
+        let one = NonEmptyRange::new(7, 8).unwrap();
+        assert_eq!(one.first(), one.last());
+        let max = NonEmptyRange::new(usize::MAX - 1, usize::MAX).unwrap();
+        assert_eq!(max.last(), usize::MAX - 1);
+        // Range methods through `Deref`.
+        assert!(r.contains(8) && !r.contains(9));
+        assert_eq!(r.split_at(6), (Range(4, 6), Range(6, 9)));
+    }
+
+    #[test]
+    fn fallible_operations_return_option() {
+        let r = NonEmptyRange::new(4, 9).unwrap();
+        assert_eq!(r.intersect(&Range(7, 20)), NonEmptyRange::new(7, 9));
+        assert_eq!(r.intersect(&Range(9, 20)), None);
+        assert_eq!(r.shrink(1, 1), NonEmptyRange::new(5, 8));
+        assert_eq!(r.shrink(2, 3), None);
+        assert_eq!(r.shrink(10, 0), None);
+    }
+
+    #[test]
+    fn indexes_slices() {
+        let a = [1, 2, 3, 4];
+        assert_eq!(&a[NonEmptyRange::new(1, 3).unwrap()], [2, 3]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Arithmetic operator impls for [`Range`].
+//!
+//! Adding or subtracting a `usize` shifts both bounds, like
+//! [`Range::offset`]. Overflow follows integer semantics: it panics in debug
+//! builds and wraps in release builds.
+
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+use super::Range;
+
+impl Add<usize> for Range {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, delta: usize) -> Self::Output {
+        Self(self.0 + delta, self.1 + delta)
+    }
+}
+
+impl Add<usize> for &Range {
+    type Output = Range;
+
+    #[inline]
+    fn add(self, delta: usize) -> Self::Output {
+        *self + delta
+    }
+}
+
+impl Sub<usize> for Range {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, delta: usize) -> Self::Output {
+        Self(self.0 - delta, self.1 - delta)
+    }
+}
+
+impl Sub<usize> for &Range {
+    type Output = Range;
+
+    #[inline]
+    fn sub(self, delta: usize) -> Self::Output {
+        *self - delta
+    }
+}
+
+impl AddAssign<usize> for Range {
+    #[inline]
+    fn add_assign(&mut self, delta: usize) {
+        *self = *self + delta;
+    }
+}
+
+impl SubAssign<usize> for Range {
+    #[inline]
+    fn sub_assign(&mut self, delta: usize) {
+        *self = *self - delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_sub_shift_both_bounds() {
+        let r = Range(2, 5);
+        assert_eq!(r + 3, Range(5, 8));
+        assert_eq!(r - 2, Range(0, 3));
+        assert_eq!(&r + 3, Range(5, 8));
+        assert_eq!(&r - 2, Range(0, 3));
+        assert_eq!(r + 3, r.offset(3));
+    }
+
+    #[test]
+    fn assign_ops() {
+        let mut r = Range(2, 5);
+        r += 10;
+        assert_eq!(r, Range(12, 15));
+        r -= 12;
+        assert_eq!(r, Range(0, 3));
+    }
+
+    #[test]
+    fn add_then_sub_round_trips() {
+        for r in [Range(0, 0), Range(2, 5), Range(7, 3)] {
+            for d in [0, 1, 100, usize::MAX - 7] {
+                assert_eq!((r + d) - d, r);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn add_overflow_panics_in_debug() {
+        let _ = Range(0, usize::MAX) + 1;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn sub_underflow_panics_in_debug() {
+        let _ = Range(1, 4) - 2;
+    }
+}
@@ -0,0 +1,208 @@
+//! Orderings of [`Range`]s other than the derived one.
+//!
+//! `Range`'s own [`Ord`] compares by start, then end. The wrappers here are
+//! zero-cost newtypes with a different primary key, for sorting, `BinaryHeap`s
+//! and `BTreeMap` keys; for `sort_by`, use the matching `cmp_by_*` method of
+//! [`Range`] instead. Every ordering breaks ties until both bounds are compared,
+//! so it agrees with `Range`'s equality.
+//!
+//! # Example
+//! ```
+//! use ars::range::Range;
+//! use std::cmp::Reverse;
+//! use std::collections::BinaryHeap;
+//!
+//! // Largest-first allocation.
+//! let mut free: BinaryHeap<_> = [Range(0, 4), Range(10, 30), Range(40, 48)]
+//!     .into_iter()
+//!     .map(Range::by_len)
+//!     .collect();
+//! assert_eq!(free.pop().map(|r| r.0), Some(Range(10, 30)));
+//!
+//! // Sweep line: process the range ending first.
+//! let mut active: BinaryHeap<_> = [Range(0, 9), Range(2, 5)]
+//!     .into_iter()
+//!     .map(|r| Reverse(r.by_end()))
+//!     .collect();
+//! assert_eq!(active.pop().map(|r| r.0.0), Some(Range(2, 5)));
+//! ```
+
+use core::cmp::Ordering;
+
+use super::Range;
+
+/// Orders ranges by start, then by *decreasing* end, so a range sorts before
+/// the ranges it contains that share its start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ByStart(pub Range);
+
+/// Orders ranges by end, then by start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ByEnd(pub Range);
+
+/// Orders ranges by length, then by start (and so by end). Inverted ranges have
+/// length 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ByLen(pub Range);
+
+macro_rules! ordered_by {
+    ($name:ident, $cmp:ident) => {
+        impl Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.$cmp(&other.0)
+            }
+        }
+
+        impl PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl From<$name> for Range {
+            #[inline]
+            fn from(wrapped: $name) -> Self {
+                wrapped.0
+            }
+        }
+    };
+}
+
+ordered_by!(ByStart, cmp_by_start);
+ordered_by!(ByEnd, cmp_by_end);
+ordered_by!(ByLen, cmp_by_len);
+
+impl Range {
+    /// Wraps the range to order it as [`ByStart`].
+    #[must_use]
+    #[inline]
+    pub const fn by_start(self) -> ByStart {
+        ByStart(self)
+    }
+
+    /// Wraps the range to order it as [`ByEnd`].
+    #[must_use]
+    #[inline]
+    pub const fn by_end(self) -> ByEnd {
+        ByEnd(self)
+    }
+
+    /// Wraps the range to order it as [`ByLen`].
+    #[must_use]
+    #[inline]
+    pub const fn by_len(self) -> ByLen {
+        ByLen(self)
+    }
+
+    /// Compares by start, then by decreasing end, as [`ByStart`] does.
+    #[must_use]
+    #[inline]
+    pub fn cmp_by_start(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0).then(other.1.cmp(&self.1))
+    }
+
+    /// Compares by end, then by start, as [`ByEnd`] does.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::Range;
+    /// let mut v = [Range(0, 9), Range(3, 4), Range(1, 4)];
+    /// v.sort_by(Range::cmp_by_end);
+    /// assert_eq!(v, [Range(1, 4), Range(3, 4), Range(0, 9)]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn cmp_by_end(&self, other: &Self) -> Ordering {
+        self.1.cmp(&other.1).then(self.0.cmp(&other.0))
+    }
+
+    /// Compares by length, then by start, as [`ByLen`] does.
+    #[must_use]
+    #[inline]
+    pub fn cmp_by_len(&self, other: &Self) -> Ordering {
+        self.len().cmp(&other.len()).then_with(|| self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGES: [Range; 7] = [
+        Range(4, 6),
+        Range(0, 9),
+        Range(4, 10),
+        Range(2, 6),
+        Range(0, 2),
+        Range(7, 7),
+        Range(5, 3),
+    ];
+
+    #[test]
+    fn sorts_three_ways() {
+        let mut v = RANGES;
+        v.sort_by_key(|&r| r.by_start());
+        assert_eq!(
+            v,
+            [
+                Range(0, 9),
+                Range(0, 2),
+                Range(2, 6),
+                Range(4, 10),
+                Range(4, 6),
+                Range(5, 3),
+                Range(7, 7),
+            ]
+        );
+
+        v.sort_by_key(|&r| r.by_end());
+        assert_eq!(
+            v,
+            [
+                Range(0, 2),
+                Range(5, 3),
+                Range(2, 6),
+                Range(4, 6),
+                Range(7, 7),
+                Range(0, 9),
+                Range(4, 10),
+            ]
+        );
+
+        v.sort_by_key(|&r| r.by_len());
+        assert_eq!(
+            v,
+            [
+                Range(5, 3),
+                Range(7, 7),
+                Range(0, 2),
+                Range(4, 6),
+                Range(2, 6),
+                Range(4, 10),
+                Range(0, 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrappers_agree_with_cmp_methods() {
+        for a in RANGES {
+            for b in RANGES {
+                assert_eq!(a.by_start().cmp(&b.by_start()), a.cmp_by_start(&b));
+                assert_eq!(a.by_end().cmp(&b.by_end()), a.cmp_by_end(&b));
+                assert_eq!(a.by_len().partial_cmp(&b.by_len()), Some(a.cmp_by_len(&b)));
+                // Ties are broken all the way down to equality.
+                let equal = a == b;
+                assert_eq!(a.cmp_by_start(&b).is_eq(), equal);
+                assert_eq!(a.cmp_by_end(&b).is_eq(), equal);
+                assert_eq!(a.cmp_by_len(&b).is_eq(), equal);
+            }
+        }
+        assert_eq!(Range::from(Range(1, 2).by_len()), Range(1, 2));
+    }
+}
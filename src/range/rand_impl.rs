@@ -0,0 +1,137 @@
+//! [`rand`] support for [`Range`], enabled by the `rand` feature.
+
+use rand::distr::Distribution;
+use rand::{Rng, RngExt};
+
+use super::Range;
+
+impl Range {
+    /// Returns a uniformly random index within the range, or `None` if it is empty.
+    pub fn sample_index<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(rng.random_range(self.0..self.1))
+        }
+    }
+
+    /// Returns a uniformly random non-empty subrange of `self` whose length is at
+    /// most `max_len`.
+    ///
+    /// Every contained subrange with a length in `1..=max_len` is equally likely.
+    /// Returns `None` if `self` is empty or `max_len` is 0.
+    pub fn sample_subrange<R: Rng + ?Sized>(&self, rng: &mut R, max_len: usize) -> Option<Self> {
+        let max_len = max_len.min(self.len());
+        if max_len == 0 {
+            return None;
+        }
+        // Rejection sampling over (start, len) pairs keeps the distribution uniform
+        // over subranges; at least half of all pairs are accepted.
+        loop {
+            let start = rng.random_range(self.0..self.1);
+            let len = rng.random_range(1..=max_len);
+            if len <= self.1 - start {
+                return Some(Self(start, start + len));
+            }
+        }
+    }
+}
+
+/// A uniform distribution over the indices of a non-empty [`Range`].
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, UniformIndex};
+/// use rand::{RngExt, SeedableRng};
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+/// let dist = UniformIndex::new(Range(10, 20)).unwrap();
+/// let i: usize = rng.sample(dist);
+/// assert!((10..20).contains(&i));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UniformIndex(Range);
+
+impl UniformIndex {
+    /// Creates the distribution, returning `None` if `range` is empty.
+    #[must_use]
+    pub const fn new(range: Range) -> Option<Self> {
+        if range.is_empty() {
+            None
+        } else {
+            Some(Self(range))
+        }
+    }
+
+    /// Returns the range sampled from.
+    #[must_use]
+    pub const fn range(&self) -> Range {
+        self.0
+    }
+}
+
+impl Distribution<usize> for UniformIndex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        rng.random_range(self.0.0..self.0.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn sample_index_stays_in_range_and_reaches_endpoints() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let r = Range(3, 8);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let i = r.sample_index(&mut rng).unwrap();
+            assert!(r.contains(i));
+            seen[i - 3] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(Range(4, 4).sample_index(&mut rng), None);
+        assert_eq!(Range(6, 2).sample_index(&mut rng), None);
+    }
+
+    #[test]
+    fn sample_subrange_is_contained_and_bounded() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let r = Range(10, 20);
+        let mut longest = 0;
+        let mut touched_start = false;
+        let mut touched_end = false;
+        for _ in 0..2000 {
+            let sub = r.sample_subrange(&mut rng, 4).unwrap();
+            assert!(!sub.is_empty());
+            assert!(sub.len() <= 4);
+            assert!(r.contains_range(&sub));
+            longest = longest.max(sub.len());
+            touched_start |= sub.start() == r.start();
+            touched_end |= sub.end() == r.end();
+        }
+        assert_eq!(longest, 4);
+        assert!(touched_start && touched_end);
+
+        assert_eq!(r.sample_subrange(&mut rng, 0), None);
+        assert_eq!(Range(5, 5).sample_subrange(&mut rng, 3), None);
+        assert_eq!(Range(5, 6).sample_subrange(&mut rng, 3), Some(Range(5, 6)));
+    }
+
+    #[test]
+    fn uniform_index_distribution() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(UniformIndex::new(Range(2, 2)), None);
+
+        let dist = UniformIndex::new(Range(0, 3)).unwrap();
+        assert_eq!(dist.range(), Range(0, 3));
+        let mut counts = [0; 3];
+        for i in dist.sample_iter(&mut rng).take(3000) {
+            counts[i] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 800));
+    }
+}
@@ -0,0 +1,231 @@
+//! [`Rect`], a 2D region described by a pair of [`Range`]s.
+
+use core::iter::FusedIterator;
+
+use super::{Iter, Range};
+
+/// A rectangular region of a 2D grid: the rows in `rows` crossed with the columns
+/// in `cols`.
+///
+/// Grids stored as a flat buffer with a row stride are sliced one row at a time
+/// with [`row_ranges`](Self::row_ranges).
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, Rect};
+/// // A 4x3 grid stored row by row.
+/// let grid = [
+///     0, 1, 2, 3,
+///     4, 5, 6, 7,
+///     8, 9, 10, 11,
+/// ];
+/// let rect = Rect::new(Range(1, 3), Range(1, 3));
+/// let rows: Vec<&[i32]> = rect.row_ranges(4).map(|r| &grid[r]).collect();
+/// assert_eq!(rows, [&[5, 6][..], &[9, 10][..]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rect {
+    /// The rows covered.
+    pub rows: Range,
+    /// The columns covered.
+    pub cols: Range,
+}
+
+impl Rect {
+    /// Creates a rectangle from its row and column ranges.
+    #[must_use]
+    #[inline]
+    pub const fn new(rows: Range, cols: Range) -> Self {
+        Self { rows, cols }
+    }
+
+    /// Returns the number of columns.
+    #[must_use]
+    #[inline]
+    pub const fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the number of rows.
+    #[must_use]
+    #[inline]
+    pub const fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of cells, `width * height`.
+    ///
+    /// # Panics
+    /// Panics in debug builds if the product overflows.
+    #[must_use]
+    #[inline]
+    pub const fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    /// Returns `true` if the rectangle contains no cells.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.rows.is_empty() || self.cols.is_empty()
+    }
+
+    /// Returns `true` if the cell at `row`, `col` is inside the rectangle.
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, row: usize, col: usize) -> bool {
+        self.rows.contains(row) && self.cols.contains(col)
+    }
+
+    /// Returns the intersection of two rectangles, or `None` if they share no cell.
+    #[must_use]
+    #[inline]
+    pub const fn intersect(&self, other: &Self) -> Option<Self> {
+        match (
+            self.rows.intersect(&other.rows),
+            self.cols.intersect(&other.cols),
+        ) {
+            (Some(rows), Some(cols)) => Some(Self { rows, cols }),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the flat-buffer range of each row of the
+    /// rectangle, for a buffer storing rows of `stride` elements back to back.
+    ///
+    /// The columns are not checked against `stride`; columns past it spill into
+    /// the following row of the buffer.
+    ///
+    /// # Panics
+    /// Panics in debug builds if a computed offset overflows.
+    #[must_use]
+    #[inline]
+    pub const fn row_ranges(&self, stride: usize) -> RowRanges {
+        RowRanges {
+            rows: self.rows.iter(),
+            cols: self.cols,
+            stride,
+        }
+    }
+}
+
+/// An iterator over the flat-buffer range of each row of a [`Rect`].
+///
+/// Created by [`Rect::row_ranges`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RowRanges {
+    rows: Iter,
+    cols: Range,
+    stride: usize,
+}
+
+impl RowRanges {
+    #[inline]
+    fn row(&self, row: usize) -> Range {
+        self.cols.offset(row * self.stride)
+    }
+}
+
+impl Iterator for RowRanges {
+    type Item = Range;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| self.row(row))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.rows.nth(n).map(|row| self.row(row))
+    }
+}
+
+impl DoubleEndedIterator for RowRanges {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.rows.next_back().map(|row| self.row(row))
+    }
+}
+
+impl ExactSizeIterator for RowRanges {}
+
+impl FusedIterator for RowRanges {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    const W: usize = 7;
+    const H: usize = 5;
+
+    fn image() -> Vec<usize> {
+        (0..W * H).map(|i| i * 3 + 1).collect()
+    }
+
+    fn naive_extract(buf: &[usize], rect: Rect) -> Vec<usize> {
+        let mut out = Vec::new();
+        for row in rect.rows.start()..rect.rows.end() {
+            for col in rect.cols.start()..rect.cols.end() {
+                out.push(buf[row * W + col]);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn row_ranges_extract_sub_image() {
+        let buf = image();
+        for rows in [Range(0, H), Range(1, 4), Range(2, 2), Range(4, 5)] {
+            for cols in [Range(0, W), Range(2, 5), Range(6, 7), Range(3, 3)] {
+                let rect = Rect::new(rows, cols);
+                let blit: Vec<usize> = rect
+                    .row_ranges(W)
+                    .flat_map(|r| buf.as_slice()[r].iter().copied())
+                    .collect();
+                assert_eq!(blit, naive_extract(&buf, rect));
+                assert_eq!(blit.len(), rect.area());
+            }
+        }
+    }
+
+    #[test]
+    fn row_ranges_is_exact_and_double_ended() {
+        let rect = Rect::new(Range(1, 4), Range(2, 5));
+        let mut rows = rect.row_ranges(10);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.next_back(), Some(Range(32, 35)));
+        assert_eq!(rows.next(), Some(Range(12, 15)));
+        assert_eq!(rows.next(), Some(Range(22, 25)));
+        assert_eq!(rows.next(), None);
+        assert_eq!(rect.row_ranges(10).nth(2), Some(Range(32, 35)));
+    }
+
+    #[test]
+    fn dimensions_and_contains() {
+        let rect = Rect::new(Range(1, 4), Range(2, 7));
+        assert_eq!((rect.width(), rect.height(), rect.area()), (5, 3, 15));
+        assert!(!rect.is_empty());
+        assert!(rect.contains(1, 2));
+        assert!(rect.contains(3, 6));
+        assert!(!rect.contains(4, 2));
+        assert!(!rect.contains(1, 7));
+        assert!(Rect::new(Range(1, 1), Range(0, 5)).is_empty());
+    }
+
+    #[test]
+    fn intersect_rects() {
+        let a = Rect::new(Range(0, 4), Range(0, 4));
+        let b = Rect::new(Range(2, 6), Range(3, 8));
+        assert_eq!(a.intersect(&b), Some(Rect::new(Range(2, 4), Range(3, 4))));
+        // Overlapping rows but disjoint columns.
+        let c = Rect::new(Range(0, 4), Range(4, 8));
+        assert_eq!(a.intersect(&c), None);
+    }
+}
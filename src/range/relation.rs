@@ -0,0 +1,215 @@
+//! Classifying how two [`Range`]s relate, following Allen's interval algebra.
+
+use super::Range;
+
+/// How a range relates to another, as returned by [`Range::relation`].
+///
+/// For two non-empty ranges exactly one of the thirteen relations of Allen's
+/// interval algebra holds. Each variant reads as "`self` *variant* `other`", and
+/// the last six are the inverses of the first six. Since an empty range has no
+/// extent to compare, any comparison involving an empty (or inverted) range is
+/// [`Empty`](Self::Empty).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalRelation {
+    /// `self` ends before `other` starts, with a gap: `0..2` and `3..5`.
+    Precedes,
+    /// `self` ends exactly where `other` starts: `0..3` and `3..5`.
+    Meets,
+    /// `self` starts first and ends inside `other`: `0..4` and `2..6`.
+    Overlaps,
+    /// Both start together and `self` ends first: `0..2` and `0..5`.
+    Starts,
+    /// `self` lies strictly inside `other`: `2..3` and `0..5`.
+    During,
+    /// Both end together and `self` starts later: `3..5` and `0..5`.
+    Finishes,
+    /// Both ranges are identical.
+    Equals,
+    /// Inverse of [`Precedes`](Self::Precedes): `other` ends before `self`
+    /// starts, with a gap.
+    PrecededBy,
+    /// Inverse of [`Meets`](Self::Meets): `other` ends exactly where `self`
+    /// starts.
+    MetBy,
+    /// Inverse of [`Overlaps`](Self::Overlaps): `other` starts first and ends
+    /// inside `self`.
+    OverlappedBy,
+    /// Inverse of [`Starts`](Self::Starts): both start together and `other` ends
+    /// first.
+    StartedBy,
+    /// Inverse of [`During`](Self::During): `other` lies strictly inside `self`.
+    Contains,
+    /// Inverse of [`Finishes`](Self::Finishes): both end together and `other`
+    /// starts later.
+    FinishedBy,
+    /// At least one of the ranges is empty or inverted.
+    Empty,
+}
+
+impl IntervalRelation {
+    /// Returns the relation with the roles of `self` and `other` swapped.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::IntervalRelation;
+    /// assert_eq!(IntervalRelation::During.inverse(), IntervalRelation::Contains);
+    /// assert_eq!(IntervalRelation::Equals.inverse(), IntervalRelation::Equals);
+    /// ```
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Precedes => Self::PrecededBy,
+            Self::Meets => Self::MetBy,
+            Self::Overlaps => Self::OverlappedBy,
+            Self::Starts => Self::StartedBy,
+            Self::During => Self::Contains,
+            Self::Finishes => Self::FinishedBy,
+            Self::Equals => Self::Equals,
+            Self::PrecededBy => Self::Precedes,
+            Self::MetBy => Self::Meets,
+            Self::OverlappedBy => Self::Overlaps,
+            Self::StartedBy => Self::Starts,
+            Self::Contains => Self::During,
+            Self::FinishedBy => Self::Finishes,
+            Self::Empty => Self::Empty,
+        }
+    }
+}
+
+impl Range {
+    /// Classifies how `self` relates to `other`; see [`IntervalRelation`].
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{IntervalRelation, Range};
+    /// assert_eq!(Range(0, 3).relation(&Range(3, 5)), IntervalRelation::Meets);
+    /// assert_eq!(Range(2, 3).relation(&Range(0, 5)), IntervalRelation::During);
+    /// assert_eq!(Range(2, 2).relation(&Range(0, 5)), IntervalRelation::Empty);
+    /// ```
+    #[must_use]
+    pub const fn relation(&self, other: &Self) -> IntervalRelation {
+        use IntervalRelation::*;
+        let (a, b) = (self, other);
+        if a.is_empty() || b.is_empty() {
+            return Empty;
+        }
+        if a.1 < b.0 {
+            Precedes
+        } else if a.1 == b.0 {
+            Meets
+        } else if b.1 < a.0 {
+            PrecededBy
+        } else if b.1 == a.0 {
+            MetBy
+        } else if a.0 == b.0 {
+            if a.1 < b.1 {
+                Starts
+            } else if a.1 > b.1 {
+                StartedBy
+            } else {
+                Equals
+            }
+        } else if a.1 == b.1 {
+            if a.0 > b.0 { Finishes } else { FinishedBy }
+        } else if a.0 < b.0 {
+            if a.1 < b.1 { Overlaps } else { Contains }
+        } else if a.1 < b.1 {
+            During
+        } else {
+            OverlappedBy
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use IntervalRelation::*;
+
+    #[test]
+    fn one_example_per_variant() {
+        let b = Range(3, 6);
+        let cases = [
+            (Range(0, 2), Precedes),
+            (Range(0, 3), Meets),
+            (Range(1, 4), Overlaps),
+            (Range(3, 4), Starts),
+            (Range(4, 5), During),
+            (Range(4, 6), Finishes),
+            (Range(3, 6), Equals),
+            (Range(7, 9), PrecededBy),
+            (Range(6, 9), MetBy),
+            (Range(5, 8), OverlappedBy),
+            (Range(3, 8), StartedBy),
+            (Range(2, 7), Contains),
+            (Range(1, 6), FinishedBy),
+            (Range(4, 4), Empty),
+        ];
+        for (a, expected) in cases {
+            assert_eq!(a.relation(&b), expected, "{a:?} vs {b:?}");
+            assert_eq!(b.relation(&a), expected.inverse(), "{b:?} vs {a:?}");
+        }
+        assert_eq!(b.relation(&Range(5, 4)), Empty);
+        assert_eq!(Range(1, 1).relation(&Range(1, 1)), Empty);
+    }
+
+    #[test]
+    fn relation_is_consistent_with_predicates() {
+        const N: usize = 7;
+        for a0 in 0..N {
+            for a1 in 0..N {
+                for b0 in 0..N {
+                    for b1 in 0..N {
+                        let (a, b) = (Range(a0, a1), Range(b0, b1));
+                        let rel = a.relation(&b);
+                        assert_eq!(b.relation(&a), rel.inverse());
+                        assert_eq!(rel.inverse().inverse(), rel);
+                        if a.is_empty() || b.is_empty() {
+                            assert_eq!(rel, Empty);
+                            continue;
+                        }
+                        assert_eq!(
+                            a.overlaps(&b),
+                            !matches!(rel, Precedes | Meets | PrecededBy | MetBy)
+                        );
+                        assert_eq!(a.is_adjacent(&b), matches!(rel, Meets | MetBy));
+                        assert_eq!(
+                            a.contains_range(&b),
+                            matches!(rel, Equals | StartedBy | Contains | FinishedBy)
+                        );
+                        assert_eq!(
+                            b.contains_range(&a),
+                            matches!(rel, Equals | Starts | During | Finishes)
+                        );
+                        assert_eq!(a == b, rel == Equals);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn relation_matches_predicates_for_wide_ranges(
+            a in crate::range::strategy::any_range_with_inverted(),
+            b in crate::range::strategy::any_range_with_inverted(),
+        ) {
+            let rel = a.relation(&b);
+            proptest::prop_assert_eq!(b.relation(&a), rel.inverse());
+            if a.is_empty() || b.is_empty() {
+                proptest::prop_assert_eq!(rel, Empty);
+            } else {
+                proptest::prop_assert_eq!(
+                    a.overlaps(&b),
+                    !matches!(rel, Precedes | Meets | PrecededBy | MetBy)
+                );
+                proptest::prop_assert_eq!(a.is_adjacent(&b), matches!(rel, Meets | MetBy));
+                proptest::prop_assert_eq!(
+                    a.contains_range(&b),
+                    matches!(rel, Equals | StartedBy | Contains | FinishedBy)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! [`rkyv`] support for [`Range`], enabled by the `rkyv` feature.
+//!
+//! Ranges are archived as [`ArchivedRange`], a pair of little-endian `u64`s, so
+//! archives are portable between platforms regardless of their `usize` width.
+
+use rkyv::rancor::{Fallible, Source};
+use rkyv::rend::u64_le;
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use super::Range;
+
+/// The archived form of a [`Range`]: `start` and `end` as little-endian `u64`s.
+///
+/// Validation through `rkyv::access` only checks the bytes, not that
+/// `start <= end`, matching the unvalidated [`Range`] itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedRange {
+    /// The archived start (inclusive).
+    pub start: u64_le,
+    /// The archived end (exclusive).
+    pub end: u64_le,
+}
+
+impl ArchivedRange {
+    /// Returns the archived start.
+    #[must_use]
+    #[inline]
+    pub const fn start(&self) -> u64 {
+        self.start.to_native()
+    }
+
+    /// Returns the archived end.
+    #[must_use]
+    #[inline]
+    pub const fn end(&self) -> u64 {
+        self.end.to_native()
+    }
+
+    /// Converts back into a [`Range`], or `None` if a bound does not fit in `usize`
+    /// on this platform.
+    #[must_use]
+    pub fn to_range(&self) -> Option<Range> {
+        let start = usize::try_from(self.start()).ok()?;
+        let end = usize::try_from(self.end()).ok()?;
+        Some(Range(start, end))
+    }
+}
+
+impl PartialEq<Range> for ArchivedRange {
+    fn eq(&self, other: &Range) -> bool {
+        self.start() == other.0 as u64 && self.end() == other.1 as u64
+    }
+}
+
+impl Archive for Range {
+    type Archived = ArchivedRange;
+    type Resolver = ();
+
+    fn resolve(&self, (): Self::Resolver, out: Place<Self::Archived>) {
+        rkyv::munge::munge!(let ArchivedRange { start, end } = out);
+        start.write(u64_le::from_native(self.0 as u64));
+        end.write(u64_le::from_native(self.1 as u64));
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Range {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+/// Deserializing fails if a bound does not fit in `usize` on this platform.
+impl<D> Deserialize<Range, D> for ArchivedRange
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Range, D::Error> {
+        let start = usize::try_from(self.start()).map_err(D::Error::new)?;
+        let end = usize::try_from(self.end()).map_err(D::Error::new)?;
+        Ok(Range(start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use rkyv::rancor::Error;
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn round_trip() {
+        for r in [Range(0, 0), Range(3, 9), Range(7, 2), Range(0, usize::MAX)] {
+            let bytes = rkyv::to_bytes::<Error>(&r).unwrap();
+            assert_eq!(bytes.len(), 16);
+            let back = rkyv::from_bytes::<Range, Error>(&bytes).unwrap();
+            assert_eq!(back, r);
+        }
+    }
+
+    #[test]
+    fn round_trip_vec() {
+        let ranges = vec![Range(0, 4), Range(4, 10), Range(10, 11)];
+        let bytes = rkyv::to_bytes::<Error>(&ranges).unwrap();
+        let back = rkyv::from_bytes::<Vec<Range>, Error>(&bytes).unwrap();
+        assert_eq!(back, ranges);
+    }
+
+    #[test]
+    fn access_without_deserializing() {
+        let ranges = vec![Range(1, 2), Range(30, 400)];
+        let bytes = rkyv::to_bytes::<Error>(&ranges).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec<Range>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 2);
+        assert_eq!(archived[0], Range(1, 2));
+        assert_eq!(archived[1].start(), 30);
+        assert_eq!(archived[1].end(), 400);
+        assert_eq!(archived[1].to_range(), Some(Range(30, 400)));
+    }
+
+    #[test]
+    fn archived_bytes_are_little_endian() {
+        let bytes = rkyv::to_bytes::<Error>(&Range(1, 0x0203)).unwrap();
+        assert_eq!(bytes[..8], [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes[8..], [3, 2, 0, 0, 0, 0, 0, 0]);
+    }
+}
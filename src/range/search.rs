@@ -0,0 +1,144 @@
+//! Binary searches over sorted tables of [`Range`]s, such as line tables.
+
+use super::Range;
+
+/// Returns the position in `sorted` of the range containing `index`, or `None`
+/// if no range does.
+///
+/// `sorted` must be sorted and non-overlapping, with no inverted ranges;
+/// touching and empty ranges are fine. The search takes `O(log n)`.
+///
+/// # Panics
+/// In debug builds, panics if `sorted` is unsorted, overlapping or contains an
+/// inverted range.
+///
+/// # Example
+/// ```
+/// use ars::range::{find_containing, Range};
+/// // Byte ranges of the lines of "ab\ncde\n\nf".
+/// let lines = [Range(0, 3), Range(3, 7), Range(7, 8), Range(8, 9)];
+/// assert_eq!(find_containing(&lines, 4), Some(1));
+/// assert_eq!(find_containing(&lines, 7), Some(2));
+/// assert_eq!(find_containing(&lines, 9), None);
+/// ```
+#[must_use]
+#[track_caller]
+pub fn find_containing(sorted: &[Range], index: usize) -> Option<usize> {
+    debug_assert_sorted(sorted);
+    let i = sorted.partition_point(|r| r.end() <= index);
+    sorted.get(i).filter(|r| r.start() <= index).map(|_| i)
+}
+
+/// Returns the positions in `sorted` of the ranges overlapping `query`, as a
+/// range of slice indices.
+///
+/// The matches are contiguous, so the result can index `sorted` directly. Empty
+/// ranges in `sorted` lying among the matches or within `query` are included. An
+/// empty or inverted `query` matches nothing, yielding an empty range at the
+/// position where it would be inserted.
+///
+/// `sorted` has the same requirements as for [`find_containing`], and the
+/// search takes `O(log n)`.
+///
+/// # Panics
+/// In debug builds, panics if `sorted` is unsorted, overlapping or contains an
+/// inverted range.
+///
+/// # Example
+/// ```
+/// use ars::range::{find_overlapping, Range};
+/// let lines = [Range(0, 3), Range(3, 7), Range(7, 8), Range(8, 9)];
+/// // A selection from the middle of line 1 to the start of line 3.
+/// let selected = find_overlapping(&lines, Range(5, 9));
+/// assert_eq!(selected, Range(1, 4));
+/// assert_eq!(&lines[selected], [Range(3, 7), Range(7, 8), Range(8, 9)]);
+/// ```
+#[must_use]
+#[track_caller]
+pub fn find_overlapping(sorted: &[Range], query: Range) -> Range {
+    debug_assert_sorted(sorted);
+    let lo = sorted.partition_point(|r| r.end() <= query.start());
+    if query.is_empty() {
+        return Range(lo, lo);
+    }
+    let hi = sorted[lo..].partition_point(|r| r.start() < query.end());
+    Range(lo, lo + hi)
+}
+
+#[track_caller]
+fn debug_assert_sorted(sorted: &[Range]) {
+    debug_assert!(
+        sorted.iter().flat_map(|r| [r.start(), r.end()]).is_sorted(),
+        "ranges must be sorted, non-overlapping and not inverted"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINES: [Range; 5] = [
+        Range(0, 4),
+        Range(4, 9),
+        Range(9, 10),
+        Range(12, 15),
+        Range(15, 20),
+    ];
+
+    #[test]
+    fn containing_at_boundaries() {
+        assert_eq!(find_containing(&LINES, 0), Some(0));
+        assert_eq!(find_containing(&LINES, 3), Some(0));
+        assert_eq!(find_containing(&LINES, 4), Some(1));
+        assert_eq!(find_containing(&LINES, 9), Some(2));
+        assert_eq!(find_containing(&LINES, 10), None);
+        assert_eq!(find_containing(&LINES, 11), None);
+        assert_eq!(find_containing(&LINES, 12), Some(3));
+        assert_eq!(find_containing(&LINES, 15), Some(4));
+        assert_eq!(find_containing(&LINES, 19), Some(4));
+        assert_eq!(find_containing(&LINES, 20), None);
+        assert_eq!(find_containing(&LINES, usize::MAX), None);
+        assert_eq!(find_containing(&[], 0), None);
+        // Empty ranges contain nothing.
+        assert_eq!(find_containing(&[Range(0, 2), Range(2, 2)], 2), None);
+    }
+
+    #[test]
+    fn containing_agrees_with_linear_scan() {
+        for index in 0..25 {
+            let expected = LINES.iter().position(|r| r.contains(index));
+            assert_eq!(find_containing(&LINES, index), expected, "{index}");
+        }
+    }
+
+    #[test]
+    fn overlapping_queries() {
+        assert_eq!(find_overlapping(&LINES, Range(0, 20)), Range(0, 5));
+        assert_eq!(find_overlapping(&LINES, Range(3, 5)), Range(0, 2));
+        // Ending exactly at a boundary excludes the next range.
+        assert_eq!(find_overlapping(&LINES, Range(4, 9)), Range(1, 2));
+        // A query within a hole matches nothing.
+        assert_eq!(find_overlapping(&LINES, Range(10, 12)), Range(3, 3));
+        assert_eq!(find_overlapping(&LINES, Range(20, 30)), Range(5, 5));
+        assert_eq!(find_overlapping(&LINES, Range(6, 6)), Range(1, 1));
+        assert_eq!(find_overlapping(&LINES, Range(13, 2)), Range(3, 3));
+        assert_eq!(find_overlapping(&[], Range(0, 5)), Range(0, 0));
+
+        for start in 0..22 {
+            for end in start + 1..23 {
+                let query = Range(start, end);
+                let found = find_overlapping(&LINES, query);
+                for (i, r) in LINES.iter().enumerate() {
+                    assert_eq!(found.contains(i), r.overlaps(&query), "{query:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "ranges must be sorted"]
+    fn rejects_overlapping_input() {
+        let _ = find_containing(&[Range(0, 5), Range(4, 8)], 0);
+    }
+}
@@ -0,0 +1,262 @@
+//! [`Selection`], an editor-style list of ranges with a primary one.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::Range;
+
+/// A non-empty list of ranges, one of which is the primary, like the cursors and
+/// selections of a multi-cursor editor.
+///
+/// Unlike a [`RangeSet`](super::RangeSet), ranges keep their insertion order and
+/// may overlap until [`normalize`](Self::normalize) is called, and empty ranges
+/// (plain cursors) are kept.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, Selection};
+///
+/// let mut sel = Selection::new(Range(10, 15));
+/// sel.add(Range(0, 5));
+/// sel.add(Range(3, 8));
+/// assert_eq!(sel.primary(), Range(3, 8));
+///
+/// sel.normalize();
+/// assert_eq!(sel.as_slice(), [Range(0, 8), Range(10, 15)]);
+/// assert_eq!(sel.primary(), Range(0, 8));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Selection {
+    ranges: Vec<Range>,
+    primary: usize,
+}
+
+impl Selection {
+    /// Creates a selection holding only `primary`.
+    #[must_use]
+    pub fn new(primary: Range) -> Self {
+        Self {
+            ranges: vec![primary],
+            primary: 0,
+        }
+    }
+
+    /// Returns the ranges in order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Range] {
+        &self.ranges
+    }
+
+    /// Returns an iterator over the ranges in order.
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, Range>> {
+        self.ranges.iter().copied()
+    }
+
+    /// Returns the primary range.
+    #[must_use]
+    pub fn primary(&self) -> Range {
+        self.ranges[self.primary]
+    }
+
+    /// Returns the position of the primary range in [`as_slice`](Self::as_slice).
+    #[must_use]
+    pub fn primary_index(&self) -> usize {
+        self.primary
+    }
+
+    /// Makes the range at `index` the primary one.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set_primary(&mut self, index: usize) {
+        assert!(index < self.ranges.len(), "selection index out of bounds");
+        self.primary = index;
+    }
+
+    /// Appends `range` and makes it the primary range.
+    pub fn add(&mut self, range: Range) {
+        self.ranges.push(range);
+        self.primary = self.ranges.len() - 1;
+    }
+
+    /// Removes the first range equal to `range`, or [`add`](Self::add)s it if
+    /// there is none. Returns `true` if `range` was added.
+    ///
+    /// The last remaining range is never removed. When the primary range is
+    /// removed, the range before it (or the new first range) becomes primary.
+    pub fn toggle(&mut self, range: Range) -> bool {
+        match self.ranges.iter().position(|&r| r == range) {
+            Some(_) if self.ranges.len() == 1 => false,
+            Some(i) => {
+                self.ranges.remove(i);
+                if self.primary > i || (self.primary == i && i > 0) {
+                    self.primary -= 1;
+                }
+                false
+            }
+            None => {
+                self.add(range);
+                true
+            }
+        }
+    }
+
+    /// Sorts the ranges and merges overlapping ones.
+    ///
+    /// Ranges that only touch stay separate, as do distinct empty ranges, but an
+    /// empty range strictly inside another is merged into it and identical ranges
+    /// collapse into one. The merged range containing the old primary becomes
+    /// primary.
+    pub fn normalize(&mut self) {
+        let primary = self.primary;
+        let mut tagged: Vec<(Range, bool)> = self
+            .ranges
+            .iter()
+            .enumerate()
+            .map(|(i, &r)| (r.normalize(), i == primary))
+            .collect();
+        tagged.sort_by_key(|&(r, _)| r);
+
+        self.ranges.clear();
+        for (r, is_primary) in tagged {
+            match self.ranges.last_mut() {
+                Some(last) if r.start() < last.end() || r == *last => {
+                    if r.end() > last.end() {
+                        last.1 = r.end();
+                    }
+                }
+                _ => self.ranges.push(r),
+            }
+            if is_primary {
+                self.primary = self.ranges.len() - 1;
+            }
+        }
+    }
+
+    /// Replaces every range by `f(range)`, e.g. to move them through an
+    /// [`Edit`](super::edit::Edit).
+    ///
+    /// The order and primary are kept; call [`normalize`](Self::normalize)
+    /// afterwards if ranges may now overlap.
+    pub fn map(&mut self, mut f: impl FnMut(Range) -> Range) {
+        for r in &mut self.ranges {
+            *r = f(*r);
+        }
+    }
+}
+
+impl From<Range> for Selection {
+    fn from(primary: Range) -> Self {
+        Self::new(primary)
+    }
+}
+
+impl<'a> IntoIterator for &'a Selection {
+    type Item = Range;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, Range>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_keeps_insertion_order() {
+        let mut sel = Selection::from(Range(20, 25));
+        sel.add(Range(0, 3));
+        sel.add(Range(10, 10));
+        assert_eq!(sel.as_slice(), [Range(20, 25), Range(0, 3), Range(10, 10)]);
+        assert_eq!(sel.primary(), Range(10, 10));
+        sel.set_primary(0);
+        assert_eq!(sel.primary(), Range(20, 25));
+        assert_eq!((&sel).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn toggle_adds_and_removes() {
+        let mut sel = Selection::new(Range(0, 5));
+        assert!(sel.toggle(Range(10, 12)));
+        assert!(sel.toggle(Range(20, 22)));
+        sel.set_primary(1);
+        assert!(!sel.toggle(Range(0, 5)));
+        assert_eq!(sel.as_slice(), [Range(10, 12), Range(20, 22)]);
+        assert_eq!(sel.primary(), Range(10, 12));
+
+        // Removing the primary moves it to the previous range.
+        sel.set_primary(1);
+        assert!(!sel.toggle(Range(20, 22)));
+        assert_eq!(sel.primary(), Range(10, 12));
+
+        // The last range is kept.
+        assert!(!sel.toggle(Range(10, 12)));
+        assert_eq!(sel.as_slice(), [Range(10, 12)]);
+        assert_eq!(sel.primary_index(), 0);
+    }
+
+    #[test]
+    fn normalize_sorts_and_merges() {
+        let mut sel = Selection::new(Range(30, 35));
+        for r in [
+            Range(0, 4),
+            Range(4, 6),
+            Range(2, 3),
+            Range(33, 40),
+            Range(8, 8),
+        ] {
+            sel.add(r);
+        }
+        sel.add(Range(8, 8));
+        sel.add(Range(32, 32));
+        sel.set_primary(4);
+        sel.normalize();
+        assert_eq!(
+            sel.as_slice(),
+            [Range(0, 4), Range(4, 6), Range(8, 8), Range(30, 40)]
+        );
+        // The primary `33..40` was merged into `30..40`.
+        assert_eq!(sel.primary(), Range(30, 40));
+        let once = sel.clone();
+        sel.normalize();
+        assert_eq!(sel, once);
+    }
+
+    #[test]
+    fn merging_swallows_the_primary() {
+        let mut sel = Selection::new(Range(0, 100));
+        sel.add(Range(40, 50));
+        assert_eq!(sel.primary(), Range(40, 50));
+        sel.normalize();
+        assert_eq!(sel.as_slice(), [Range(0, 100)]);
+        assert_eq!(sel.primary_index(), 0);
+
+        // An empty cursor inside a selection is swallowed too.
+        let mut sel = Selection::new(Range(60, 70));
+        sel.add(Range(0, 10));
+        sel.add(Range(5, 5));
+        sel.normalize();
+        assert_eq!(sel.as_slice(), [Range(0, 10), Range(60, 70)]);
+        assert_eq!(sel.primary(), Range(0, 10));
+    }
+
+    #[test]
+    fn map_applies_edits_to_every_range() {
+        use crate::range::edit::Edit;
+
+        let mut sel = Selection::new(Range(10, 12));
+        sel.add(Range(2, 4));
+        let edit = Edit::insert(0, 3);
+        sel.map(|r| edit.apply_to(r).unwrap());
+        assert_eq!(sel.as_slice(), [Range(13, 15), Range(5, 7)]);
+        assert_eq!(sel.primary(), Range(5, 7));
+
+        // Edits that make ranges overlap are resolved by normalizing.
+        sel.map(|r| Range(r.start() - 1, r.end() + 7));
+        sel.normalize();
+        assert_eq!(sel.as_slice(), [Range(4, 22)]);
+    }
+}
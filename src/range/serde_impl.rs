@@ -0,0 +1,282 @@
+//! [`serde`] support for [`Range`], enabled by the `serde` feature.
+//!
+//! Human-readable formats (like JSON) serialize a range as a
+//! `{"start": .., "end": ..}` map and additionally accept a two-element sequence
+//! or a `"start..end"` / `"start..=end"` string when deserializing. Compact formats
+//! (like bincode) use a plain `(start, end)` tuple.
+//!
+//! Inverted ranges are rejected when deserializing, with the message of
+//! [`InvalidRange`](super::InvalidRange).
+//!
+//! A [`RangeSet`](super::RangeSet) serializes as a sequence of `[start, end]`
+//! pairs in every format. Deserializing accepts the ranges in any order, overlapping
+//! or touching, and normalizes them as [`RangeSet::insert`](super::RangeSet::insert)
+//! would, so hand-edited files load; inverted ranges are still rejected.
+
+use core::fmt::Formatter;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use super::{ParseRangeError, Range};
+
+const FIELDS: &[&str] = &["start", "end"];
+
+impl Serialize for Range {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut state = serializer.serialize_struct("Range", 2)?;
+            state.serialize_field("start", &self.0)?;
+            state.serialize_field("end", &self.1)?;
+            state.end()
+        } else {
+            (self.0, self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Range {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(RangeVisitor)
+        } else {
+            deserializer.deserialize_tuple(2, RangeVisitor)
+        }
+    }
+}
+
+enum Field {
+    Start,
+    End,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldVisitor;
+
+        impl Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.write_str("`start` or `end`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "start" => Ok(Field::Start),
+                    "end" => Ok(Field::End),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct RangeVisitor;
+
+impl<'de> Visitor<'de> for RangeVisitor {
+    type Value = Range;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a range as a `[start, end]` pair, a map, or a `start..end` string")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Range, A::Error> {
+        let start = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let end = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Range::try_new(start, end).map_err(de::Error::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Range, A::Error> {
+        let mut start = None;
+        let mut end = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Start => {
+                    if start.is_some() {
+                        return Err(de::Error::duplicate_field("start"));
+                    }
+                    start = Some(map.next_value()?);
+                }
+                Field::End => {
+                    if end.is_some() {
+                        return Err(de::Error::duplicate_field("end"));
+                    }
+                    end = Some(map.next_value()?);
+                }
+            }
+        }
+        let start = start.ok_or_else(|| de::Error::missing_field("start"))?;
+        let end = end.ok_or_else(|| de::Error::missing_field("end"))?;
+        Range::try_new(start, end).map_err(de::Error::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Range, E> {
+        value.parse().map_err(|err| match err {
+            ParseRangeError::InvalidRange(err) => de::Error::custom(err),
+            _ => de::Error::invalid_value(de::Unexpected::Str(value), &self),
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for super::RangeSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|r| (r.0, r.1)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for super::RangeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RangeSetVisitor;
+
+        impl<'de> Visitor<'de> for RangeSetVisitor {
+            type Value = super::RangeSet;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a sequence of `[start, end]` pairs")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut set = super::RangeSet::new();
+                while let Some(range) = seq.next_element::<Range>()? {
+                    set.insert(range);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(RangeSetVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::string::ToString;
+
+    #[test]
+    fn json_round_trip() {
+        let r = Range(2, 5);
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, r#"{"start":2,"end":5}"#);
+        assert_eq!(serde_json::from_str::<Range>(&json).unwrap(), r);
+    }
+
+    #[test]
+    fn json_accepts_sequence_and_string_forms() {
+        assert_eq!(serde_json::from_str::<Range>("[2,5]").unwrap(), Range(2, 5));
+        assert_eq!(
+            serde_json::from_str::<Range>(r#""2..5""#).unwrap(),
+            Range(2, 5)
+        );
+        assert_eq!(
+            serde_json::from_str::<Range>(r#""2..=4""#).unwrap(),
+            Range(2, 5)
+        );
+        assert_eq!(
+            serde_json::from_str::<Range>(r#"{"end":5,"start":2}"#).unwrap(),
+            Range(2, 5)
+        );
+    }
+
+    #[test]
+    fn json_rejects_malformed_input() {
+        assert!(serde_json::from_str::<Range>("[2]").is_err());
+        assert!(serde_json::from_str::<Range>(r#"{"start":2}"#).is_err());
+        assert!(serde_json::from_str::<Range>(r#"{"start":2,"end":5,"len":3}"#).is_err());
+        assert!(serde_json::from_str::<Range>(r#""2-5""#).is_err());
+        assert!(serde_json::from_str::<Range>("[-1,5]").is_err());
+    }
+
+    #[test]
+    fn rejects_inverted_ranges() {
+        let msg = "invalid range 5..2: start is greater than end";
+        for input in ["[5,2]", r#"{"start":5,"end":2}"#, r#""5..2""#] {
+            let err = serde_json::from_str::<Range>(input).unwrap_err();
+            assert!(err.to_string().starts_with(msg), "{err}");
+        }
+        let bytes = bincode::serialize(&(5usize, 2usize)).unwrap();
+        let err = bincode::deserialize::<Range>(&bytes).unwrap_err();
+        assert!(err.to_string().starts_with(msg), "{err}");
+    }
+
+    #[test]
+    fn json_rejects_values_too_large_for_usize() {
+        let too_big = "[0,100000000000000000000]";
+        assert!(serde_json::from_str::<Range>(too_big).is_err());
+        assert!(serde_json::from_str::<Range>(r#""0..100000000000000000000""#).is_err());
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let r = Range(7, usize::MAX);
+        let bytes = bincode::serialize(&r).unwrap();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bincode::deserialize::<Range>(&bytes).unwrap(), r);
+    }
+
+    #[cfg(feature = "alloc")]
+    mod range_set {
+        use super::*;
+        use crate::range::RangeSet;
+
+        fn sample() -> RangeSet {
+            let mut set = RangeSet::new();
+            set.insert(Range(0, 10));
+            set.insert(Range(20, 25));
+            set.insert(Range(usize::MAX - 1, usize::MAX));
+            set
+        }
+
+        #[test]
+        fn json_round_trip() {
+            let set = sample();
+            let json = serde_json::to_string(&set).unwrap();
+            assert_eq!(
+                json,
+                std::format!("[[0,10],[20,25],[{},{}]]", usize::MAX - 1, usize::MAX)
+            );
+            assert_eq!(serde_json::from_str::<RangeSet>(&json).unwrap(), set);
+            assert_eq!(serde_json::to_string(&RangeSet::new()).unwrap(), "[]");
+        }
+
+        #[test]
+        fn bincode_round_trip() {
+            let set = sample();
+            let bytes = bincode::serialize(&set).unwrap();
+            assert_eq!(bytes.len(), 8 + 3 * 16);
+            assert_eq!(bincode::deserialize::<RangeSet>(&bytes).unwrap(), set);
+        }
+
+        #[test]
+        fn normalizes_overlapping_and_unsorted_input() {
+            let json = r#"[[20,25],[3,8],[0,5],[8,10],[22,24],[30,30]]"#;
+            let set: RangeSet = serde_json::from_str(json).unwrap();
+            assert_eq!(set.as_slice(), [Range(0, 10), Range(20, 25)]);
+
+            let bytes = bincode::serialize(&[(5usize, 9usize), (0, 6)][..]).unwrap();
+            let set: RangeSet = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(set.as_slice(), [Range(0, 9)]);
+        }
+
+        #[test]
+        fn rejects_inverted_and_malformed_input() {
+            let err = serde_json::from_str::<RangeSet>("[[0,5],[9,2]]").unwrap_err();
+            assert!(err.to_string().starts_with("invalid range 9..2"), "{err}");
+            assert!(serde_json::from_str::<RangeSet>("[[0]]").is_err());
+            assert!(serde_json::from_str::<RangeSet>(r#"{"start":0,"end":5}"#).is_err());
+
+            let bytes = bincode::serialize(&[(5usize, 2usize)][..]).unwrap();
+            assert!(bincode::deserialize::<RangeSet>(&bytes).is_err());
+        }
+    }
+}
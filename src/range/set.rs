@@ -0,0 +1,857 @@
+//! [`RangeSet`], a set of indices stored as sorted, disjoint [`Range`]s.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+use super::{Iter, Range};
+
+/// A set of indices stored as sorted, non-overlapping, non-adjacent ranges.
+///
+/// Inserting overlapping or adjacent ranges merges them, and removing a range
+/// splits any stored range it cuts through, so the representation is always
+/// canonical: two sets cover the same indices exactly when they are equal.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, RangeSet};
+///
+/// let mut downloaded = RangeSet::new();
+/// downloaded.insert(Range(0, 100));
+/// downloaded.insert(Range(200, 300));
+/// downloaded.insert(Range(100, 150));
+/// assert_eq!(downloaded.as_slice(), [Range(0, 150), Range(200, 300)]);
+/// assert_eq!(downloaded.len(), 250);
+///
+/// downloaded.remove(Range(50, 250));
+/// assert_eq!(downloaded.as_slice(), [Range(0, 50), Range(250, 300)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RangeSet {
+    ranges: Vec<Range>,
+}
+
+impl RangeSet {
+    /// Creates an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Builds a set from indices in ascending order, merging runs of consecutive
+    /// indices in a single pass. Duplicates are allowed.
+    ///
+    /// This is the same as inserting each index one by one, but without the
+    /// sorting done by the [`FromIterator<usize>`] impl. `usize::MAX` is never
+    /// stored, as no [`Range`] contains it.
+    ///
+    /// # Panics
+    /// In debug builds, panics if `indices` is not sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeSet};
+    /// let dirty = RangeSet::from_sorted_indices([1, 2, 3, 3, 7, 9, 10]);
+    /// assert_eq!(dirty.as_slice(), [Range(1, 4), Range(7, 8), Range(9, 11)]);
+    /// ```
+    #[must_use]
+    #[track_caller]
+    pub fn from_sorted_indices<I: IntoIterator<Item = usize>>(indices: I) -> Self {
+        let mut ranges: Vec<Range> = Vec::new();
+        for i in indices.into_iter().filter(|&i| i < usize::MAX) {
+            match ranges.last_mut() {
+                Some(last) if i < last.end() => {
+                    debug_assert!(i == last.end() - 1, "indices must be sorted");
+                }
+                Some(last) if i == last.end() => last.1 += 1,
+                _ => ranges.push(Range(i, i + 1)),
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the stored ranges, sorted and with no two touching.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Range] {
+        &self.ranges
+    }
+
+    /// Returns an iterator over the stored ranges in order.
+    pub fn iter(&self) -> core::iter::Copied<core::slice::Iter<'_, Range>> {
+        self.ranges.iter().copied()
+    }
+
+    /// Returns an iterator over every index in the set, in increasing order.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeSet};
+    /// let mut set = RangeSet::new();
+    /// set.insert(Range(1, 3));
+    /// set.insert(Range(7, 9));
+    /// assert_eq!(set.indices().collect::<Vec<_>>(), [1, 2, 7, 8]);
+    /// ```
+    pub fn indices(&self) -> Indices<'_> {
+        Indices {
+            ranges: self.ranges.iter(),
+            front: Range(0, 0).iter(),
+            back: Range(0, 0).iter(),
+            len: self.len(),
+        }
+    }
+
+    /// Returns the number of stored ranges.
+    #[must_use]
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns the total number of indices covered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(Range::len).sum()
+    }
+
+    /// Returns `true` if the set covers no index.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Removes every range from the set.
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Returns `true` if `index` is in the set.
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        let i = self.ranges.partition_point(|r| r.end() <= index);
+        self.ranges.get(i).is_some_and(|r| r.start() <= index)
+    }
+
+    /// Returns the stored range covering `index`, if any.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeSet};
+    /// let mut set = RangeSet::new();
+    /// set.insert(Range(10, 20));
+    /// assert_eq!(set.range_containing(15), Some(Range(10, 20)));
+    /// assert_eq!(set.range_containing(20), None);
+    /// ```
+    #[must_use]
+    pub fn range_containing(&self, index: usize) -> Option<Range> {
+        let i = self.ranges.partition_point(|r| r.end() <= index);
+        self.ranges.get(i).copied().filter(|r| r.start() <= index)
+    }
+
+    /// Returns `true` if every index of `range` is in the set.
+    ///
+    /// Empty and inverted ranges contain no index, so they are always contained.
+    #[must_use]
+    pub fn contains_range(&self, range: Range) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end() <= range.start());
+        self.ranges.get(i).is_some_and(|r| r.contains_range(&range))
+    }
+
+    /// Adds every index of `range` to the set, merging it with the stored ranges
+    /// it overlaps or touches.
+    ///
+    /// Empty and inverted ranges are ignored.
+    pub fn insert(&mut self, range: Range) {
+        if range.is_empty() {
+            return;
+        }
+        // Stored ranges in `i..j` overlap or touch `range`.
+        let i = self.ranges.partition_point(|r| r.end() < range.start());
+        let j = self.ranges.partition_point(|r| r.start() <= range.end());
+        let merged = match self.ranges.get(i..j) {
+            Some([first, .., last]) => first.hull(last).hull(&range),
+            Some([only]) => only.hull(&range),
+            _ => range,
+        };
+        self.ranges.splice(i..j, [merged]);
+    }
+
+    /// Removes every index of `range` from the set, splitting stored ranges that
+    /// extend past either side of it.
+    ///
+    /// Empty and inverted ranges are ignored.
+    pub fn remove(&mut self, range: Range) {
+        if range.is_empty() {
+            return;
+        }
+        // Stored ranges in `i..j` overlap `range`.
+        let i = self.ranges.partition_point(|r| r.end() <= range.start());
+        let j = self.ranges.partition_point(|r| r.start() < range.end());
+        if i >= j {
+            return;
+        }
+        let head = Range(self.ranges[i].start(), range.start());
+        let tail = Range(range.end(), self.ranges[j - 1].end());
+        let kept = [head, tail].into_iter().filter(|r| !r.is_empty());
+        self.ranges.splice(i..j, kept);
+    }
+
+    /// Returns the indices of `bound` that are not in the set.
+    ///
+    /// Stored ranges outside `bound` are ignored. An empty or inverted `bound`
+    /// yields an empty set.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeSet};
+    /// let mut have = RangeSet::new();
+    /// have.insert(Range(10, 20));
+    /// have.insert(Range(50, 200));
+    /// let missing = have.complement(Range(0, 100));
+    /// assert_eq!(missing.as_slice(), [Range(0, 10), Range(20, 50)]);
+    /// ```
+    #[must_use]
+    pub fn complement(&self, bound: Range) -> Self {
+        if bound.is_empty() {
+            return Self::new();
+        }
+        let mut ranges = Vec::new();
+        let mut cursor = bound.start();
+        let first = self.ranges.partition_point(|r| r.end() <= bound.start());
+        for r in &self.ranges[first..] {
+            if r.start() >= bound.end() {
+                break;
+            }
+            if cursor < r.start() {
+                ranges.push(Range(cursor, r.start()));
+            }
+            cursor = r.end();
+        }
+        if cursor < bound.end() {
+            ranges.push(Range(cursor, bound.end()));
+        }
+        Self { ranges }
+    }
+
+    /// Replaces the set by its [`complement`](Self::complement) within `bound`.
+    pub fn invert_within(&mut self, bound: Range) {
+        *self = self.complement(bound);
+    }
+
+    /// Returns the indices in `self`, `other`, or both. Also available as
+    /// `&a | &b`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::range::{Range, RangeSet};
+    /// let mut a = RangeSet::new();
+    /// a.insert(Range(0, 5));
+    /// let mut b = RangeSet::new();
+    /// b.insert(Range(5, 8));
+    /// b.insert(Range(10, 12));
+    /// assert_eq!((&a | &b).as_slice(), [Range(0, 8), Range(10, 12)]);
+    /// ```
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges: Vec<Range> = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start() <= y.start() => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            let Some(r) = next else { break };
+            match ranges.last_mut() {
+                Some(last) if last.end() >= r.start() => *last = last.hull(&r),
+                _ => ranges.push(r),
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the indices in both `self` and `other`. Also available as
+    /// `&a & &b`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if let Some(r) = a[i].intersect(&b[j]) {
+                ranges.push(r);
+            }
+            // The range ending first cannot overlap anything further on the
+            // other side.
+            if a[i].end() <= b[j].end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the indices in `self` but not in `other`. Also available as
+    /// `&a - &b`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let b = &other.ranges;
+        let mut j = 0;
+        for &r in &self.ranges {
+            let mut rest = r;
+            // Skip the ranges of `other` that end before `rest` starts.
+            while j < b.len() && b[j].end() <= rest.start() {
+                j += 1;
+            }
+            let mut k = j;
+            while k < b.len() && b[k].start() < rest.end() {
+                if rest.start() < b[k].start() {
+                    ranges.push(Range(rest.start(), b[k].start()));
+                }
+                rest = Range(b[k].end(), rest.end());
+                if rest.is_empty() {
+                    break;
+                }
+                k += 1;
+            }
+            if !rest.is_empty() {
+                ranges.push(rest);
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Returns the indices in exactly one of `self` and `other`. Also available
+    /// as `&a ^ &b`.
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.union(other).difference(&self.intersection(other))
+    }
+}
+
+/// An iterator over every index in a [`RangeSet`], in increasing order.
+///
+/// Created by [`RangeSet::indices`].
+#[derive(Debug, Clone)]
+pub struct Indices<'a> {
+    ranges: core::slice::Iter<'a, Range>,
+    front: Iter,
+    back: Iter,
+    len: usize,
+}
+
+impl Iterator for Indices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if let Some(index) = self.front.next() {
+                self.len -= 1;
+                return Some(index);
+            }
+            match self.ranges.next() {
+                Some(r) => self.front = r.iter(),
+                None => {
+                    let index = self.back.next()?;
+                    self.len -= 1;
+                    return Some(index);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl DoubleEndedIterator for Indices<'_> {
+    fn next_back(&mut self) -> Option<usize> {
+        loop {
+            if let Some(index) = self.back.next_back() {
+                self.len -= 1;
+                return Some(index);
+            }
+            match self.ranges.next_back() {
+                Some(r) => self.back = r.iter(),
+                None => {
+                    let index = self.front.next_back()?;
+                    self.len -= 1;
+                    return Some(index);
+                }
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for Indices<'_> {}
+
+impl FusedIterator for Indices<'_> {}
+
+macro_rules! set_operator {
+    ($trait:ident, $method:ident, $op:ident) => {
+        impl core::ops::$trait<&RangeSet> for &RangeSet {
+            type Output = RangeSet;
+
+            fn $method(self, rhs: &RangeSet) -> RangeSet {
+                self.$op(rhs)
+            }
+        }
+
+        impl core::ops::$trait for RangeSet {
+            type Output = RangeSet;
+
+            fn $method(self, rhs: RangeSet) -> RangeSet {
+                self.$op(&rhs)
+            }
+        }
+    };
+}
+
+set_operator!(BitOr, bitor, union);
+set_operator!(BitAnd, bitand, intersection);
+set_operator!(Sub, sub, difference);
+set_operator!(BitXor, bitxor, symmetric_difference);
+
+impl<'a> IntoIterator for &'a RangeSet {
+    type Item = Range;
+    type IntoIter = core::iter::Copied<core::slice::Iter<'a, Range>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for RangeSet {
+    type Item = Range;
+    type IntoIter = alloc::vec::IntoIter<Range>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+/// Collects indices in any order, sorting them first; see
+/// [`RangeSet::from_sorted_indices`].
+impl FromIterator<usize> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut indices: Vec<usize> = iter.into_iter().collect();
+        indices.sort_unstable();
+        Self::from_sorted_indices(indices)
+    }
+}
+
+impl Extend<usize> for RangeSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        let other: Self = iter.into_iter().collect();
+        *self = self.union(&other);
+    }
+}
+
+impl Extend<Range> for RangeSet {
+    fn extend<I: IntoIterator<Item = Range>>(&mut self, iter: I) {
+        let other = Self {
+            ranges: super::coalesce_iter(iter),
+        };
+        *self = self.union(&other);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    extern crate std;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use std::collections::BTreeSet;
+
+    /// Checks that `set` is canonical and covers exactly `model`.
+    pub(crate) fn assert_matches_model(set: &RangeSet, model: &BTreeSet<usize>) {
+        for r in set.as_slice() {
+            assert!(!r.is_empty(), "empty range in {set:?}");
+        }
+        for w in set.as_slice().windows(2) {
+            assert!(w[0].end() < w[1].start(), "not coalesced: {set:?}");
+        }
+        let indices: BTreeSet<usize> = set.iter().flatten().collect();
+        assert_eq!(&indices, model);
+        assert_eq!(set.len(), model.len());
+        assert_eq!(set.is_empty(), model.is_empty());
+    }
+
+    pub(crate) fn random_range(rng: &mut StdRng, max: usize) -> Range {
+        let start = rng.random_range(0..max);
+        Range(start, start + rng.random_range(0..max / 4))
+    }
+
+    #[test]
+    fn insert_coalesces_overlapping_and_adjacent() {
+        let mut set = RangeSet::new();
+        set.insert(Range(10, 20));
+        set.insert(Range(30, 40));
+        assert_eq!(set.as_slice(), [Range(10, 20), Range(30, 40)]);
+        set.insert(Range(20, 25));
+        assert_eq!(set.as_slice(), [Range(10, 25), Range(30, 40)]);
+        set.insert(Range(25, 30));
+        assert_eq!(set.as_slice(), [Range(10, 40)]);
+        set.insert(Range(0, 5));
+        set.insert(Range(12, 14));
+        set.insert(Range(7, 7));
+        set.insert(Range(9, 3));
+        assert_eq!(set.as_slice(), [Range(0, 5), Range(10, 40)]);
+        set.insert(Range(2, 50));
+        assert_eq!(set.as_slice(), [Range(0, 50)]);
+        assert_eq!(set.range_count(), 1);
+    }
+
+    #[test]
+    fn remove_splits() {
+        let mut set = RangeSet::new();
+        set.insert(Range(0, 10));
+        set.remove(Range(3, 5));
+        assert_eq!(set.as_slice(), [Range(0, 3), Range(5, 10)]);
+        set.remove(Range(0, 3));
+        assert_eq!(set.as_slice(), [Range(5, 10)]);
+        set.remove(Range(8, 20));
+        assert_eq!(set.as_slice(), [Range(5, 8)]);
+        set.remove(Range(0, 5));
+        set.remove(Range(8, 9));
+        set.remove(Range(6, 6));
+        assert_eq!(set.as_slice(), [Range(5, 8)]);
+        set.remove(Range(0, 100));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = RangeSet::new();
+        set.insert(Range(2, 4));
+        set.insert(Range(6, 9));
+        for (i, expected) in [
+            false, false, true, true, false, false, true, true, true, false,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            assert_eq!(set.contains(i), expected, "{i}");
+        }
+        assert!(set.contains_range(Range(6, 9)));
+        assert!(set.contains_range(Range(7, 8)));
+        assert!(!set.contains_range(Range(3, 7)));
+        assert!(!set.contains_range(Range(8, 10)));
+        assert!(set.contains_range(Range(100, 100)));
+        assert!(RangeSet::new().contains_range(Range(1, 1)));
+        assert!(!RangeSet::new().contains(0));
+    }
+
+    #[test]
+    fn iteration() {
+        let mut set = RangeSet::new();
+        set.insert(Range(5, 6));
+        set.insert(Range(1, 2));
+        let ranges: Vec<Range> = (&set).into_iter().collect();
+        assert_eq!(ranges, [Range(1, 2), Range(5, 6)]);
+        assert_eq!(set.clone().into_iter().collect::<Vec<_>>(), ranges);
+        set.clear();
+        assert_eq!(set, RangeSet::default());
+    }
+
+    #[test]
+    fn randomized_against_btreeset() {
+        for seed in 0..40 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut set = RangeSet::new();
+            let mut model = BTreeSet::new();
+            for _ in 0..200 {
+                let r = random_range(&mut rng, 200);
+                if rng.random_bool(0.6) {
+                    set.insert(r);
+                    model.extend(r);
+                } else {
+                    set.remove(r);
+                    for i in r {
+                        model.remove(&i);
+                    }
+                }
+                assert_matches_model(&set, &model);
+                let probe = random_range(&mut rng, 220);
+                assert_eq!(set.contains(probe.start()), model.contains(&probe.start()));
+                assert_eq!(
+                    set.contains_range(probe),
+                    probe.iter().all(|i| model.contains(&i))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn complement_within_bound() {
+        let mut set = RangeSet::new();
+        set.insert(Range(5, 10));
+        set.insert(Range(20, 30));
+        assert_eq!(
+            set.complement(Range(0, 40)).as_slice(),
+            [Range(0, 5), Range(10, 20), Range(30, 40)]
+        );
+        // Stored ranges crossing or outside the bound are clipped or ignored.
+        assert_eq!(set.complement(Range(7, 25)).as_slice(), [Range(10, 20)]);
+        assert!(set.complement(Range(22, 28)).is_empty());
+        assert_eq!(set.complement(Range(40, 50)).as_slice(), [Range(40, 50)]);
+        assert!(set.complement(Range(3, 3)).is_empty());
+        assert!(set.complement(Range(9, 3)).is_empty());
+        assert_eq!(
+            RangeSet::new().complement(Range(1, 4)).as_slice(),
+            [Range(1, 4)]
+        );
+
+        let mut inverted = set.clone();
+        inverted.invert_within(Range(0, 40));
+        assert_eq!(inverted, set.complement(Range(0, 40)));
+    }
+
+    #[test]
+    fn randomized_complement() {
+        for seed in 0..40 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut set = RangeSet::new();
+            for _ in 0..rng.random_range(0..20) {
+                set.insert(random_range(&mut rng, 200));
+            }
+            let bound = random_range(&mut rng, 200);
+            let gaps = set.complement(bound);
+            let expected: BTreeSet<usize> = bound.iter().filter(|&i| !set.contains(i)).collect();
+            assert_matches_model(&gaps, &expected);
+
+            // The set and its complement together cover exactly `bound`.
+            let mut covered = gaps.clone();
+            for r in &set {
+                covered.insert(r.clamp_to_range(&bound));
+            }
+            let expected_cover = if bound.is_empty() {
+                RangeSet::new()
+            } else {
+                let mut s = RangeSet::new();
+                s.insert(bound);
+                s
+            };
+            assert_eq!(covered, expected_cover);
+
+            // Complementing twice round-trips within the bound.
+            let mut clipped = RangeSet::new();
+            for r in &set {
+                clipped.insert(r.clamp_to_range(&bound));
+            }
+            assert_eq!(gaps.complement(bound), clipped);
+        }
+    }
+
+    fn set_of(ranges: &[Range]) -> RangeSet {
+        let mut set = RangeSet::new();
+        for &r in ranges {
+            set.insert(r);
+        }
+        set
+    }
+
+    #[test]
+    fn set_algebra_examples() {
+        let a = set_of(&[Range(0, 10), Range(20, 30)]);
+        let b = set_of(&[Range(5, 25), Range(30, 35)]);
+        assert_eq!((&a | &b).as_slice(), [Range(0, 35)]);
+        assert_eq!((&a & &b).as_slice(), [Range(5, 10), Range(20, 25)]);
+        assert_eq!((&a - &b).as_slice(), [Range(0, 5), Range(25, 30)]);
+        assert_eq!((&b - &a).as_slice(), [Range(10, 20), Range(30, 35)]);
+        assert_eq!(
+            (&a ^ &b).as_slice(),
+            [Range(0, 5), Range(10, 20), Range(25, 35)]
+        );
+        assert_eq!(a.clone() | RangeSet::new(), a);
+        assert!((a.clone() & RangeSet::new()).is_empty());
+        assert_eq!(a.clone() - RangeSet::new(), a);
+        assert!((a.clone() ^ a.clone()).is_empty());
+    }
+
+    #[test]
+    fn union_with_complement_covers_bound() {
+        let a = set_of(&[Range(3, 8), Range(12, 40)]);
+        let bound = Range(0, 20);
+        let cover = a
+            .intersection(&set_of(&[bound]))
+            .union(&a.complement(bound));
+        assert_eq!(cover.as_slice(), [bound]);
+        assert_eq!(a.complement(bound).complement(bound), a & set_of(&[bound]));
+    }
+
+    #[test]
+    fn randomized_set_algebra_against_btreeset() {
+        for seed in 0..200 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let make = |rng: &mut StdRng| {
+                let mut set = RangeSet::new();
+                for _ in 0..rng.random_range(0..12) {
+                    set.insert(random_range(rng, 150));
+                }
+                let model: BTreeSet<usize> = set.iter().flatten().collect();
+                (set, model)
+            };
+            let (a, ma) = make(&mut rng);
+            let (b, mb) = make(&mut rng);
+            assert_matches_model(&a.union(&b), &ma.union(&mb).copied().collect());
+            assert_matches_model(
+                &a.intersection(&b),
+                &ma.intersection(&mb).copied().collect(),
+            );
+            assert_matches_model(&a.difference(&b), &ma.difference(&mb).copied().collect());
+            assert_matches_model(
+                &a.symmetric_difference(&b),
+                &ma.symmetric_difference(&mb).copied().collect(),
+            );
+        }
+    }
+
+    #[test]
+    fn membership_at_fragment_boundaries() {
+        let set = set_of(&[Range(0, 1), Range(5, 10), Range(11, 12), Range(100, 200)]);
+        let cases = [
+            (0, Some(Range(0, 1))),
+            (1, None),
+            (4, None),
+            (5, Some(Range(5, 10))),
+            (9, Some(Range(5, 10))),
+            (10, None),
+            (11, Some(Range(11, 12))),
+            (12, None),
+            (99, None),
+            (100, Some(Range(100, 200))),
+            (199, Some(Range(100, 200))),
+            (200, None),
+            (usize::MAX, None),
+        ];
+        for (index, expected) in cases {
+            assert_eq!(set.range_containing(index), expected, "index {index}");
+            assert_eq!(set.contains(index), expected.is_some(), "index {index}");
+        }
+        assert!(set.contains_range(Range(5, 10)));
+        assert!(set.contains_range(Range(100, 101)));
+        assert!(!set.contains_range(Range(5, 11)));
+        assert!(!set.contains_range(Range(9, 12)));
+        assert!(!set.contains_range(Range(4, 6)));
+        assert!(set.contains_range(Range(50, 50)));
+        assert_eq!(RangeSet::new().range_containing(0), None);
+    }
+
+    #[test]
+    fn indices_in_both_directions() {
+        let set = set_of(&[Range(1, 3), Range(5, 6), Range(8, 11)]);
+        let forward: Vec<usize> = set.indices().collect();
+        assert_eq!(forward, [1, 2, 5, 8, 9, 10]);
+        let backward: Vec<usize> = set.indices().rev().collect();
+        assert_eq!(backward, [10, 9, 8, 5, 2, 1]);
+
+        let mut it = set.indices();
+        assert_eq!(it.len(), 6);
+        assert_eq!(it.next(), Some(1));
+        assert_eq!(it.next_back(), Some(10));
+        assert_eq!(it.next_back(), Some(9));
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.next(), Some(5));
+        assert_eq!(it.next_back(), Some(8));
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+        assert_eq!(RangeSet::new().indices().next(), None);
+    }
+
+    #[test]
+    fn randomized_indices_and_lookups() {
+        for seed in 0..100 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut set = RangeSet::new();
+            for _ in 0..rng.random_range(0..15) {
+                set.insert(random_range(&mut rng, 120));
+            }
+            let model: BTreeSet<usize> = set.iter().flatten().collect();
+            let mut it = set.indices();
+            let mut expected = model.iter().copied();
+            loop {
+                assert_eq!(it.size_hint(), expected.size_hint());
+                let (a, b) = if rng.random_bool(0.5) {
+                    (it.next(), expected.next())
+                } else {
+                    (it.next_back(), expected.next_back())
+                };
+                assert_eq!(a, b);
+                if a.is_none() {
+                    break;
+                }
+            }
+            for index in 0..130 {
+                let found = set.range_containing(index);
+                assert_eq!(found.is_some(), model.contains(&index));
+                assert!(found.is_none_or(|r| r.contains(index) && set.as_slice().contains(&r)));
+            }
+        }
+    }
+
+    #[test]
+    fn from_indices() {
+        let set: RangeSet = [9, 3, 1, 2, 3, 10, 7].into_iter().collect();
+        assert_eq!(set.as_slice(), [Range(1, 4), Range(7, 8), Range(9, 11)]);
+        assert!(RangeSet::from_sorted_indices([]).is_empty());
+        assert_eq!(
+            RangeSet::from_sorted_indices([0, 0, 0]).as_slice(),
+            [Range(0, 1)]
+        );
+        let max = usize::MAX;
+        let set = RangeSet::from_sorted_indices([max - 2, max - 1, max]);
+        assert_eq!(set.as_slice(), [Range(max - 2, max)]);
+        assert!(RangeSet::from_sorted_indices([max]).is_empty());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic = "indices must be sorted"]
+    fn from_sorted_indices_rejects_unsorted() {
+        let _ = RangeSet::from_sorted_indices([1, 5, 3]);
+    }
+
+    #[test]
+    fn extend_merges() {
+        let mut set = set_of(&[Range(0, 5), Range(10, 15)]);
+        set.extend([5, 6, 9, 20]);
+        assert_eq!(set.as_slice(), [Range(0, 7), Range(9, 15), Range(20, 21)]);
+        set.extend([Range(30, 40), Range(6, 9), Range(3, 2), Range(21, 22)]);
+        assert_eq!(set.as_slice(), [Range(0, 15), Range(20, 22), Range(30, 40)]);
+    }
+
+    #[test]
+    fn million_indices_against_btreeset() {
+        let mut rng = StdRng::seed_from_u64(89);
+        let indices: Vec<usize> = (0..1_000_000)
+            .map(|_| rng.random_range(0..3_000_000))
+            .collect();
+        let model: BTreeSet<usize> = indices.iter().copied().collect();
+        let set: RangeSet = indices.iter().copied().collect();
+        assert_eq!(set.len(), model.len());
+        assert_matches_model(&set, &model);
+        assert_eq!(RangeSet::from_sorted_indices(model.iter().copied()), set);
+
+        let mut one_by_one = RangeSet::new();
+        for &i in &indices[..2000] {
+            one_by_one.insert(Range(i, i + 1));
+        }
+        let from_iter: RangeSet = indices[..2000].iter().copied().collect();
+        assert_eq!(from_iter, one_by_one);
+
+        let mut extended = RangeSet::new();
+        for chunk in indices.chunks(100_000) {
+            extended.extend(chunk.iter().copied());
+        }
+        assert_eq!(extended, set);
+    }
+}
@@ -0,0 +1,261 @@
+//! [`Span`], an offset-and-length sibling of [`Range`].
+
+use super::Range;
+
+/// A region described by its `start` and `len` rather than its end, as many
+/// file formats do.
+///
+/// Converting into a [`Range`] computes `start + len`, which can overflow; the
+/// `From` conversion panics in that case and
+/// [`checked_to_range`](Self::checked_to_range) returns `None`. Converting from a
+/// [`Range`] never fails: inverted ranges become empty spans.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, Span};
+///
+/// let header = Span::new(4, 3);
+/// let data = *b"RIFFabcdef";
+/// assert_eq!(&data[header], b"abc");
+/// assert_eq!(Range::from(header), Range(4, 7));
+/// assert_eq!(Span::from(Range(4, 7)), header);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    /// Index of the first element.
+    pub start: usize,
+    /// Number of elements.
+    pub len: usize,
+}
+
+impl Span {
+    /// Construct a new `Span` from `start` and `len`.
+    #[must_use]
+    #[inline]
+    pub const fn new(start: usize, len: usize) -> Self {
+        Self { start, len }
+    }
+
+    /// Returns the end (exclusive) of the span.
+    ///
+    /// # Panics
+    /// Panics if `start + len` overflows.
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn end(&self) -> usize {
+        self.checked_end().expect("span end overflows usize")
+    }
+
+    /// Returns the end (exclusive) of the span, or `None` if `start + len`
+    /// overflows.
+    #[must_use]
+    #[inline]
+    pub const fn checked_end(&self) -> Option<usize> {
+        self.start.checked_add(self.len)
+    }
+
+    /// Returns the length of the span.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the span contains no elements.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the provided index is inside the span.
+    ///
+    /// This never overflows, even when the end would.
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, index: usize) -> bool {
+        index >= self.start && index - self.start < self.len
+    }
+
+    /// Converts the span into a [`Range`].
+    ///
+    /// # Panics
+    /// Panics if `start + len` overflows.
+    #[must_use]
+    #[inline]
+    #[track_caller]
+    pub const fn to_range(&self) -> Range {
+        Range(self.start, self.end())
+    }
+
+    /// Converts the span into a [`Range`], or returns `None` if `start + len`
+    /// overflows.
+    #[must_use]
+    #[inline]
+    pub const fn checked_to_range(&self) -> Option<Range> {
+        match self.checked_end() {
+            Some(end) => Some(Range(self.start, end)),
+            None => None,
+        }
+    }
+}
+
+impl From<Span> for Range {
+    #[track_caller]
+    fn from(span: Span) -> Self {
+        span.to_range()
+    }
+}
+
+/// Inverted ranges become empty spans at their start.
+impl From<Range> for Span {
+    fn from(r: Range) -> Self {
+        Self::new(r.start(), r.len())
+    }
+}
+
+impl<T> core::ops::Index<Span> for [T] {
+    type Output = [T];
+
+    fn index(&self, index: Span) -> &Self::Output {
+        &self[index.to_range()]
+    }
+}
+
+impl<T> core::ops::Index<&Span> for [T] {
+    type Output = [T];
+
+    fn index(&self, index: &Span) -> &Self::Output {
+        &self[index.to_range()]
+    }
+}
+
+impl<T> core::ops::IndexMut<Span> for [T] {
+    fn index_mut(&mut self, index: Span) -> &mut Self::Output {
+        &mut self[index.to_range()]
+    }
+}
+
+impl<T> core::ops::IndexMut<&Span> for [T] {
+    fn index_mut(&mut self, index: &Span) -> &mut Self::Output {
+        &mut self[index.to_range()]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::Index<Span> for alloc::vec::Vec<T> {
+    type Output = [T];
+
+    fn index(&self, index: Span) -> &Self::Output {
+        &self[index.to_range()]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::Index<&Span> for alloc::vec::Vec<T> {
+    type Output = [T];
+
+    fn index(&self, index: &Span) -> &Self::Output {
+        &self[index.to_range()]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::IndexMut<Span> for alloc::vec::Vec<T> {
+    fn index_mut(&mut self, index: Span) -> &mut Self::Output {
+        &mut self[index.to_range()]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::ops::IndexMut<&Span> for alloc::vec::Vec<T> {
+    fn index_mut(&mut self, index: &Span) -> &mut Self::Output {
+        &mut self[index.to_range()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn helpers_match_range() {
+        let span = Span::new(2, 3);
+        let r = Range::from(span);
+        assert_eq!(span.end(), r.end());
+        assert_eq!(span.len(), r.len());
+        assert_eq!(span.is_empty(), r.is_empty());
+        for i in 0..8 {
+            assert_eq!(span.contains(i), r.contains(i));
+        }
+        assert!(Span::new(4, 0).is_empty());
+        assert!(!Span::new(4, 0).contains(4));
+    }
+
+    #[test]
+    fn round_trips() {
+        let max = usize::MAX;
+        for r in [
+            Range(0, 0),
+            Range(3, 9),
+            Range(0, max),
+            Range(max, max),
+            Range(max - 1, max),
+        ] {
+            assert_eq!(Range::from(Span::from(r)), r);
+        }
+        for span in [
+            Span::new(0, max),
+            Span::new(max, 0),
+            Span::new(1, max - 1),
+            Span::new(7, 2),
+        ] {
+            assert_eq!(Span::from(Range::from(span)), span);
+        }
+    }
+
+    #[test]
+    fn overflow_near_usize_max() {
+        let max = usize::MAX;
+        assert_eq!(Span::new(max, 1).checked_end(), None);
+        assert_eq!(Span::new(1, max).checked_to_range(), None);
+        assert_eq!(
+            Span::new(max - 1, 1).checked_to_range(),
+            Some(Range(max - 1, max))
+        );
+        // `contains` works even when the end overflows.
+        assert!(Span::new(max - 1, 5).contains(max));
+        assert!(!Span::new(max - 1, 5).contains(max - 2));
+    }
+
+    #[test]
+    #[should_panic = "span end overflows usize"]
+    fn from_overflowing_span_panics() {
+        let _ = Range::from(Span::new(usize::MAX, 2));
+    }
+
+    #[test]
+    fn inverted_ranges_become_empty_spans() {
+        assert_eq!(Span::from(Range(9, 4)), Span::new(9, 0));
+    }
+
+    #[test]
+    fn indexing() {
+        let mut buf = [10, 20, 30, 40, 50];
+        let span = Span::new(1, 3);
+        assert_eq!(&buf[span], &[20, 30, 40]);
+        assert_eq!(&buf[&span], &[20, 30, 40]);
+        buf[span].fill(0);
+        buf[&Span::new(0, 1)][0] = 1;
+        assert_eq!(buf, [1, 0, 0, 0, 50]);
+    }
+
+    #[test]
+    fn const_helpers() {
+        const R: Option<Range> = Span::new(4, 4).checked_to_range();
+        const END: usize = Span::new(4, 4).end();
+        assert_eq!(R, Some(Range(4, 8)));
+        assert_eq!(END, 8);
+    }
+}
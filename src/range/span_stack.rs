@@ -0,0 +1,149 @@
+//! [`SpanStack`], for building nested [`Range`]s while parsing.
+
+use alloc::vec::Vec;
+
+use super::{Range, UnclosedSpans};
+
+/// A stack of open spans: each [`close`](Self::close) ends the most recently
+/// [`open`](Self::open)ed one, so spans nest like the productions of a
+/// recursive-descent parser.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, SpanStack};
+///
+/// let src = "f(a, g(b))";
+/// let mut spans = SpanStack::new();
+/// spans.open(0);
+/// spans.open(5);
+/// assert_eq!(spans.close(9), Range(5, 9));
+/// assert_eq!(&src[spans.close(10)], "f(a, g(b))");
+/// assert!(spans.finish().is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SpanStack {
+    starts: Vec<usize>,
+}
+
+impl SpanStack {
+    /// Creates a stack with no open spans.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { starts: Vec::new() }
+    }
+
+    /// Opens a span starting at `start`.
+    pub fn open(&mut self, start: usize) {
+        self.starts.push(start);
+    }
+
+    /// Closes the innermost open span at `end`, returning it.
+    ///
+    /// `end` is not checked against the start, so closing before the start
+    /// yields an inverted range.
+    ///
+    /// # Panics
+    /// Panics if no span is open.
+    #[must_use]
+    #[track_caller]
+    pub fn close(&mut self, end: usize) -> Range {
+        self.checked_close(end).expect("no open span to close")
+    }
+
+    /// Closes the innermost open span at `end`, or returns `None` if no span is
+    /// open.
+    #[must_use]
+    pub fn checked_close(&mut self, end: usize) -> Option<Range> {
+        self.starts.pop().map(|start| Range(start, end))
+    }
+
+    /// Closes every open span at `end`, innermost first, for error recovery.
+    pub fn close_all(&mut self, end: usize) -> impl ExactSizeIterator<Item = Range> + '_ {
+        self.starts
+            .drain(..)
+            .rev()
+            .map(move |start| Range(start, end))
+    }
+
+    /// Returns the number of open spans.
+    #[must_use]
+    pub fn current_depth(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Consumes the stack, checking that every span was closed.
+    ///
+    /// # Errors
+    /// Returns [`UnclosedSpans`] listing the start of every span still open.
+    pub fn finish(self) -> Result<(), UnclosedSpans> {
+        if self.starts.is_empty() {
+            Ok(())
+        } else {
+            Err(UnclosedSpans {
+                starts: self.starts,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn nested_and_sibling_spans() {
+        let mut spans = SpanStack::new();
+        spans.open(0);
+        spans.open(2);
+        spans.open(3);
+        assert_eq!(spans.current_depth(), 3);
+        assert_eq!(spans.close(5), Range(3, 5));
+        // A sibling of the innermost span.
+        spans.open(6);
+        assert_eq!(spans.close(8), Range(6, 8));
+        assert_eq!(spans.close(9), Range(2, 9));
+        // A sibling of the middle span.
+        spans.open(10);
+        assert_eq!(spans.current_depth(), 2);
+        assert_eq!(spans.close(12), Range(10, 12));
+        assert_eq!(spans.close(13), Range(0, 13));
+        assert_eq!(spans.current_depth(), 0);
+        assert_eq!(spans.checked_close(14), None);
+        assert!(spans.finish().is_ok());
+    }
+
+    #[test]
+    #[should_panic = "no open span to close"]
+    fn close_without_open_panics() {
+        let _ = SpanStack::new().close(3);
+    }
+
+    #[test]
+    fn finish_lists_every_unclosed_start() {
+        let mut spans = SpanStack::new();
+        spans.open(1);
+        spans.open(4);
+        spans.open(7);
+        let _ = spans.close(8);
+        spans.open(9);
+        let err = spans.finish().unwrap_err();
+        assert_eq!(err.starts(), [1, 4, 9]);
+        assert_eq!(err.to_string(), "3 unclosed span(s) starting at 1, 4, 9");
+    }
+
+    #[test]
+    fn close_all_for_recovery() {
+        let mut spans = SpanStack::new();
+        spans.open(0);
+        spans.open(3);
+        spans.open(5);
+        let closed: Vec<Range> = spans.close_all(20).collect();
+        assert_eq!(closed, [Range(5, 20), Range(3, 20), Range(0, 20)]);
+        assert_eq!(spans.current_depth(), 0);
+        assert_eq!(spans.close_all(30).len(), 0);
+        assert!(spans.finish().is_ok());
+    }
+}
@@ -0,0 +1,117 @@
+//! [`proptest`] strategies for [`Range`], enabled by the `proptest` feature.
+//!
+//! All strategies shrink toward the empty range at the lowest allowed position,
+//! so failing cases minimize to small, readable inputs.
+//!
+//! # Example
+//! ```
+//! use ars::range::strategy::range_within;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(r in range_within(0..16))| {
+//!     prop_assert!(r.end() <= 16);
+//!     prop_assert!(r.start() <= r.end());
+//! });
+//! ```
+
+use proptest::arbitrary::{Arbitrary, any};
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use super::Range;
+
+/// Generates valid (non-inverted) ranges anywhere in `usize`.
+impl Arbitrary for Range {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (any::<usize>(), any::<usize>())
+            .prop_map(|(a, b)| if a <= b { Range(a, b) } else { Range(b, a) })
+            .boxed()
+    }
+}
+
+/// Generates valid (non-inverted) ranges lying within `bound`.
+///
+/// Both ends are drawn from `bound.start()..=bound.end()`, so empty ranges at
+/// either edge are included. Shrinks toward the empty range at `bound.start()`.
+///
+/// # Panics
+/// The returned strategy panics when sampled if `bound` is inverted.
+pub fn range_within(bound: impl Into<Range>) -> impl Strategy<Value = Range> {
+    let bound = bound.into();
+    let ends = bound.start()..=bound.end();
+    (ends.clone(), ends).prop_map(|(a, b)| if a <= b { Range(a, b) } else { Range(b, a) })
+}
+
+/// Generates arbitrary ranges including deliberately inverted and degenerate ones.
+///
+/// This mixes valid ranges, inverted ranges, empty ranges and ranges touching
+/// `usize::MAX`, for checking that code handles every `Range` value without
+/// panicking or misbehaving. Shrinks toward `Range(0, 0)`.
+pub fn any_range_with_inverted() -> impl Strategy<Value = Range> {
+    proptest::prop_oneof![
+        any::<Range>(),
+        (any::<usize>(), any::<usize>()).prop_map(|(a, b)| Range(a, b)),
+        any::<usize>().prop_map(|a| Range(a, a)),
+        any::<usize>().prop_map(|a| Range(a, usize::MAX)),
+        any::<usize>().prop_map(|a| Range(usize::MAX, a)),
+        Just(Range(usize::MAX, 0)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    proptest::proptest! {
+        #[test]
+        fn arbitrary_is_never_inverted(r in any::<Range>()) {
+            proptest::prop_assert!(r.start() <= r.end());
+        }
+
+        #[test]
+        fn range_within_respects_bound(
+            (len, r) in (0usize..64).prop_flat_map(|len| (Just(len), range_within(0..len)))
+        ) {
+            proptest::prop_assert!(r.start() <= r.end());
+            proptest::prop_assert!(r.end() <= len);
+        }
+
+        #[test]
+        fn len_never_panics(r in any_range_with_inverted()) {
+            proptest::prop_assert_eq!(r.iter().len(), r.len());
+            proptest::prop_assert_eq!(r.is_empty(), r.start() >= r.end());
+        }
+    }
+
+    #[test]
+    fn range_within_shrinks_to_empty_at_start() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = range_within(Range(5, 100)).new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(tree.current(), Range(5, 5));
+    }
+
+    #[test]
+    fn arbitrary_shrinks_to_empty_at_zero() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = any::<Range>().new_tree(&mut runner).unwrap();
+        while tree.simplify() {}
+        assert_eq!(tree.current(), Range(0, 0));
+    }
+
+    #[test]
+    fn inverted_strategy_produces_inverted_ranges() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = any_range_with_inverted();
+        let inverted = (0..1000)
+            .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+            .filter(|r| r.start() > r.end())
+            .count();
+        assert!(inverted > 0);
+    }
+}
@@ -0,0 +1,193 @@
+//! [`StridedRange`], a [`Range`] visiting every `step`-th index.
+
+use super::{Range, StepIter};
+
+/// The indices `start, start + step, start + 2 * step, ...` below `end`.
+///
+/// Describes access patterns like "every 4th byte from offset 2 until 1024"
+/// that a contiguous [`Range`] cannot express. The step is never zero; the
+/// bounds are not validated, and an inverted range is empty.
+///
+/// # Example
+/// ```
+/// use ars::range::{Range, StridedRange};
+/// let sr = StridedRange::new(2, 1024, 4).unwrap();
+/// assert_eq!(sr.len(), 256);
+/// assert!(sr.contains(6));
+/// assert!(!sr.contains(7));
+/// assert_eq!(sr.nth_index(3), Some(14));
+/// assert_eq!(StridedRange::from_range(Range(0, 10), 0), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StridedRange {
+    start: usize,
+    end: usize,
+    step: usize,
+}
+
+impl StridedRange {
+    /// Creates a strided range over `start..end` with the given `step`, or `None`
+    /// if `step` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn new(start: usize, end: usize, step: usize) -> Option<Self> {
+        if step == 0 {
+            None
+        } else {
+            Some(Self { start, end, step })
+        }
+    }
+
+    /// Creates a strided range over `range` with the given `step`, or `None` if
+    /// `step` is 0.
+    #[must_use]
+    #[inline]
+    pub const fn from_range(range: Range, step: usize) -> Option<Self> {
+        Self::new(range.0, range.1, step)
+    }
+
+    /// Returns the start (inclusive), which is always the first index.
+    #[must_use]
+    #[inline]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end (exclusive).
+    #[must_use]
+    #[inline]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the step between consecutive indices.
+    #[must_use]
+    #[inline]
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the contiguous range spanned, ignoring the step.
+    #[must_use]
+    #[inline]
+    pub const fn range(&self) -> Range {
+        Range(self.start, self.end)
+    }
+
+    /// Returns the number of indices.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.range().len().div_ceil(self.step)
+    }
+
+    /// Returns `true` if there are no indices.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.range().is_empty()
+    }
+
+    /// Returns `true` if `index` is one of the indices, i.e. it lies in the range
+    /// and is a whole number of steps from the start.
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, index: usize) -> bool {
+        self.range().contains(index) && (index - self.start).is_multiple_of(self.step)
+    }
+
+    /// Returns the `i`-th index, or `None` if there are at most `i` indices.
+    #[must_use]
+    #[inline]
+    pub const fn nth_index(&self, i: usize) -> Option<usize> {
+        if i < self.len() {
+            Some(self.start + i * self.step)
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the indices.
+    #[must_use]
+    #[inline]
+    pub const fn iter(&self) -> StepIter {
+        StepIter::new(self.range(), self.step)
+    }
+}
+
+impl IntoIterator for StridedRange {
+    type Item = usize;
+    type IntoIter = StepIter;
+
+    #[inline]
+    fn into_iter(self) -> StepIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &StridedRange {
+    type Item = usize;
+    type IntoIter = StepIter;
+
+    #[inline]
+    fn into_iter(self) -> StepIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    #[test]
+    fn zero_step_is_rejected() {
+        assert_eq!(StridedRange::new(0, 10, 0), None);
+        assert_eq!(StridedRange::from_range(Range(0, 10), 0), None);
+        assert!(StridedRange::new(0, 10, 1).is_some());
+    }
+
+    #[test]
+    fn len_matches_iter() {
+        for start in 0..6 {
+            for end in 0..12 {
+                for step in 1..5 {
+                    let sr = StridedRange::new(start, end, step).unwrap();
+                    let indices: Vec<usize> = sr.iter().collect();
+                    assert_eq!(sr.len(), indices.len());
+                    assert_eq!(sr.is_empty(), indices.is_empty());
+                    let expected: Vec<usize> = (start..end).step_by(step).collect();
+                    assert_eq!(indices, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn contains_respects_stride() {
+        let sr = StridedRange::new(2, 20, 4).unwrap();
+        let members: Vec<usize> = (0..25).filter(|&i| sr.contains(i)).collect();
+        assert_eq!(members, [2, 6, 10, 14, 18]);
+        assert!(!StridedRange::new(5, 3, 1).unwrap().contains(4));
+    }
+
+    #[test]
+    fn nth_index_agrees_with_iter() {
+        let sr = StridedRange::new(3, 17, 5).unwrap();
+        let indices: Vec<usize> = sr.into_iter().collect();
+        for (i, &index) in indices.iter().enumerate() {
+            assert_eq!(sr.nth_index(i), Some(index));
+        }
+        assert_eq!(sr.nth_index(indices.len()), None);
+        assert_eq!(sr.nth_index(usize::MAX), None);
+    }
+
+    #[test]
+    fn accessors() {
+        let sr = StridedRange::from_range(Range(1, 9), 2).unwrap();
+        assert_eq!((sr.start(), sr.end(), sr.step()), (1, 9, 2));
+        assert_eq!(sr.range(), Range(1, 9));
+        assert_eq!((&sr).into_iter().next_back(), Some(7));
+    }
+}
@@ -0,0 +1,1217 @@
+//! Slice utilities built on [`Range`].
+
+use core::fmt::{Display, Formatter};
+use core::iter::FusedIterator;
+
+use crate::range::{Range, StridedRange};
+
+/// The error returned by [`SliceExt::get_disjoint_ranges_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisjointError {
+    /// The range at `index` is inverted.
+    Inverted {
+        /// Position of the range in the input array.
+        index: usize,
+        /// The offending range.
+        range: Range,
+    },
+    /// The range at `index` extends past the end of the slice.
+    OutOfBounds {
+        /// Position of the range in the input array.
+        index: usize,
+        /// The offending range.
+        range: Range,
+        /// Length of the slice.
+        len: usize,
+    },
+    /// The ranges at `first` and `second` overlap, with `first < second`.
+    Overlapping {
+        /// Position of the first range in the input array.
+        first: usize,
+        /// Position of the second range in the input array.
+        second: usize,
+    },
+}
+
+impl Display for DisjointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Inverted { index, range } => write!(f, "range {index} ({range}) is inverted"),
+            Self::OutOfBounds { index, range, len } => {
+                write!(
+                    f,
+                    "range {index} ({range}) out of bounds for slice of length {len}"
+                )
+            }
+            Self::Overlapping { first, second } => {
+                write!(f, "ranges {first} and {second} overlap")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DisjointError {}
+
+/// Extension methods for slices that take a [`Range`].
+///
+/// # Example
+/// ```
+/// use ars::prelude::*;
+/// use ars::range::Range;
+///
+/// let a = [1, 2, 3, 4];
+/// assert_eq!(a.get_range(Range::new(1, 3)), Some(&[2, 3][..]));
+/// assert_eq!(a.get_range(Range::new(3, 5)), None);
+/// ```
+pub trait SliceExt<T> {
+    /// Returns the subslice covered by `r`, or `None` if `r` is inverted or extends
+    /// past the end of the slice.
+    ///
+    /// This is the non-panicking counterpart of indexing with `&self[r]`.
+    fn get_range(&self, r: Range) -> Option<&[T]>;
+
+    /// Returns the mutable subslice covered by `r`, or `None` if `r` is inverted or
+    /// extends past the end of the slice.
+    fn get_range_mut(&mut self, r: Range) -> Option<&mut [T]>;
+
+    /// Splits the slice into the parts before `r`, inside `r`, and after `r`.
+    ///
+    /// # Panics
+    /// Panics if `r` is inverted or extends past the end of the slice. See
+    /// [`try_split_at_range`](Self::try_split_at_range) for a non-panicking variant.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// let a = [1, 2, 3, 4, 5];
+    /// let (before, mid, after) = a.split_at_range(ars::range::Range(1, 3));
+    /// assert_eq!((before, mid, after), (&[1][..], &[2, 3][..], &[4, 5][..]));
+    /// ```
+    fn split_at_range(&self, r: Range) -> (&[T], &[T], &[T]);
+
+    /// Splits the slice into three disjoint mutable parts: before `r`, inside `r`,
+    /// and after `r`.
+    ///
+    /// # Panics
+    /// Panics if `r` is inverted or extends past the end of the slice. See
+    /// [`try_split_at_range_mut`](Self::try_split_at_range_mut) for a non-panicking
+    /// variant.
+    fn split_at_range_mut(&mut self, r: Range) -> (&mut [T], &mut [T], &mut [T]);
+
+    /// Like [`split_at_range`](Self::split_at_range), but returns `None` instead of
+    /// panicking if `r` is inverted or out of bounds.
+    fn try_split_at_range(&self, r: Range) -> Option<(&[T], &[T], &[T])>;
+
+    /// Like [`split_at_range_mut`](Self::split_at_range_mut), but returns `None`
+    /// instead of panicking if `r` is inverted or out of bounds.
+    fn try_split_at_range_mut(&mut self, r: Range) -> Option<(&mut [T], &mut [T], &mut [T])>;
+
+    /// Returns mutable subslices for several ranges at once, mirroring
+    /// [`slice::get_disjoint_mut`].
+    ///
+    /// Every range must be in bounds and the non-empty ranges must be pairwise
+    /// disjoint; empty ranges never overlap anything. The subslices are returned
+    /// in the order of `ranges`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    ///
+    /// let mut buf = [0; 6];
+    /// let [a, b] = buf.get_disjoint_ranges_mut([Range(4, 6), Range(0, 2)]).unwrap();
+    /// a.fill(1);
+    /// b.fill(2);
+    /// assert_eq!(buf, [2, 2, 0, 0, 1, 1]);
+    /// ```
+    fn get_disjoint_ranges_mut<const N: usize>(
+        &mut self,
+        ranges: [Range; N],
+    ) -> Result<[&mut [T]; N], DisjointError>;
+
+    /// Returns the range of the leading elements matching `pred`.
+    ///
+    /// The result always starts at 0; it is empty if the first element does not
+    /// match or the slice is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let line = b"  indented";
+    /// assert_eq!(line.leading_range_where(|b| *b == b' '), Range(0, 2));
+    /// ```
+    fn leading_range_where(&self, pred: impl FnMut(&T) -> bool) -> Range;
+
+    /// Returns the range of the trailing elements matching `pred`.
+    ///
+    /// The result always ends at the slice length; it is empty if the last element
+    /// does not match or the slice is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!([1, 2, 0, 0].trailing_range_where(|x| *x == 0), Range(2, 4));
+    /// ```
+    fn trailing_range_where(&self, pred: impl FnMut(&T) -> bool) -> Range;
+
+    /// Returns the range left after removing the leading and trailing runs of
+    /// elements matching `pred`.
+    ///
+    /// If every element matches, the result is the empty range at the slice
+    /// length.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let a = [0, 0, 3, 0, 4, 0];
+    /// let r = a.trimmed_range_where(|x| *x == 0);
+    /// assert_eq!(r, Range(2, 5));
+    /// assert_eq!(&a[r], [3, 0, 4]);
+    /// ```
+    fn trimmed_range_where(&self, pred: impl FnMut(&T) -> bool) -> Range;
+
+    /// Returns the range of elements equal to `key` in a sorted slice, like C++'s
+    /// `std::equal_range`.
+    ///
+    /// If `key` is absent, the result is the empty range at the position where it
+    /// could be inserted while keeping the slice sorted. Runs in O(log n) using two
+    /// binary searches. The result is unspecified if the slice is not sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let a = [1, 2, 2, 2, 5];
+    /// assert_eq!(a.equal_range(&2), Range(1, 4));
+    /// assert_eq!(a.equal_range(&3), Range(4, 4));
+    /// ```
+    fn equal_range(&self, key: &T) -> Range
+    where
+        T: Ord;
+
+    /// Like [`equal_range`](Self::equal_range), but compares the keys extracted by
+    /// `f`, mirroring [`slice::binary_search_by_key`]. The slice must be sorted by
+    /// that key.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let words = ["a", "to", "be", "the", "four"];
+    /// assert_eq!(words.equal_range_by_key(&2, |w| w.len()), Range(1, 3));
+    /// ```
+    fn equal_range_by_key<B: Ord>(&self, key: &B, f: impl FnMut(&T) -> B) -> Range;
+
+    /// Returns an iterator over the elements at the indices of `sr`.
+    ///
+    /// # Panics
+    /// Panics if `sr`'s range is inverted or extends past the end of the slice,
+    /// like indexing with [`StridedRange::range`] would.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::StridedRange;
+    /// let rgba = [1, 2, 3, 255, 4, 5, 6, 255];
+    /// let green = StridedRange::new(1, rgba.len(), 4).unwrap();
+    /// assert!(rgba.iter_strided(green).eq(&[2, 5]));
+    /// ```
+    fn iter_strided(&self, sr: StridedRange) -> core::iter::StepBy<core::slice::Iter<'_, T>>;
+
+    /// Returns an iterator over the elements in `r` after clamping it to the slice
+    /// length, so ranges extending past the end never panic.
+    ///
+    /// Ranges entirely past the end, and inverted ranges, yield nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let a = [1, 2, 3];
+    /// assert!(a.iter_range(Range(1, 10)).eq(&[2, 3]));
+    /// assert_eq!(a.iter_range(Range(5, 10)).len(), 0);
+    /// ```
+    fn iter_range(&self, r: Range) -> core::slice::Iter<'_, T>;
+
+    /// Returns a mutable iterator over the elements in `r` after clamping it to
+    /// the slice length; see [`iter_range`](Self::iter_range).
+    fn iter_range_mut(&mut self, r: Range) -> core::slice::IterMut<'_, T>;
+
+    /// Returns an iterator over the maximal runs of equal adjacent elements, each
+    /// as its range and a reference to its first element.
+    ///
+    /// The ranges tile the slice in order and are never empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let runs: Vec<(Range, &char)> = ['a', 'a', 'b', 'a'].group_ranges().collect();
+    /// assert_eq!(runs, [(Range(0, 2), &'a'), (Range(2, 3), &'b'), (Range(3, 4), &'a')]);
+    /// ```
+    fn group_ranges(&self) -> GroupRanges<'_, T, fn(&T, &T) -> bool>
+    where
+        T: PartialEq;
+
+    /// Like [`group_ranges`](Self::group_ranges), but a run continues while
+    /// `same_group` returns `true` for each pair of adjacent elements, like
+    /// [`slice::chunk_by`].
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let words = ["apple", "avocado", "banana", "blueberry", "cherry"];
+    /// let by_letter: Vec<Range> = words
+    ///     .group_ranges_by(|a, b| a.as_bytes()[0] == b.as_bytes()[0])
+    ///     .map(|(r, _)| r)
+    ///     .collect();
+    /// assert_eq!(by_letter, [Range(0, 2), Range(2, 4), Range(4, 5)]);
+    /// ```
+    fn group_ranges_by<F: FnMut(&T, &T) -> bool>(&self, same_group: F) -> GroupRanges<'_, T, F>;
+
+    /// Returns an iterator over the maximal runs of elements matching `pred`, in
+    /// order.
+    ///
+    /// The ranges are never empty and never touch each other.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let samples = [0, 3, 5, 0, 0, 2, 0];
+    /// let sounds: Vec<Range> = samples.ranges_where(|s| *s != 0).collect();
+    /// assert_eq!(sounds, [Range(1, 3), Range(5, 6)]);
+    /// ```
+    fn ranges_where<P: FnMut(&T) -> bool>(&self, pred: P) -> RangesWhere<'_, T, P>;
+
+    /// Returns an iterator over the ranges between elements matching `pred`, like
+    /// [`slice::split`] but yielding positions.
+    ///
+    /// As with `split`, `n` matches produce `n + 1` ranges, which are empty between
+    /// adjacent matches and at the ends when the slice starts or ends with one. An
+    /// empty slice yields a single empty range.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let csv = b"ab,,c";
+    /// let fields: Vec<Range> = csv.ranges_split_by(|b| *b == b',').collect();
+    /// assert_eq!(fields, [Range(0, 2), Range(3, 3), Range(4, 5)]);
+    /// ```
+    fn ranges_split_by<P: FnMut(&T) -> bool>(&self, pred: P) -> RangesSplitBy<'_, T, P>;
+
+    /// Returns an iterator over the non-overlapping occurrences of `needle`.
+    ///
+    /// Matches are found greedily from left to right: after a match, searching
+    /// resumes at its end, so `"aa"` occurs twice in `"aaaaa"`. An empty `needle`
+    /// matches nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let hits: Vec<Range> = b"aaaa".find_ranges(b"aa").collect();
+    /// assert_eq!(hits, [Range(0, 2), Range(2, 4)]);
+    /// ```
+    fn find_ranges<'a>(&'a self, needle: &'a [T]) -> FindRanges<'a, T>
+    where
+        T: PartialEq;
+
+    /// Returns the range of the first occurrence of `needle`, or `None` if it does
+    /// not occur or is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!(b"abcabc".find_range(b"bc"), Some(Range(1, 3)));
+    /// assert_eq!(b"abcabc".rfind_range(b"bc"), Some(Range(4, 6)));
+    /// ```
+    fn find_range(&self, needle: &[T]) -> Option<Range>
+    where
+        T: PartialEq;
+
+    /// Returns the range of the last occurrence of `needle`, or `None` if it does
+    /// not occur or is empty.
+    fn rfind_range(&self, needle: &[T]) -> Option<Range>
+    where
+        T: PartialEq;
+}
+
+/// Recovers the position of `sub` within `parent`, if `sub` is a subslice of it.
+///
+/// This compares pointers, like the unstable `<[T]>::subslice_range`, so it only
+/// succeeds for slices actually derived from `parent` (for example by `split`,
+/// `chunks` or indexing), not for equal elements stored elsewhere. An empty `sub`
+/// is accepted if it points within `parent` or one past its end.
+///
+/// Always returns `None` for zero-sized `T`, whose pointers carry no position.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// use ars::slice::subslice_range;
+///
+/// let data = [1, 2, 0, 3, 4];
+/// let mut parts = data.split(|x| *x == 0);
+/// let first = parts.next().unwrap();
+/// let second = parts.next().unwrap();
+/// assert_eq!(subslice_range(&data, second), Some(Range(3, 5)));
+/// assert_eq!(subslice_range(&data, first), Some(Range(0, 2)));
+/// assert_eq!(subslice_range(&data, &[3, 4]), None);
+/// ```
+#[must_use]
+pub fn subslice_range<T>(parent: &[T], sub: &[T]) -> Option<Range> {
+    let size = core::mem::size_of::<T>();
+    if size == 0 {
+        return None;
+    }
+    // A `sub` before `parent` wraps around to a huge offset and fails the bounds
+    // check below.
+    let offset = sub.as_ptr().addr().wrapping_sub(parent.as_ptr().addr());
+    if !offset.is_multiple_of(size) {
+        return None;
+    }
+    let start = offset / size;
+    let end = start.checked_add(sub.len())?;
+    (end <= parent.len()).then_some(Range(start, end))
+}
+
+/// Panics with a message describing why `r` cannot split a slice of length `len`.
+#[cold]
+#[track_caller]
+fn split_at_range_fail(r: Range, len: usize) -> ! {
+    if r.start() > r.end() {
+        panic!("range start {} is greater than end {}", r.start(), r.end());
+    }
+    panic!("range {r} out of bounds for slice of length {len}");
+}
+
+impl<T> SliceExt<T> for [T] {
+    #[inline]
+    fn get_range(&self, r: Range) -> Option<&[T]> {
+        self.get(r.start()..r.end())
+    }
+
+    #[inline]
+    fn get_range_mut(&mut self, r: Range) -> Option<&mut [T]> {
+        self.get_mut(r.start()..r.end())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn split_at_range(&self, r: Range) -> (&[T], &[T], &[T]) {
+        match self.try_split_at_range(r) {
+            Some(parts) => parts,
+            None => split_at_range_fail(r, self.len()),
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    fn split_at_range_mut(&mut self, r: Range) -> (&mut [T], &mut [T], &mut [T]) {
+        let len = self.len();
+        match self.try_split_at_range_mut(r) {
+            Some(parts) => parts,
+            None => split_at_range_fail(r, len),
+        }
+    }
+
+    #[inline]
+    fn try_split_at_range(&self, r: Range) -> Option<(&[T], &[T], &[T])> {
+        if r.start() > r.end() || r.end() > self.len() {
+            return None;
+        }
+        let (head, after) = self.split_at(r.end());
+        let (before, mid) = head.split_at(r.start());
+        Some((before, mid, after))
+    }
+
+    #[inline]
+    fn try_split_at_range_mut(&mut self, r: Range) -> Option<(&mut [T], &mut [T], &mut [T])> {
+        if r.start() > r.end() || r.end() > self.len() {
+            return None;
+        }
+        let (head, after) = self.split_at_mut(r.end());
+        let (before, mid) = head.split_at_mut(r.start());
+        Some((before, mid, after))
+    }
+
+    fn get_disjoint_ranges_mut<const N: usize>(
+        &mut self,
+        ranges: [Range; N],
+    ) -> Result<[&mut [T]; N], DisjointError> {
+        let len = self.len();
+        for (index, &range) in ranges.iter().enumerate() {
+            if range.start() > range.end() {
+                return Err(DisjointError::Inverted { index, range });
+            }
+            if range.end() > len {
+                return Err(DisjointError::OutOfBounds { index, range, len });
+            }
+        }
+
+        // Visit the ranges in positional order so each one can be split off the
+        // front of what remains.
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by_key(|&i| ranges[i]);
+
+        let mut prev: Option<usize> = None;
+        for &i in &order {
+            if ranges[i].is_empty() {
+                continue;
+            }
+            if let Some(p) = prev
+                && ranges[p].end() > ranges[i].start()
+            {
+                return Err(DisjointError::Overlapping {
+                    first: p.min(i),
+                    second: p.max(i),
+                });
+            }
+            prev = Some(i);
+        }
+
+        let mut out: [Option<&mut [T]>; N] = core::array::from_fn(|_| None);
+        let mut rest = self;
+        let mut offset = 0;
+        for &i in &order {
+            let range = ranges[i];
+            if range.is_empty() {
+                continue;
+            }
+            let (_, tail) = rest.split_at_mut(range.start() - offset);
+            let (piece, tail) = tail.split_at_mut(range.len());
+            out[i] = Some(piece);
+            rest = tail;
+            offset = range.end();
+        }
+        // Empty ranges were skipped above and map to empty slices.
+        Ok(out.map(Option::unwrap_or_default))
+    }
+
+    fn leading_range_where(&self, mut pred: impl FnMut(&T) -> bool) -> Range {
+        let end = self.iter().position(|x| !pred(x)).unwrap_or(self.len());
+        Range(0, end)
+    }
+
+    fn trailing_range_where(&self, mut pred: impl FnMut(&T) -> bool) -> Range {
+        let start = self.iter().rposition(|x| !pred(x)).map_or(0, |i| i + 1);
+        Range(start, self.len())
+    }
+
+    fn trimmed_range_where(&self, mut pred: impl FnMut(&T) -> bool) -> Range {
+        let start = self.leading_range_where(&mut pred).end();
+        let end = self[start..].trailing_range_where(&mut pred).start();
+        Range(start, start + end)
+    }
+
+    fn equal_range(&self, key: &T) -> Range
+    where
+        T: Ord,
+    {
+        let start = self.partition_point(|x| x < key);
+        let len = self[start..].partition_point(|x| x <= key);
+        Range::from_start_len(start, len)
+    }
+
+    fn equal_range_by_key<B: Ord>(&self, key: &B, mut f: impl FnMut(&T) -> B) -> Range {
+        let start = self.partition_point(|x| f(x) < *key);
+        let len = self[start..].partition_point(|x| f(x) <= *key);
+        Range::from_start_len(start, len)
+    }
+
+    #[inline]
+    fn iter_range(&self, r: Range) -> core::slice::Iter<'_, T> {
+        let r = r.clamp_to(self.len());
+        self.get_range(r).unwrap_or_default().iter()
+    }
+
+    #[inline]
+    fn iter_range_mut(&mut self, r: Range) -> core::slice::IterMut<'_, T> {
+        let r = r.clamp_to(self.len());
+        self.get_range_mut(r).unwrap_or_default().iter_mut()
+    }
+
+    #[track_caller]
+    fn iter_strided(&self, sr: StridedRange) -> core::iter::StepBy<core::slice::Iter<'_, T>> {
+        self[sr.range()].iter().step_by(sr.step())
+    }
+
+    fn group_ranges(&self) -> GroupRanges<'_, T, fn(&T, &T) -> bool>
+    where
+        T: PartialEq,
+    {
+        self.group_ranges_by(T::eq)
+    }
+
+    fn group_ranges_by<F: FnMut(&T, &T) -> bool>(&self, same_group: F) -> GroupRanges<'_, T, F> {
+        GroupRanges {
+            rest: self,
+            offset: 0,
+            same_group,
+        }
+    }
+
+    fn ranges_where<P: FnMut(&T) -> bool>(&self, pred: P) -> RangesWhere<'_, T, P> {
+        RangesWhere {
+            rest: self,
+            offset: 0,
+            pred,
+        }
+    }
+
+    fn ranges_split_by<P: FnMut(&T) -> bool>(&self, pred: P) -> RangesSplitBy<'_, T, P> {
+        RangesSplitBy {
+            rest: Some(self),
+            offset: 0,
+            pred,
+        }
+    }
+
+    fn find_ranges<'a>(&'a self, needle: &'a [T]) -> FindRanges<'a, T>
+    where
+        T: PartialEq,
+    {
+        FindRanges {
+            rest: self,
+            offset: 0,
+            needle,
+        }
+    }
+
+    fn find_range(&self, needle: &[T]) -> Option<Range>
+    where
+        T: PartialEq,
+    {
+        if needle.is_empty() {
+            return None;
+        }
+        let start = self.windows(needle.len()).position(|w| w == needle)?;
+        Some(Range::from_start_len(start, needle.len()))
+    }
+
+    fn rfind_range(&self, needle: &[T]) -> Option<Range>
+    where
+        T: PartialEq,
+    {
+        if needle.is_empty() {
+            return None;
+        }
+        let start = self.windows(needle.len()).rposition(|w| w == needle)?;
+        Some(Range::from_start_len(start, needle.len()))
+    }
+}
+
+/// An iterator over the maximal runs of a slice whose adjacent elements belong
+/// to the same group.
+///
+/// Created by [`SliceExt::group_ranges`] and [`SliceExt::group_ranges_by`].
+#[derive(Debug, Clone)]
+pub struct GroupRanges<'a, T, F> {
+    rest: &'a [T],
+    /// Index of `rest[0]` in the original slice.
+    offset: usize,
+    same_group: F,
+}
+
+impl<'a, T, F: FnMut(&T, &T) -> bool> Iterator for GroupRanges<'a, T, F> {
+    type Item = (Range, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.rest.first()?;
+        let len = 1 + self
+            .rest
+            .windows(2)
+            .position(|w| !(self.same_group)(&w[0], &w[1]))
+            .unwrap_or(self.rest.len() - 1);
+        let r = Range::from_start_len(self.offset, len);
+        self.rest = &self.rest[len..];
+        self.offset += len;
+        Some((r, first))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> DoubleEndedIterator for GroupRanges<'_, T, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let last = self.rest.len().checked_sub(1)?;
+        let start = self
+            .rest
+            .windows(2)
+            .rposition(|w| !(self.same_group)(&w[0], &w[1]))
+            .map_or(0, |i| i + 1);
+        let item = (
+            Range(self.offset + start, self.offset + last + 1),
+            &self.rest[start],
+        );
+        self.rest = &self.rest[..start];
+        Some(item)
+    }
+}
+
+impl<T, F: FnMut(&T, &T) -> bool> FusedIterator for GroupRanges<'_, T, F> {}
+
+/// An iterator over the maximal runs of a slice matching a predicate.
+///
+/// Created by [`SliceExt::ranges_where`].
+#[derive(Debug, Clone)]
+pub struct RangesWhere<'a, T, P> {
+    rest: &'a [T],
+    /// Index of `rest[0]` in the original slice.
+    offset: usize,
+    pred: P,
+}
+
+impl<T, P: FnMut(&T) -> bool> Iterator for RangesWhere<'_, T, P> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let Some(start) = self.rest.iter().position(&mut self.pred) else {
+            self.offset += self.rest.len();
+            self.rest = &[];
+            return None;
+        };
+        let len = self.rest[start..]
+            .iter()
+            .position(|x| !(self.pred)(x))
+            .unwrap_or(self.rest.len() - start);
+        let r = Range::from_start_len(self.offset + start, len);
+        self.rest = &self.rest[start + len..];
+        self.offset = r.end();
+        Some(r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.rest.len().div_ceil(2)))
+    }
+}
+
+impl<T, P: FnMut(&T) -> bool> FusedIterator for RangesWhere<'_, T, P> {}
+
+/// An iterator over the ranges of a slice between elements matching a predicate.
+///
+/// Created by [`SliceExt::ranges_split_by`].
+#[derive(Debug, Clone)]
+pub struct RangesSplitBy<'a, T, P> {
+    /// `None` once the last range has been yielded.
+    rest: Option<&'a [T]>,
+    /// Index of the start of `rest` in the original slice.
+    offset: usize,
+    pred: P,
+}
+
+impl<T, P: FnMut(&T) -> bool> Iterator for RangesSplitBy<'_, T, P> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let rest = self.rest?;
+        match rest.iter().position(&mut self.pred) {
+            Some(i) => {
+                let r = Range::from_start_len(self.offset, i);
+                self.rest = Some(&rest[i + 1..]);
+                self.offset += i + 1;
+                Some(r)
+            }
+            None => {
+                self.rest = None;
+                Some(Range::from_start_len(self.offset, rest.len()))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rest
+            .map_or((0, Some(0)), |rest| (1, Some(rest.len() + 1)))
+    }
+}
+
+impl<T, P: FnMut(&T) -> bool> FusedIterator for RangesSplitBy<'_, T, P> {}
+
+/// An iterator over the non-overlapping occurrences of a needle in a slice.
+///
+/// Created by [`SliceExt::find_ranges`].
+#[derive(Debug, Clone)]
+pub struct FindRanges<'a, T> {
+    rest: &'a [T],
+    /// Index of `rest[0]` in the original slice.
+    offset: usize,
+    needle: &'a [T],
+}
+
+impl<T: PartialEq> Iterator for FindRanges<'_, T> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let Some(found) = self.rest.find_range(self.needle) else {
+            self.rest = &[];
+            return None;
+        };
+        let r = found.offset(self.offset);
+        self.rest = &self.rest[found.end()..];
+        self.offset = r.end();
+        Some(r)
+    }
+}
+
+impl<T: PartialEq> FusedIterator for FindRanges<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn get_range_in_bounds_matches_index() {
+        let a = [1, 2, 3, 4, 5];
+        for r in [Range(0, 5), Range(1, 3), Range(2, 2), Range(5, 5)] {
+            assert_eq!(a.get_range(r), Some(&a[r]));
+        }
+    }
+
+    #[test]
+    fn get_range_out_of_bounds_is_none_where_index_panics() {
+        let a = [1, 2, 3];
+        for r in [Range(2, 4), Range(4, 4), Range(3, 1), Range(0, usize::MAX)] {
+            assert_eq!(a.get_range(r), None);
+            assert!(catch_unwind(|| a[r].len()).is_err());
+        }
+    }
+
+    #[test]
+    fn split_at_range_three_parts() {
+        let a = [1, 2, 3, 4, 5];
+        assert_eq!(
+            a.split_at_range(Range(1, 3)),
+            (&[1][..], &[2, 3][..], &[4, 5][..])
+        );
+        assert_eq!(a.split_at_range(Range(0, 5)), (&[][..], &a[..], &[][..]));
+        assert_eq!(a.split_at_range(Range(5, 5)), (&a[..], &[][..], &[][..]));
+        assert_eq!(a.try_split_at_range(Range(2, 6)), None);
+        assert_eq!(a.try_split_at_range(Range(3, 2)), None);
+    }
+
+    #[test]
+    fn split_at_range_mut_writes_all_parts_at_once() {
+        let mut a = [0; 6];
+        let (before, mid, after) = a.split_at_range_mut(Range(2, 4));
+        before.fill(1);
+        mid.fill(2);
+        after.fill(3);
+        assert_eq!(a, [1, 1, 2, 2, 3, 3]);
+
+        assert!(a.try_split_at_range_mut(Range(0, 7)).is_none());
+        let (_, mid, _) = a.try_split_at_range_mut(Range(0, 6)).unwrap();
+        mid.fill(9);
+        assert_eq!(a, [9; 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range 2..6 out of bounds for slice of length 5")]
+    fn split_at_range_out_of_bounds_panics() {
+        let a = [0; 5];
+        let _ = a.split_at_range(Range(2, 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "range start 4 is greater than end 2")]
+    fn split_at_range_mut_inverted_panics() {
+        let mut a = [0; 5];
+        let _ = a.split_at_range_mut(Range(4, 2));
+    }
+
+    #[test]
+    fn get_disjoint_ranges_mut_in_input_order() {
+        let mut a = [0; 8];
+        let [x, y, z] = a
+            .get_disjoint_ranges_mut([Range(5, 8), Range(0, 2), Range(2, 5)])
+            .unwrap();
+        x.fill(1);
+        y.fill(2);
+        z.fill(3);
+        assert_eq!(a, [2, 2, 3, 3, 3, 1, 1, 1]);
+
+        let [] = a.get_disjoint_ranges_mut([]).unwrap();
+    }
+
+    #[test]
+    fn get_disjoint_ranges_mut_empty_ranges_never_overlap() {
+        let mut a = [0; 4];
+        let [x, e1, e2] = a
+            .get_disjoint_ranges_mut([Range(0, 4), Range(2, 2), Range(2, 2)])
+            .unwrap();
+        x.fill(7);
+        assert!(e1.is_empty() && e2.is_empty());
+        assert_eq!(a, [7; 4]);
+    }
+
+    #[test]
+    fn get_disjoint_ranges_mut_errors() {
+        let mut a = [0; 4];
+        assert_eq!(
+            a.get_disjoint_ranges_mut([Range(0, 1), Range(3, 5)])
+                .unwrap_err(),
+            DisjointError::OutOfBounds {
+                index: 1,
+                range: Range(3, 5),
+                len: 4
+            }
+        );
+        assert_eq!(
+            a.get_disjoint_ranges_mut([Range(3, 2)]).unwrap_err(),
+            DisjointError::Inverted {
+                index: 0,
+                range: Range(3, 2)
+            }
+        );
+        let err = a
+            .get_disjoint_ranges_mut([Range(2, 4), Range(0, 1), Range(1, 3)])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            DisjointError::Overlapping {
+                first: 0,
+                second: 2
+            }
+        );
+        assert_eq!(std::format!("{err}"), "ranges 0 and 2 overlap");
+        // Touching ranges are fine.
+        assert!(
+            a.get_disjoint_ranges_mut([Range(0, 2), Range(2, 4)])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn leading_and_trailing_runs() {
+        let a = [0, 0, 1, 2, 0];
+        let zero = |x: &i32| *x == 0;
+        assert_eq!(a.leading_range_where(zero), Range(0, 2));
+        assert_eq!(a.trailing_range_where(zero), Range(4, 5));
+        assert_eq!(a.trimmed_range_where(zero), Range(2, 4));
+        assert_eq!(&a[a.trimmed_range_where(zero)], [1, 2]);
+    }
+
+    #[test]
+    fn runs_when_everything_matches() {
+        let a = [0; 3];
+        let zero = |x: &i32| *x == 0;
+        assert_eq!(a.leading_range_where(zero), Range(0, 3));
+        assert_eq!(a.trailing_range_where(zero), Range(0, 3));
+        assert_eq!(a.trimmed_range_where(zero), Range(3, 3));
+    }
+
+    #[test]
+    fn runs_when_nothing_matches() {
+        let a = [1, 2, 3];
+        let zero = |x: &i32| *x == 0;
+        assert_eq!(a.leading_range_where(zero), Range(0, 0));
+        assert_eq!(a.trailing_range_where(zero), Range(3, 3));
+        assert_eq!(a.trimmed_range_where(zero), Range(0, 3));
+    }
+
+    #[test]
+    fn runs_on_empty_slice() {
+        let a: [i32; 0] = [];
+        let zero = |x: &i32| *x == 0;
+        assert_eq!(a.leading_range_where(zero), Range(0, 0));
+        assert_eq!(a.trailing_range_where(zero), Range(0, 0));
+        assert_eq!(a.trimmed_range_where(zero), Range(0, 0));
+    }
+
+    #[test]
+    fn subslice_range_of_derived_slices() {
+        let a = [10u32, 11, 12, 13, 14];
+        assert_eq!(subslice_range(&a, &a), Some(Range(0, 5)));
+        assert_eq!(subslice_range(&a, &a[1..3]), Some(Range(1, 3)));
+        assert_eq!(subslice_range(&a, &a[4..]), Some(Range(4, 5)));
+        for (i, chunk) in a.chunks(2).enumerate() {
+            assert_eq!(
+                subslice_range(&a, chunk),
+                Some(Range(2 * i, (2 * i + 2).min(5)))
+            );
+        }
+        // Only the positions of `parent` count, not its elements.
+        assert_eq!(subslice_range(&a[1..3], &a[..2]), None);
+        assert_eq!(subslice_range(&a[..2], &a[1..3]), None);
+        assert_eq!(subslice_range(&a, &[11, 12]), None);
+    }
+
+    #[test]
+    fn subslice_range_empty_subslices() {
+        let a = [1u16, 2, 3];
+        assert_eq!(subslice_range(&a, &a[0..0]), Some(Range(0, 0)));
+        assert_eq!(subslice_range(&a, &a[2..2]), Some(Range(2, 2)));
+        // One past the end is still within `parent`.
+        assert_eq!(subslice_range(&a, &a[3..]), Some(Range(3, 3)));
+        assert_eq!(subslice_range(&a[..1], &a[2..2]), None);
+        let b = [0u16; 3];
+        assert_eq!(subslice_range(&a, &b[..0]), None);
+    }
+
+    #[test]
+    fn subslice_range_rejects_views_between_elements() {
+        let a = [[0u8; 2]; 4];
+        // Two elements starting halfway through `a[0]`.
+        let (between, _) = a.as_flattened()[1..5].as_chunks::<2>();
+        assert_eq!(between.len(), 2);
+        assert_eq!(subslice_range(&a, between), None);
+        let (aligned, _) = a.as_flattened()[2..6].as_chunks::<2>();
+        assert_eq!(subslice_range(&a, aligned), Some(Range(1, 3)));
+    }
+
+    #[test]
+    fn subslice_range_zero_sized_types() {
+        let a = [(); 8];
+        assert_eq!(subslice_range(&a, &a), None);
+        assert_eq!(subslice_range(&a, &a[2..4]), None);
+        assert_eq!(subslice_range(&a, &a[..0]), None);
+    }
+
+    #[test]
+    fn equal_range_runs_and_insertion_points() {
+        let a = [1, 3, 3, 3, 5, 5, 8];
+        assert_eq!(a.equal_range(&3), Range(1, 4));
+        assert_eq!(a.equal_range(&5), Range(4, 6));
+        assert_eq!(a.equal_range(&1), Range(0, 1));
+        assert_eq!(a.equal_range(&8), Range(6, 7));
+        // Absent keys give an empty range at the insertion point.
+        assert_eq!(a.equal_range(&0), Range(0, 0));
+        assert_eq!(a.equal_range(&4), Range(4, 4));
+        assert_eq!(a.equal_range(&9), Range(7, 7));
+        let empty: [i32; 0] = [];
+        assert_eq!(empty.equal_range(&1), Range(0, 0));
+    }
+
+    #[test]
+    fn equal_range_composes_with_range_ops() {
+        let a = [1, 3, 3, 3, 5, 5, 8];
+        let threes = a.equal_range(&3);
+        assert_eq!(&a[threes], [3, 3, 3]);
+        assert_eq!(threes.intersect(&Range(2, 6)), Some(Range(2, 4)));
+        let fives = a.equal_range(&5);
+        assert_eq!(threes.hull(&fives), Range(1, 6));
+        assert_eq!(&a[threes.hull(&fives)], [3, 3, 3, 5, 5]);
+        let stored = std::vec![threes, fives];
+        assert_eq!(&a[stored[1]], [5, 5]);
+    }
+
+    #[test]
+    fn equal_range_by_key_uses_extracted_key() {
+        let pairs = [(0, 'a'), (1, 'b'), (1, 'c'), (4, 'd')];
+        assert_eq!(pairs.equal_range_by_key(&1, |p| p.0), Range(1, 3));
+        assert_eq!(pairs.equal_range_by_key(&2, |p| p.0), Range(3, 3));
+        assert_eq!(pairs.equal_range_by_key(&4, |p| p.0), Range(3, 4));
+    }
+
+    #[test]
+    fn iter_strided_visits_strided_indices() {
+        let a: [usize; 20] = core::array::from_fn(|i| i * 10);
+        for step in 1..6 {
+            for start in 0..8 {
+                let sr = StridedRange::new(start, 17, step).unwrap();
+                assert!(a.iter_strided(sr).copied().eq(sr.iter().map(|i| a[i])));
+                assert_eq!(a.iter_strided(sr).len(), sr.len());
+            }
+        }
+        let empty = StridedRange::new(20, 20, 3).unwrap();
+        assert_eq!(a.iter_strided(empty).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn iter_strided_out_of_bounds_panics() {
+        let a = [0; 4];
+        let _ = a.iter_strided(StridedRange::new(0, 5, 2).unwrap());
+    }
+
+    #[test]
+    fn iter_range_matches_clamped_index() {
+        let a = [1, 2, 3, 4, 5];
+        for start in 0..8 {
+            for end in start..9 {
+                let r = Range(start, end);
+                let clamped = &a[r.clamp_to(a.len())];
+                assert!(a.iter_range(r).eq(clamped));
+                assert_eq!(a.iter_range(r).len(), clamped.len());
+                assert!(a.iter_range(r).rev().eq(clamped.iter().rev()));
+            }
+        }
+    }
+
+    #[test]
+    fn iter_range_out_of_range_is_empty() {
+        let a = [1, 2, 3];
+        assert_eq!(a.iter_range(Range(3, 10)).next(), None);
+        assert_eq!(a.iter_range(Range(7, 10)).next(), None);
+        assert_eq!(a.iter_range(Range(2, 1)).next(), None);
+        assert_eq!(a.iter_range(Range(9, 4)).next(), None);
+        let mut b = [0; 3];
+        assert_eq!(b.iter_range_mut(Range(5, 9)).len(), 0);
+        assert_eq!(b.iter_range_mut(Range(2, 1)).len(), 0);
+    }
+
+    #[test]
+    fn iter_range_mut_writes_clamped_part() {
+        let mut a = [0; 5];
+        for (i, x) in a.iter_range_mut(Range(3, 100)).enumerate() {
+            *x = i + 1;
+        }
+        assert_eq!(a, [0, 0, 0, 1, 2]);
+        a.iter_range_mut(Range(0, 2)).rev().for_each(|x| *x = 9);
+        assert_eq!(a, [9, 9, 0, 1, 2]);
+    }
+
+    #[test]
+    fn get_range_mut_writes_through() {
+        let mut a = [0; 4];
+        a.get_range_mut(Range(1, 3)).unwrap().fill(7);
+        assert_eq!(a, [0, 7, 7, 0]);
+        assert!(a.get_range_mut(Range(3, 5)).is_none());
+        assert!(a.get_range_mut(Range(2, 1)).is_none());
+    }
+
+    #[test]
+    fn group_ranges_edge_cases() {
+        use std::vec::Vec;
+
+        let same = [7; 5];
+        let runs: Vec<(Range, &i32)> = same.group_ranges().collect();
+        assert_eq!(runs, [(Range(0, 5), &7)]);
+
+        let distinct = [1, 2, 3, 4];
+        let runs: Vec<Range> = distinct.group_ranges().map(|(r, _)| r).collect();
+        assert_eq!(runs, [Range(0, 1), Range(1, 2), Range(2, 3), Range(3, 4)]);
+
+        let empty: [i32; 0] = [];
+        assert_eq!(empty.group_ranges().next(), None);
+        assert_eq!(empty.group_ranges().next_back(), None);
+    }
+
+    #[test]
+    fn group_ranges_tile_the_slice() {
+        use std::vec::Vec;
+
+        let a = [1, 1, 2, 3, 3, 3, 1, 2, 2];
+        let runs: Vec<(Range, &i32)> = a.group_ranges().collect();
+        let mut cursor = 0;
+        for &(r, value) in &runs {
+            assert_eq!(r.start(), cursor);
+            assert!(!r.is_empty());
+            assert!(a[r].iter().all(|x| x == value));
+            cursor = r.end();
+        }
+        assert_eq!(cursor, a.len());
+        assert_eq!(runs.len(), 5);
+
+        let mut backward: Vec<(Range, &i32)> = a.group_ranges().rev().collect();
+        backward.reverse();
+        assert_eq!(backward, runs);
+
+        let mut it = a.group_ranges();
+        assert_eq!(it.next(), Some((Range(0, 2), &1)));
+        assert_eq!(it.next_back(), Some((Range(7, 9), &2)));
+        assert_eq!(it.next_back(), Some((Range(6, 7), &1)));
+        assert_eq!(it.next(), Some((Range(2, 3), &2)));
+        assert_eq!(it.next(), Some((Range(3, 6), &3)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn group_ranges_by_custom_equivalence() {
+        use std::vec::Vec;
+
+        // Runs of increasing values.
+        let a = [1, 2, 5, 3, 4, 0];
+        let runs: Vec<(Range, &i32)> = a.group_ranges_by(|x, y| x < y).collect();
+        assert_eq!(
+            runs,
+            [(Range(0, 3), &1), (Range(3, 5), &3), (Range(5, 6), &0)]
+        );
+    }
+
+    #[test]
+    fn ranges_where_runs_touching_both_ends() {
+        use std::vec::Vec;
+
+        let a = [1, 1, 0, 2, 0, 0, 3, 3];
+        let runs: Vec<Range> = a.ranges_where(|x| *x != 0).collect();
+        assert_eq!(runs, [Range(0, 2), Range(3, 4), Range(6, 8)]);
+        for r in &runs {
+            assert!(a[r].iter().all(|x| *x != 0));
+        }
+
+        let all: Vec<Range> = a.ranges_where(|_| true).collect();
+        assert_eq!(all, [Range(0, 8)]);
+        assert_eq!(a.ranges_where(|_| false).next(), None);
+        assert_eq!([0u8; 0].ranges_where(|_| true).next(), None);
+
+        let mut it = a.ranges_where(|x| *x == 0);
+        assert_eq!(it.next(), Some(Range(2, 3)));
+        assert_eq!(it.next(), Some(Range(4, 6)));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn ranges_split_by_matches_slice_split() {
+        use std::vec::Vec;
+
+        let inputs: [&[u8]; 7] = [b"a,b,c", b",a,", b",", b"", b"abc", b",,", b"a,,b"];
+        for input in inputs {
+            let ranges: Vec<Range> = input.ranges_split_by(|b| *b == b',').collect();
+            let pieces: Vec<&[u8]> = input.split(|b| *b == b',').collect();
+            assert_eq!(ranges.len(), pieces.len(), "{input:?}");
+            for (r, piece) in ranges.iter().zip(pieces) {
+                assert_eq!(&input[r], piece, "{input:?}");
+                assert_eq!(subslice_range(input, piece), Some(*r));
+            }
+        }
+        let edges: Vec<Range> = b",x,".ranges_split_by(|b| *b == b',').collect();
+        assert_eq!(edges, [Range(0, 0), Range(1, 2), Range(3, 3)]);
+    }
+
+    #[test]
+    fn find_ranges_is_greedy_and_non_overlapping() {
+        use std::vec::Vec;
+
+        let hits: Vec<Range> = b"aaaaa".find_ranges(b"aa").collect();
+        assert_eq!(hits, [Range(0, 2), Range(2, 4)]);
+        let hits: Vec<Range> = b"abababa".find_ranges(b"aba").collect();
+        assert_eq!(hits, [Range(0, 3), Range(4, 7)]);
+        // Matches at the very end and the very start.
+        let hits: Vec<Range> = b"xyzxy".find_ranges(b"xy").collect();
+        assert_eq!(hits, [Range(0, 2), Range(3, 5)]);
+        let whole: Vec<Range> = b"abc".find_ranges(b"abc").collect();
+        assert_eq!(whole, [Range(0, 3)]);
+
+        assert_eq!(b"abc".find_ranges(b"abcd").next(), None);
+        assert_eq!(b"abc".find_ranges(b"").next(), None);
+        assert_eq!(b"".find_ranges(b"a").next(), None);
+    }
+
+    #[test]
+    fn find_range_and_rfind_range() {
+        let hay = [1, 2, 1, 2, 1];
+        assert_eq!(hay.find_range(&[1, 2]), Some(Range(0, 2)));
+        assert_eq!(hay.rfind_range(&[1, 2]), Some(Range(2, 4)));
+        assert_eq!(hay.find_range(&[2, 1]), Some(Range(1, 3)));
+        assert_eq!(hay.rfind_range(&[2, 1]), Some(Range(3, 5)));
+        assert_eq!(hay.rfind_range(&[1]), Some(Range(4, 5)));
+        assert_eq!(hay.find_range(&[3]), None);
+        assert_eq!(hay.find_range(&[]), None);
+        assert_eq!(hay.rfind_range(&[]), None);
+        let r = hay.find_range(&[2, 1, 2]).unwrap();
+        assert_eq!(&hay[r], [2, 1, 2]);
+    }
+}
@@ -0,0 +1,1327 @@
+//! String utilities built on [`Range`].
+
+use core::fmt::{Display, Formatter};
+use core::iter::FusedIterator;
+
+use crate::range::Range;
+use crate::slice::subslice_range;
+
+/// The error returned by [`StrExt::try_slice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SliceStrError {
+    /// The range is inverted.
+    Inverted(Range),
+    /// The range extends past the end of the string.
+    OutOfBounds {
+        /// The offending range.
+        range: Range,
+        /// Length of the string in bytes.
+        len: usize,
+    },
+    /// A bound of the range falls inside a `char`.
+    NotCharBoundary {
+        /// The offending byte offset; the start if both bounds are bad.
+        offset: usize,
+    },
+}
+
+impl Display for SliceStrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Inverted(range) => write!(f, "range {range} is inverted"),
+            Self::OutOfBounds { range, len } => {
+                write!(f, "range {range} out of bounds for string of length {len}")
+            }
+            Self::NotCharBoundary { offset } => {
+                write!(f, "byte offset {offset} is not a char boundary")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SliceStrError {}
+
+/// Recovers the byte range of `sub` within `parent`, if `sub` is a substring of
+/// it.
+///
+/// The `str` counterpart of [`subslice_range`]: it compares pointers, so `sub`
+/// must be derived from `parent`, e.g. by `split`, `lines` or `trim`. The result
+/// always lies on `char` boundaries and can index `parent` directly.
+///
+/// # Example
+/// ```
+/// use ars::range::Range;
+/// use ars::str::substr_range;
+///
+/// let line = "  key = value ";
+/// assert_eq!(substr_range(line, line.trim()), Some(Range(2, 13)));
+/// assert_eq!(substr_range(line, "key"), None);
+/// ```
+#[must_use]
+pub fn substr_range(parent: &str, sub: &str) -> Option<Range> {
+    subslice_range(parent.as_bytes(), sub.as_bytes())
+}
+
+/// Extension methods for `str` working with byte [`Range`]s.
+///
+/// # Example
+/// ```
+/// use ars::prelude::*;
+/// use ars::range::Range;
+///
+/// let s = "naïve";
+/// // Byte 3 is inside 'ï', so `&s[1..3]` would panic.
+/// assert_eq!(s.slice_snapped(Range(1, 3)), "a");
+/// ```
+pub trait StrExt {
+    /// Returns the substring covered by `r`, or `None` if `r` is inverted, out of
+    /// bounds, or not on `char` boundaries.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!("naïve".get_range(Range(1, 4)), Some("aï"));
+    /// assert_eq!("naïve".get_range(Range(1, 3)), None);
+    /// ```
+    fn get_range(&self, r: Range) -> Option<&str>;
+
+    /// Returns the mutable substring covered by `r`, or `None` if `r` is
+    /// inverted, out of bounds, or not on `char` boundaries.
+    fn get_range_mut(&mut self, r: Range) -> Option<&mut str>;
+
+    /// Returns the substring covered by `r`, like [`get_range`](Self::get_range),
+    /// but reporting why it can't be sliced.
+    ///
+    /// # Errors
+    /// Returns [`SliceStrError::Inverted`] if `r` is inverted,
+    /// [`SliceStrError::OutOfBounds`] if it ends past the string, and otherwise
+    /// [`SliceStrError::NotCharBoundary`] if a bound falls inside a `char`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// use ars::str::SliceStrError;
+    /// assert_eq!("naïve".try_slice(Range(3, 6)), Err(SliceStrError::NotCharBoundary { offset: 3 }));
+    /// ```
+    fn try_slice(&self, r: Range) -> Result<&str, SliceStrError>;
+
+    /// Returns an iterator over the byte range of each line, excluding its
+    /// terminator.
+    ///
+    /// Lines end with `\n` or `\r\n`, and the final line's terminator is
+    /// optional, exactly as for [`str::lines`]: `&s[r]` yields the same lines.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let lines: Vec<Range> = "ab\r\n\ncd".line_ranges().collect();
+    /// assert_eq!(lines, [Range(0, 2), Range(4, 4), Range(5, 7)]);
+    /// ```
+    fn line_ranges(&self) -> LineRanges<'_>;
+
+    /// Returns an iterator over the byte range of each line, including its
+    /// terminator, so the ranges tile the whole string.
+    ///
+    /// The lines are the same as for [`line_ranges`](Self::line_ranges).
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let lines: Vec<Range> = "ab\r\n\ncd".line_ranges_with_terminator().collect();
+    /// assert_eq!(lines, [Range(0, 4), Range(4, 5), Range(5, 7)]);
+    /// ```
+    fn line_ranges_with_terminator(&self) -> LineRangesWithTerminator<'_>;
+
+    /// Returns the zero-based number and byte range, excluding the terminator, of
+    /// the line containing byte `offset`.
+    ///
+    /// An offset within a line's terminator belongs to that line. An offset of
+    /// `self.len()` lies on the last line, or on an empty line past it if the
+    /// string is empty or ends with a terminator, as a cursor there would. Returns
+    /// `None` if `offset > self.len()`. Runs in a single pass over the string up
+    /// to the end of the line.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let s = "fn main() {\n    oops\n}\n";
+    /// assert_eq!(s.line_of_offset(18), Some((1, Range(12, 20))));
+    /// assert_eq!(s.line_of_offset(s.len()), Some((3, Range(23, 23))));
+    /// ```
+    fn line_of_offset(&self, offset: usize) -> Option<(usize, Range)>;
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// occurrences of `pat`, in order.
+    ///
+    /// These are the same matches as [`str::match_indices`].
+    ///
+    /// # Panics
+    /// Panics if `pat` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let hits: Vec<Range> = "to be or not to be".match_ranges("be").collect();
+    /// assert_eq!(hits, [Range(3, 5), Range(16, 18)]);
+    /// ```
+    fn match_ranges<'a>(&'a self, pat: &'a str) -> MatchRanges<'a>;
+
+    /// Returns an iterator over the byte ranges of the occurrences of `c`, in
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let hits: Vec<Range> = "a€b€".match_ranges_char('€').collect();
+    /// assert_eq!(hits, [Range(1, 4), Range(5, 8)]);
+    /// ```
+    fn match_ranges_char(&self, c: char) -> MatchRanges<'_>;
+
+    /// Like [`match_ranges`](Self::match_ranges), but comparing ASCII letters
+    /// case-insensitively, as [`str::eq_ignore_ascii_case`] does.
+    ///
+    /// Other characters must match exactly. The search takes `O(n * m)` for
+    /// a needle of length `m`.
+    ///
+    /// # Panics
+    /// Panics if `pat` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let hits: Vec<Range> = "Error: ERROR".match_ranges_ignore_ascii_case("error").collect();
+    /// assert_eq!(hits, [Range(0, 5), Range(7, 12)]);
+    /// ```
+    fn match_ranges_ignore_ascii_case<'a>(&'a self, pat: &'a str) -> MatchRanges<'a>;
+
+    /// Returns an iterator over the byte ranges of the pieces
+    /// [`str::split_whitespace`] yields: the non-empty runs between Unicode
+    /// whitespace.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let s = " let  x\u{3000}= 1\n";
+    /// let tokens: Vec<Range> = s.split_whitespace_ranges().collect();
+    /// assert_eq!(tokens, [Range(1, 4), Range(6, 7), Range(10, 11), Range(12, 13)]);
+    /// ```
+    fn split_whitespace_ranges(&self) -> SplitWhitespaceRanges<'_>;
+
+    /// Returns an iterator over the byte ranges of the pieces [`str::split`]
+    /// yields for `delim`, including the empty ones between consecutive
+    /// delimiters and at either end.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let fields: Vec<Range> = "a,,bc,".split_ranges(',').collect();
+    /// assert_eq!(fields, [Range(0, 1), Range(2, 2), Range(3, 5), Range(6, 6)]);
+    /// ```
+    fn split_ranges(&self, delim: char) -> SplitRanges<'_>;
+
+    /// Returns the byte range of the string with leading and trailing whitespace
+    /// removed, so `&s[s.trim_range()] == s.trim()`.
+    ///
+    /// The range starts where [`trim_start_range`](Self::trim_start_range)'s does,
+    /// so a string of only whitespace yields the empty range at its end.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!("  key = value \n".trim_range(), Range(2, 13));
+    /// assert_eq!(" \t ".trim_range(), Range(3, 3));
+    /// ```
+    fn trim_range(&self) -> Range;
+
+    /// Returns the byte range of the string with leading whitespace removed, so
+    /// `&s[s.trim_start_range()] == s.trim_start()`.
+    ///
+    /// A string of only whitespace yields the empty range at its end.
+    fn trim_start_range(&self) -> Range;
+
+    /// Returns the byte range of the string with trailing whitespace removed, so
+    /// `&s[s.trim_end_range()] == s.trim_end()`.
+    ///
+    /// Nothing is trimmed from the start, so a string of only whitespace yields
+    /// the empty range at 0.
+    fn trim_end_range(&self) -> Range;
+
+    /// Returns the byte range of the string with the leading and trailing
+    /// characters matching `pred` removed, so
+    /// `&s[s.trim_range_matches(f)] == s.trim_matches(f)`.
+    ///
+    /// As for [`trim_range`](Self::trim_range), a string of only matching
+    /// characters yields the empty range at its end.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!("**bold**".trim_range_matches(|c| c == '*'), Range(2, 6));
+    /// ```
+    fn trim_range_matches<F: FnMut(char) -> bool>(&self, pred: F) -> Range;
+
+    /// Returns an iterator over the byte range of each extended grapheme cluster,
+    /// the unit a user perceives as one character.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// // 'e' with a combining acute accent, then a flag.
+    /// let s = "e\u{301}🇳🇿";
+    /// let clusters: Vec<Range> = s.grapheme_ranges().collect();
+    /// assert_eq!(clusters, [Range(0, 3), Range(3, 11)]);
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn grapheme_ranges(&self) -> GraphemeRanges<'_>;
+
+    /// Shrinks the byte range `r` to extended grapheme cluster boundaries,
+    /// moving the start forward and the end backward, so the result covers only
+    /// whole clusters.
+    ///
+    /// The grapheme counterpart of [`Range::snap_to_char_boundaries`]: bounds past
+    /// the end are clamped first, and a range that contains no whole cluster
+    /// yields an empty range at the first boundary after its start.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let s = "ae\u{301}b";
+    /// // Byte 2 is between 'e' and its accent.
+    /// assert_eq!(s.snap_to_grapheme_boundaries(Range(0, 2)), Range(0, 1));
+    /// assert_eq!(s.snap_to_grapheme_boundaries(Range(2, 5)), Range(4, 5));
+    /// ```
+    #[cfg(feature = "unicode")]
+    fn snap_to_grapheme_boundaries(&self, r: Range) -> Range;
+
+    /// Returns the substring covered by `r` after shrinking it to `char`
+    /// boundaries with [`Range::snap_to_char_boundaries`].
+    ///
+    /// Never panics: bounds past the end are clamped, and a range that contains no
+    /// whole character yields an empty string.
+    fn slice_snapped(&self, r: Range) -> &str;
+
+    /// Converts a range of `char` indices into the byte range covering the same
+    /// characters.
+    ///
+    /// Returns `None` if `chars` is inverted or extends past the number of
+    /// characters. Runs in a single pass over the string up to `chars.end()`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!("a€b".char_range_to_byte_range(Range(1, 3)), Some(Range(1, 5)));
+    /// ```
+    fn char_range_to_byte_range(&self, chars: Range) -> Option<Range>;
+
+    /// Converts a byte range into the range of `char` indices it covers.
+    ///
+    /// Returns `None` if `bytes` is inverted, extends past the end of the string,
+    /// or either bound is not on a `char` boundary. Runs in a single pass over the
+    /// string up to `bytes.end()`.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// assert_eq!("a€b".byte_range_to_char_range(Range(1, 5)), Some(Range(1, 3)));
+    /// assert_eq!("a€b".byte_range_to_char_range(Range(2, 5)), None);
+    /// ```
+    fn byte_range_to_char_range(&self, bytes: Range) -> Option<Range>;
+}
+
+impl StrExt for str {
+    #[inline]
+    fn get_range(&self, r: Range) -> Option<&str> {
+        self.get(r.start()..r.end())
+    }
+
+    #[inline]
+    fn get_range_mut(&mut self, r: Range) -> Option<&mut str> {
+        self.get_mut(r.start()..r.end())
+    }
+
+    fn line_ranges(&self) -> LineRanges<'_> {
+        LineRanges {
+            inner: self.line_ranges_with_terminator(),
+        }
+    }
+
+    fn line_ranges_with_terminator(&self) -> LineRangesWithTerminator<'_> {
+        LineRangesWithTerminator {
+            rest: self,
+            offset: 0,
+        }
+    }
+
+    fn line_of_offset(&self, offset: usize) -> Option<(usize, Range)> {
+        let before = self.as_bytes().get(..offset)?;
+        let (mut number, mut start) = (0, 0);
+        for (i, &b) in before.iter().enumerate() {
+            if b == b'\n' {
+                number += 1;
+                start = i + 1;
+            }
+        }
+        let end = self.as_bytes()[start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(self.len(), |i| start + i + 1);
+        let len = len_without_terminator(&self.as_bytes()[start..end]);
+        Some((number, Range::from_start_len(start, len)))
+    }
+
+    #[track_caller]
+    fn match_ranges<'a>(&'a self, pat: &'a str) -> MatchRanges<'a> {
+        assert!(!pat.is_empty(), "match pattern must be non-empty");
+        MatchRanges::new(self, Needle::Str(pat))
+    }
+
+    fn match_ranges_char(&self, c: char) -> MatchRanges<'_> {
+        MatchRanges::new(self, Needle::Char(c))
+    }
+
+    #[track_caller]
+    fn match_ranges_ignore_ascii_case<'a>(&'a self, pat: &'a str) -> MatchRanges<'a> {
+        assert!(!pat.is_empty(), "match pattern must be non-empty");
+        MatchRanges::new(self, Needle::IgnoreAsciiCase(pat))
+    }
+
+    fn split_whitespace_ranges(&self) -> SplitWhitespaceRanges<'_> {
+        SplitWhitespaceRanges {
+            parent: self,
+            inner: self.split_whitespace(),
+        }
+    }
+
+    fn split_ranges(&self, delim: char) -> SplitRanges<'_> {
+        SplitRanges {
+            parent: self,
+            inner: self.split(delim),
+        }
+    }
+
+    fn trim_range(&self) -> Range {
+        self.trim_range_matches(char::is_whitespace)
+    }
+
+    fn trim_start_range(&self) -> Range {
+        Range(self.len() - self.trim_start().len(), self.len())
+    }
+
+    fn trim_end_range(&self) -> Range {
+        Range(0, self.trim_end().len())
+    }
+
+    fn trim_range_matches<F: FnMut(char) -> bool>(&self, mut pred: F) -> Range {
+        let start = self.len() - self.trim_start_matches(&mut pred).len();
+        let end = self.trim_end_matches(pred).len();
+        // Trimming everything leaves `end <= start`.
+        Range(start, end.max(start))
+    }
+
+    #[cfg(feature = "unicode")]
+    fn grapheme_ranges(&self) -> GraphemeRanges<'_> {
+        GraphemeRanges {
+            inner: unicode_segmentation::UnicodeSegmentation::grapheme_indices(self, true),
+        }
+    }
+
+    #[cfg(feature = "unicode")]
+    fn snap_to_grapheme_boundaries(&self, r: Range) -> Range {
+        let r = r.clamp_to(self.len());
+        let start = ceil_grapheme_boundary(self, r.start());
+        let end = floor_grapheme_boundary(self, r.end());
+        if start <= end {
+            Range(start, end)
+        } else {
+            Range(start, start)
+        }
+    }
+
+    fn try_slice(&self, r: Range) -> Result<&str, SliceStrError> {
+        if r.start() > r.end() {
+            return Err(SliceStrError::Inverted(r));
+        }
+        if r.end() > self.len() {
+            return Err(SliceStrError::OutOfBounds {
+                range: r,
+                len: self.len(),
+            });
+        }
+        for offset in [r.start(), r.end()] {
+            if !self.is_char_boundary(offset) {
+                return Err(SliceStrError::NotCharBoundary { offset });
+            }
+        }
+        Ok(&self[r])
+    }
+
+    fn slice_snapped(&self, r: Range) -> &str {
+        &self[r.snap_to_char_boundaries(self)]
+    }
+
+    fn char_range_to_byte_range(&self, chars: Range) -> Option<Range> {
+        if chars.start() > chars.end() {
+            return None;
+        }
+        let mut start = None;
+        // Chain the end of the string as an extra boundary so a range ending at the
+        // character count maps to `self.len()`.
+        let boundaries = self
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(self.len()));
+        for (n, byte) in boundaries.enumerate() {
+            if n == chars.start() {
+                start = Some(byte);
+            }
+            if n == chars.end() {
+                return start.map(|start| Range(start, byte));
+            }
+        }
+        None
+    }
+
+    fn byte_range_to_char_range(&self, bytes: Range) -> Option<Range> {
+        if bytes.start() > bytes.end()
+            || !self.is_char_boundary(bytes.start())
+            || !self.is_char_boundary(bytes.end())
+        {
+            return None;
+        }
+        let start = self[..bytes.start()].chars().count();
+        let len = self[bytes].chars().count();
+        Some(Range::from_start_len(start, len))
+    }
+}
+
+/// Returns the length of `line` without its `\n` or `\r\n` terminator.
+fn len_without_terminator(line: &[u8]) -> usize {
+    match line {
+        [.., b'\r', b'\n'] => line.len() - 2,
+        [.., b'\n'] => line.len() - 1,
+        _ => line.len(),
+    }
+}
+
+/// An iterator over the byte ranges of the lines of a string, including their
+/// terminators.
+///
+/// Created by [`StrExt::line_ranges_with_terminator`].
+#[derive(Debug, Clone)]
+pub struct LineRangesWithTerminator<'a> {
+    rest: &'a str,
+    /// Byte offset of `rest` in the original string.
+    offset: usize,
+}
+
+impl<'a> LineRangesWithTerminator<'a> {
+    fn next_line(&mut self) -> Option<(Range, &'a str)> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = self.rest.find('\n').map_or(self.rest.len(), |i| i + 1);
+        let (line, rest) = self.rest.split_at(len);
+        let r = Range::from_start_len(self.offset, len);
+        self.rest = rest;
+        self.offset += len;
+        Some((r, line))
+    }
+
+    fn next_line_back(&mut self) -> Option<(Range, &'a str)> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let body = self.rest.strip_suffix('\n').unwrap_or(self.rest);
+        let start = body.rfind('\n').map_or(0, |i| i + 1);
+        let (rest, line) = self.rest.split_at(start);
+        let r = Range::from_start_len(self.offset + start, line.len());
+        self.rest = rest;
+        Some((r, line))
+    }
+}
+
+impl Iterator for LineRangesWithTerminator<'_> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        self.next_line().map(|(r, _)| r)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.rest.len();
+        (usize::from(len > 0), Some(len))
+    }
+}
+
+impl DoubleEndedIterator for LineRangesWithTerminator<'_> {
+    fn next_back(&mut self) -> Option<Range> {
+        self.next_line_back().map(|(r, _)| r)
+    }
+}
+
+impl FusedIterator for LineRangesWithTerminator<'_> {}
+
+/// An iterator over the byte ranges of the lines of a string, excluding their
+/// terminators.
+///
+/// Created by [`StrExt::line_ranges`].
+#[derive(Debug, Clone)]
+pub struct LineRanges<'a> {
+    inner: LineRangesWithTerminator<'a>,
+}
+
+impl Iterator for LineRanges<'_> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let (r, line) = self.inner.next_line()?;
+        Some(Range::from_start_len(
+            r.start(),
+            len_without_terminator(line.as_bytes()),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for LineRanges<'_> {
+    fn next_back(&mut self) -> Option<Range> {
+        let (r, line) = self.inner.next_line_back()?;
+        Some(Range::from_start_len(
+            r.start(),
+            len_without_terminator(line.as_bytes()),
+        ))
+    }
+}
+
+impl FusedIterator for LineRanges<'_> {}
+
+/// What a [`MatchRanges`] searches for.
+#[derive(Debug, Clone, Copy)]
+enum Needle<'a> {
+    Str(&'a str),
+    Char(char),
+    IgnoreAsciiCase(&'a str),
+}
+
+/// An iterator over the byte ranges of the non-overlapping matches of a
+/// pattern in a string.
+///
+/// Created by [`StrExt::match_ranges`], [`StrExt::match_ranges_char`] and
+/// [`StrExt::match_ranges_ignore_ascii_case`].
+#[derive(Debug, Clone)]
+pub struct MatchRanges<'a> {
+    rest: &'a str,
+    /// Byte offset of `rest` in the original string.
+    offset: usize,
+    needle: Needle<'a>,
+}
+
+impl<'a> MatchRanges<'a> {
+    fn new(haystack: &'a str, needle: Needle<'a>) -> Self {
+        Self {
+            rest: haystack,
+            offset: 0,
+            needle,
+        }
+    }
+
+    /// Returns the range of the first match in `rest`.
+    fn find_next(&self) -> Option<Range> {
+        match self.needle {
+            Needle::Str(pat) => self
+                .rest
+                .find(pat)
+                .map(|i| Range::from_start_len(i, pat.len())),
+            Needle::Char(c) => self
+                .rest
+                .find(c)
+                .map(|i| Range::from_start_len(i, c.len_utf8())),
+            Needle::IgnoreAsciiCase(pat) => self
+                .rest
+                .as_bytes()
+                .windows(pat.len())
+                .position(|w| w.eq_ignore_ascii_case(pat.as_bytes()))
+                // A byte-wise match of a whole `str` starts and ends on `char`
+                // boundaries.
+                .map(|i| Range::from_start_len(i, pat.len())),
+        }
+    }
+}
+
+impl Iterator for MatchRanges<'_> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let Some(found) = self.find_next() else {
+            self.rest = "";
+            return None;
+        };
+        let r = found.offset(self.offset);
+        self.rest = &self.rest[found.end()..];
+        self.offset = r.end();
+        Some(r)
+    }
+}
+
+impl FusedIterator for MatchRanges<'_> {}
+
+/// Returns the range of `piece`, which a `str` iterator borrowed from `parent`.
+fn piece_range(parent: &str, piece: &str) -> Range {
+    let start = piece.as_ptr().addr() - parent.as_ptr().addr();
+    Range::from_start_len(start, piece.len())
+}
+
+macro_rules! piece_ranges {
+    ($(#[$attr:meta])* $name:ident, $inner:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name<'a> {
+            parent: &'a str,
+            inner: $inner,
+        }
+
+        impl Iterator for $name<'_> {
+            type Item = Range;
+
+            fn next(&mut self) -> Option<Range> {
+                self.inner.next().map(|piece| piece_range(self.parent, piece))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl DoubleEndedIterator for $name<'_> {
+            fn next_back(&mut self) -> Option<Range> {
+                self.inner.next_back().map(|piece| piece_range(self.parent, piece))
+            }
+        }
+
+        impl FusedIterator for $name<'_> {}
+    };
+}
+
+piece_ranges!(
+    /// An iterator over the byte ranges of the whitespace-separated pieces of a
+    /// string.
+    ///
+    /// Created by [`StrExt::split_whitespace_ranges`].
+    SplitWhitespaceRanges,
+    core::str::SplitWhitespace<'a>
+);
+
+piece_ranges!(
+    /// An iterator over the byte ranges of the pieces of a string between a
+    /// delimiter.
+    ///
+    /// Created by [`StrExt::split_ranges`].
+    SplitRanges,
+    core::str::Split<'a, char>
+);
+
+/// Returns the first grapheme cluster boundary at or after `offset`, which must
+/// be at most `s.len()`.
+#[cfg(feature = "unicode")]
+fn ceil_grapheme_boundary(s: &str, offset: usize) -> usize {
+    use unicode_segmentation::GraphemeCursor;
+
+    let offset = s.ceil_char_boundary(offset);
+    let mut cursor = GraphemeCursor::new(offset, s.len(), true);
+    // The whole string is one chunk, so the cursor never asks for more context.
+    if cursor.is_boundary(s, 0).expect("whole string given") {
+        return offset;
+    }
+    cursor
+        .next_boundary(s, 0)
+        .expect("whole string given")
+        .unwrap_or(s.len())
+}
+
+/// Returns the last grapheme cluster boundary at or before `offset`, which must
+/// be at most `s.len()`.
+#[cfg(feature = "unicode")]
+fn floor_grapheme_boundary(s: &str, offset: usize) -> usize {
+    use unicode_segmentation::GraphemeCursor;
+
+    let offset = s.floor_char_boundary(offset);
+    let mut cursor = GraphemeCursor::new(offset, s.len(), true);
+    if cursor.is_boundary(s, 0).expect("whole string given") {
+        return offset;
+    }
+    cursor
+        .prev_boundary(s, 0)
+        .expect("whole string given")
+        .unwrap_or(0)
+}
+
+/// An iterator over the byte ranges of the extended grapheme clusters of a
+/// string.
+///
+/// Created by [`StrExt::grapheme_ranges`].
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone)]
+pub struct GraphemeRanges<'a> {
+    inner: unicode_segmentation::GraphemeIndices<'a>,
+}
+
+#[cfg(feature = "unicode")]
+impl Iterator for GraphemeRanges<'_> {
+    type Item = Range;
+
+    fn next(&mut self) -> Option<Range> {
+        let (start, cluster) = self.inner.next()?;
+        Some(Range::from_start_len(start, cluster.len()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl DoubleEndedIterator for GraphemeRanges<'_> {
+    fn next_back(&mut self) -> Option<Range> {
+        let (start, cluster) = self.inner.next_back()?;
+        Some(Range::from_start_len(start, cluster.len()))
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl FusedIterator for GraphemeRanges<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::vec::Vec;
+
+    // 'a' 0..1, 'é' 1..3, '€' 3..6, '𝄞' 6..10, 'b' 10..11
+    const S: &str = "aé€𝄞b";
+
+    #[test]
+    fn get_range_checks_bounds_and_boundaries() {
+        assert_eq!(S.get_range(Range(0, 11)), Some(S));
+        assert_eq!(S.get_range(Range(1, 3)), Some("é"));
+        assert_eq!(S.get_range(Range(6, 10)), Some("𝄞"));
+        assert_eq!(S.get_range(Range(11, 11)), Some(""));
+        // A multibyte character at either bound.
+        assert_eq!(S.get_range(Range(2, 6)), None);
+        assert_eq!(S.get_range(Range(3, 8)), None);
+        assert_eq!(S.get_range(Range(10, 12)), None);
+        assert_eq!(S.get_range(Range(12, 12)), None);
+        assert_eq!(S.get_range(Range(3, 1)), None);
+
+        let mut s = std::string::String::from(S);
+        s.get_range_mut(Range(1, 3)).unwrap().make_ascii_uppercase();
+        s.get_range_mut(Range(10, 11))
+            .unwrap()
+            .make_ascii_uppercase();
+        assert_eq!(s, "aé€𝄞B");
+        assert!(s.get_range_mut(Range(7, 10)).is_none());
+        assert!(s.get_range_mut(Range(0, 12)).is_none());
+    }
+
+    #[test]
+    fn try_slice_reports_why() {
+        assert_eq!(S.try_slice(Range(3, 6)), Ok("€"));
+        assert_eq!(S.try_slice(Range(0, 0)), Ok(""));
+        assert_eq!(
+            S.try_slice(Range(2, 6)),
+            Err(SliceStrError::NotCharBoundary { offset: 2 })
+        );
+        assert_eq!(
+            S.try_slice(Range(6, 9)),
+            Err(SliceStrError::NotCharBoundary { offset: 9 })
+        );
+        assert_eq!(
+            S.try_slice(Range(2, 9)),
+            Err(SliceStrError::NotCharBoundary { offset: 2 })
+        );
+        assert_eq!(
+            S.try_slice(Range(7, 12)),
+            Err(SliceStrError::OutOfBounds {
+                range: Range(7, 12),
+                len: 11
+            })
+        );
+        assert_eq!(
+            S.try_slice(Range(20, 30)),
+            Err(SliceStrError::OutOfBounds {
+                range: Range(20, 30),
+                len: 11
+            })
+        );
+        assert_eq!(
+            S.try_slice(Range(5, 2)),
+            Err(SliceStrError::Inverted(Range(5, 2)))
+        );
+        assert_eq!(
+            std::format!("{}", S.try_slice(Range(2, 6)).unwrap_err()),
+            "byte offset 2 is not a char boundary"
+        );
+
+        for start in 0..=S.len() + 1 {
+            for end in 0..=S.len() + 1 {
+                let r = Range(start, end);
+                assert_eq!(S.try_slice(r).ok(), S.get_range(r), "{r:?}");
+            }
+        }
+    }
+
+    const LINE_CORPUS: [&str; 12] = [
+        "",
+        "\n",
+        "\n\n",
+        "one",
+        "one\n",
+        "one\ntwo",
+        "one\r\ntwo\r\n",
+        "one\n\n\nfour\n",
+        "bare\rcr\r",
+        "\r\n\r\n",
+        "trailing\r",
+        "é\n€\r\n𝄞",
+    ];
+
+    #[test]
+    fn line_ranges_match_lines() {
+        for s in LINE_CORPUS {
+            let lines: Vec<&str> = s.line_ranges().map(|r| &s[r]).collect();
+            let expected: Vec<&str> = s.lines().collect();
+            assert_eq!(lines, expected, "{s:?}");
+
+            let mut back: Vec<Range> = s.line_ranges().rev().collect();
+            back.reverse();
+            assert_eq!(back, s.line_ranges().collect::<Vec<_>>(), "{s:?}");
+        }
+        let ranges: Vec<Range> = "a\n\nb\r\n".line_ranges().collect();
+        assert_eq!(ranges, [Range(0, 1), Range(2, 2), Range(3, 4)]);
+    }
+
+    #[test]
+    fn line_ranges_with_terminator_tile_the_string() {
+        for s in LINE_CORPUS {
+            let lines: Vec<Range> = s.line_ranges_with_terminator().collect();
+            let expected: Vec<&str> = s.split_inclusive('\n').collect();
+            assert_eq!(lines.iter().map(|&r| &s[r]).collect::<Vec<_>>(), expected);
+            let mut end = 0;
+            for r in &lines {
+                assert_eq!(r.start(), end, "{s:?}");
+                end = r.end();
+            }
+            assert_eq!(end, s.len());
+            assert!(
+                lines
+                    .iter()
+                    .rev()
+                    .copied()
+                    .eq(s.line_ranges_with_terminator().rev())
+            );
+        }
+        // No final newline.
+        let ranges: Vec<Range> = "a\nbc".line_ranges_with_terminator().collect();
+        assert_eq!(ranges, [Range(0, 2), Range(2, 4)]);
+    }
+
+    #[test]
+    fn line_of_every_offset() {
+        for s in LINE_CORPUS {
+            let lines: Vec<Range> = s.line_ranges_with_terminator().collect();
+            for offset in 0..=s.len() {
+                let (number, line) = s.line_of_offset(offset).unwrap();
+                match lines.get(number) {
+                    Some(full) => {
+                        assert!(full.contains(offset) || offset == s.len(), "{s:?} {offset}");
+                        assert_eq!(Some(line), s.line_ranges().nth(number));
+                    }
+                    // Past a final terminator.
+                    None => {
+                        assert_eq!(number, lines.len());
+                        assert_eq!((offset, line), (s.len(), Range(s.len(), s.len())));
+                    }
+                }
+            }
+            assert_eq!(s.line_of_offset(s.len() + 1), None);
+        }
+        let s = "ab\r\ncd";
+        assert_eq!(s.line_of_offset(2), Some((0, Range(0, 2))));
+        assert_eq!(s.line_of_offset(3), Some((0, Range(0, 2))));
+        assert_eq!(s.line_of_offset(4), Some((1, Range(4, 6))));
+        assert_eq!(s.line_of_offset(6), Some((1, Range(4, 6))));
+    }
+
+    #[test]
+    fn match_ranges_agree_with_match_indices() {
+        let cases = [
+            ("abcabc", "bc"),
+            ("aaaaa", "aa"),
+            ("éé€é", "é"),
+            ("x𝄞𝄞y𝄞", "𝄞𝄞"),
+            ("needle", "needle"),
+            ("needle", "needles"),
+            ("", "a"),
+        ];
+        for (haystack, pat) in cases {
+            let hits: Vec<&str> = haystack.match_ranges(pat).map(|r| &haystack[r]).collect();
+            let expected: Vec<&str> = haystack.matches(pat).collect();
+            assert_eq!(hits, expected, "{haystack:?} {pat:?}");
+            let starts: Vec<usize> = haystack.match_ranges(pat).map(|r| r.start()).collect();
+            let expected: Vec<usize> = haystack.match_indices(pat).map(|(i, _)| i).collect();
+            assert_eq!(starts, expected);
+        }
+        assert!("needle".match_ranges("needle").eq([Range(0, 6)]));
+        assert!("x€x€".match_ranges("€").eq([Range(1, 4), Range(5, 8)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "match pattern must be non-empty")]
+    fn empty_needle_panics() {
+        let _ = "abc".match_ranges("");
+    }
+
+    #[test]
+    #[should_panic(expected = "match pattern must be non-empty")]
+    fn empty_ignore_ascii_case_needle_panics() {
+        let _ = "abc".match_ranges_ignore_ascii_case("");
+    }
+
+    #[test]
+    fn match_ranges_char() {
+        assert!(S.match_ranges_char('𝄞').eq([Range(6, 10)]));
+        assert!(
+            "a,b,,c"
+                .match_ranges_char(',')
+                .eq([Range(1, 2), Range(3, 4), Range(4, 5)])
+        );
+        assert_eq!(S.match_ranges_char('x').next(), None);
+    }
+
+    #[test]
+    fn match_ranges_ignoring_ascii_case() {
+        let s = "Straße STRASSE strasse";
+        assert!(
+            s.match_ranges_ignore_ascii_case("strasse")
+                .eq([Range(8, 15), Range(16, 23)])
+        );
+        // Non-ASCII characters must match exactly.
+        assert!(s.match_ranges_ignore_ascii_case("STRAẞE").next().is_none());
+        assert!(s.match_ranges_ignore_ascii_case("straße").eq([Range(0, 7)]));
+        assert!("ÉéÉ".match_ranges_ignore_ascii_case("é").eq([Range(2, 4)]));
+        assert!(
+            "aAaA"
+                .match_ranges_ignore_ascii_case("AA")
+                .eq([Range(0, 2), Range(2, 4)])
+        );
+        assert!("ab".match_ranges_ignore_ascii_case("abc").next().is_none());
+    }
+
+    const SPLIT_CORPUS: [&str; 10] = [
+        "",
+        " ",
+        "word",
+        "  leading and trailing  ",
+        "a,,b,",
+        ",,,",
+        "tab\tnew\nline\r\nx",
+        "nbsp\u{a0}ideographic\u{3000}em\u{2003}space",
+        "é, 𝄞 ,€",
+        "\u{85}next\u{2028}line",
+    ];
+
+    #[test]
+    fn split_whitespace_ranges_match_split_whitespace() {
+        for s in SPLIT_CORPUS {
+            let pieces: Vec<&str> = s.split_whitespace_ranges().map(|r| &s[r]).collect();
+            let expected: Vec<&str> = s.split_whitespace().collect();
+            assert_eq!(pieces, expected, "{s:?}");
+            let back: Vec<&str> = s.split_whitespace_ranges().rev().map(|r| &s[r]).collect();
+            assert!(back.iter().eq(expected.iter().rev()));
+        }
+        assert!(
+            "\u{3000}a b\u{a0}"
+                .split_whitespace_ranges()
+                .eq([Range(3, 4), Range(5, 6)])
+        );
+    }
+
+    #[test]
+    fn split_ranges_match_split() {
+        for s in SPLIT_CORPUS {
+            for delim in [',', ' ', '𝄞', 'x'] {
+                let ranges: Vec<Range> = s.split_ranges(delim).collect();
+                let pieces: Vec<&str> = ranges.iter().map(|&r| &s[r]).collect();
+                let expected: Vec<&str> = s.split(delim).collect();
+                assert_eq!(pieces, expected, "{s:?} {delim:?}");
+                // Consecutive pieces are separated by exactly one delimiter.
+                for w in ranges.windows(2) {
+                    assert_eq!(w[1].start() - w[0].end(), delim.len_utf8());
+                }
+                assert!(ranges.iter().rev().copied().eq(s.split_ranges(delim).rev()));
+            }
+        }
+    }
+
+    const TRIM_CORPUS: [&str; 11] = [
+        "",
+        "x",
+        "   ",
+        "  padded  ",
+        "\tline\r\n",
+        "\u{a0}\u{3000}wide\u{2003}\u{2029}",
+        "inner  space",
+        "\u{85}é€𝄞\u{205f}",
+        "\u{3000}",
+        "**star**",
+        "*",
+    ];
+
+    #[test]
+    fn trim_ranges_match_trim() {
+        for s in TRIM_CORPUS {
+            assert_eq!(&s[s.trim_range()], s.trim(), "{s:?}");
+            assert_eq!(&s[s.trim_start_range()], s.trim_start(), "{s:?}");
+            assert_eq!(&s[s.trim_end_range()], s.trim_end(), "{s:?}");
+            let star = |c| c == '*';
+            assert_eq!(
+                &s[s.trim_range_matches(star)],
+                s.trim_matches(star),
+                "{s:?}"
+            );
+            // The range is where the trimmed slice lives.
+            if !s.trim().is_empty() {
+                assert_eq!(substr_range(s, s.trim()), Some(s.trim_range()));
+            }
+        }
+        assert_eq!("\u{3000}a b\u{a0}".trim_range(), Range(3, 6));
+    }
+
+    #[test]
+    fn trimming_everything_is_well_positioned() {
+        let s = " \u{3000}\t";
+        assert_eq!(s.trim_range(), Range(5, 5));
+        assert_eq!(s.trim_start_range(), Range(5, 5));
+        assert_eq!(s.trim_end_range(), Range(0, 0));
+        assert_eq!("***".trim_range_matches(|c| c == '*'), Range(3, 3));
+        assert_eq!("".trim_range(), Range(0, 0));
+        assert_eq!("".trim_start_range(), Range(0, 0));
+    }
+
+    #[test]
+    fn snap_inward_straddling_both_bounds() {
+        assert_eq!(Range(2, 8).snap_to_char_boundaries(S), Range(3, 6));
+        assert_eq!(S.slice_snapped(Range(2, 8)), "€");
+        assert_eq!(Range(1, 10).snap_to_char_boundaries(S), Range(1, 10));
+        assert_eq!(Range(0, 11).snap_to_char_boundaries(S), Range(0, 11));
+    }
+
+    #[test]
+    fn snap_inward_inside_one_char_is_empty() {
+        let r = Range(7, 9).snap_to_char_boundaries(S);
+        assert!(r.is_empty());
+        assert_eq!(r, Range(10, 10));
+        assert_eq!(S.slice_snapped(Range(7, 9)), "");
+        assert_eq!(S.slice_snapped(Range(6, 9)), "");
+        assert_eq!(S.slice_snapped(Range(7, 10)), "");
+    }
+
+    #[test]
+    fn snap_outward() {
+        assert_eq!(Range(2, 8).snap_to_char_boundaries_outward(S), Range(1, 10));
+        assert_eq!(Range(7, 9).snap_to_char_boundaries_outward(S), Range(6, 10));
+        assert_eq!(Range(3, 6).snap_to_char_boundaries_outward(S), Range(3, 6));
+        assert_eq!(Range(8, 8).snap_to_char_boundaries_outward(S), Range(6, 10));
+    }
+
+    #[test]
+    fn snap_clamps_to_len() {
+        assert_eq!(Range(9, 50).snap_to_char_boundaries(S), Range(10, 11));
+        assert_eq!(Range(20, 50).snap_to_char_boundaries(S), Range(11, 11));
+        assert_eq!(
+            Range(9, 50).snap_to_char_boundaries_outward(S),
+            Range(6, 11)
+        );
+        assert_eq!(S.slice_snapped(Range(0, usize::MAX)), S);
+        assert_eq!("".slice_snapped(Range(1, 2)), "");
+    }
+
+    #[test]
+    fn char_to_byte_range() {
+        assert_eq!(S.char_range_to_byte_range(Range(0, 0)), Some(Range(0, 0)));
+        assert_eq!(S.char_range_to_byte_range(Range(1, 2)), Some(Range(1, 3)));
+        assert_eq!(S.char_range_to_byte_range(Range(3, 4)), Some(Range(6, 10)));
+        assert_eq!(S.char_range_to_byte_range(Range(0, 5)), Some(Range(0, 11)));
+        // Ranges at the very end of the string.
+        assert_eq!(S.char_range_to_byte_range(Range(4, 5)), Some(Range(10, 11)));
+        assert_eq!(S.char_range_to_byte_range(Range(5, 5)), Some(Range(11, 11)));
+        assert_eq!(S.char_range_to_byte_range(Range(5, 6)), None);
+        assert_eq!(S.char_range_to_byte_range(Range(6, 6)), None);
+        assert_eq!(S.char_range_to_byte_range(Range(3, 2)), None);
+        assert_eq!("".char_range_to_byte_range(Range(0, 0)), Some(Range(0, 0)));
+        assert_eq!("𝄞".char_range_to_byte_range(Range(0, 1)), Some(Range(0, 4)));
+    }
+
+    #[test]
+    fn byte_to_char_range() {
+        assert_eq!(S.byte_range_to_char_range(Range(0, 0)), Some(Range(0, 0)));
+        assert_eq!(S.byte_range_to_char_range(Range(1, 3)), Some(Range(1, 2)));
+        assert_eq!(S.byte_range_to_char_range(Range(6, 10)), Some(Range(3, 4)));
+        assert_eq!(S.byte_range_to_char_range(Range(0, 11)), Some(Range(0, 5)));
+        assert_eq!(S.byte_range_to_char_range(Range(11, 11)), Some(Range(5, 5)));
+        // Inside the astral-plane character.
+        assert_eq!(S.byte_range_to_char_range(Range(7, 10)), None);
+        assert_eq!(S.byte_range_to_char_range(Range(6, 9)), None);
+        assert_eq!(S.byte_range_to_char_range(Range(10, 12)), None);
+        assert_eq!(S.byte_range_to_char_range(Range(3, 1)), None);
+    }
+
+    #[test]
+    fn char_byte_conversions_round_trip() {
+        let count = S.chars().count();
+        for start in 0..=count {
+            for end in start..=count {
+                let chars = Range(start, end);
+                let bytes = S.char_range_to_byte_range(chars).unwrap();
+                assert_eq!(S.byte_range_to_char_range(bytes), Some(chars));
+            }
+        }
+    }
+
+    #[test]
+    fn substr_range_of_derived_substrings() {
+        let s = "α,βγ,,δ";
+        let ranges: [Option<Range>; 4] = {
+            let mut parts = s.split(',');
+            core::array::from_fn(|_| substr_range(s, parts.next().unwrap()))
+        };
+        assert_eq!(
+            ranges,
+            [
+                Some(Range(0, 2)),
+                Some(Range(3, 7)),
+                Some(Range(8, 8)),
+                Some(Range(9, 11))
+            ]
+        );
+        for r in ranges.into_iter().flatten() {
+            assert!(s.is_char_boundary(r.start()) && s.is_char_boundary(r.end()));
+        }
+        assert_eq!(substr_range(s, &s[s.len()..]), Some(Range(11, 11)));
+        assert_eq!(substr_range(&s[..3], &s[3..7]), None);
+        assert_eq!(substr_range(s, "βγ"), None);
+    }
+
+    #[test]
+    fn snapped_ranges_never_panic_when_slicing() {
+        for start in 0..=S.len() + 1 {
+            for end in start..=S.len() + 1 {
+                let r = Range(start, end);
+                let _ = &S[r.snap_to_char_boundaries(S)];
+                let _ = &S[r.snap_to_char_boundaries_outward(S)];
+            }
+        }
+    }
+
+    #[cfg(feature = "unicode")]
+    mod graphemes {
+        use super::*;
+
+        // Combining accents, a ZWJ family, a skin-tone modifier and two flags.
+        const CLUSTERS: [&str; 8] = [
+            "e\u{301}",
+            "a",
+            "o\u{308}\u{304}",
+            "👨\u{200d}👩\u{200d}👧",
+            "👍🏽",
+            "🇯🇵",
+            "🇫🇷",
+            "\r\n",
+        ];
+
+        fn text() -> std::string::String {
+            CLUSTERS.concat()
+        }
+
+        #[test]
+        fn grapheme_ranges_yield_each_cluster() {
+            let s = text();
+            let clusters: Vec<&str> = s.grapheme_ranges().map(|r| &s.as_str()[r]).collect();
+            assert_eq!(clusters, CLUSTERS);
+            let mut back: Vec<Range> = s.grapheme_ranges().rev().collect();
+            back.reverse();
+            assert!(back.into_iter().eq(s.grapheme_ranges()));
+            assert_eq!("".grapheme_ranges().next(), None);
+        }
+
+        #[test]
+        fn snapping_never_splits_a_cluster() {
+            let s = text();
+            let boundaries: Vec<usize> = s
+                .grapheme_ranges()
+                .map(|r| r.start())
+                .chain([s.len()])
+                .collect();
+            for start in 0..=s.len() + 2 {
+                for end in start..=s.len() + 2 {
+                    let r = Range(start, end);
+                    let snapped = s.snap_to_grapheme_boundaries(r);
+                    assert!(boundaries.contains(&snapped.start()), "{r:?}");
+                    assert!(boundaries.contains(&snapped.end()), "{r:?}");
+                    let _ = &s.as_str()[snapped];
+                    if !snapped.is_empty() {
+                        // Snapping only ever shrinks the range.
+                        assert!(start <= snapped.start() && snapped.end() <= end);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn snapping_examples() {
+            let family = "👨\u{200d}👩\u{200d}👧";
+            let s = std::format!("a{family}b");
+            let len = family.len();
+            // Anything short of the whole family drops it.
+            assert_eq!(s.snap_to_grapheme_boundaries(Range(0, len)), Range(0, 1));
+            assert_eq!(
+                s.snap_to_grapheme_boundaries(Range(2, len + 2)),
+                Range(len + 1, len + 2)
+            );
+            assert_eq!(
+                s.snap_to_grapheme_boundaries(Range(1, len + 1)),
+                Range(1, len + 1)
+            );
+            // Inside one cluster.
+            assert_eq!(
+                s.snap_to_grapheme_boundaries(Range(5, 9)),
+                Range(len + 1, len + 1)
+            );
+            // Between the regional indicators of a flag.
+            assert_eq!("🇯🇵".snap_to_grapheme_boundaries(Range(4, 8)), Range(8, 8));
+            assert_eq!("🇯🇵".snap_to_grapheme_boundaries(Range(0, 4)), Range(0, 0));
+            assert_eq!(
+                s.snap_to_grapheme_boundaries(Range(0, usize::MAX)),
+                Range(0, s.len())
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! `Vec` utilities built on [`Range`].
+
+use alloc::vec::Vec;
+
+use crate::range::{Range, coalesce_iter};
+
+/// Extension methods for `Vec` that take [`Range`]s.
+pub trait VecExt<T> {
+    /// Removes the elements in every range of `ranges` in a single pass, returning
+    /// the new length.
+    ///
+    /// All ranges index the vector as it was before the call; they may be in any
+    /// order and may overlap. The kept elements are shifted down in order, so this
+    /// runs in O(n + k log k) for `k` ranges instead of draining each range in
+    /// turn.
+    ///
+    /// # Panics
+    /// Panics if a range is inverted or extends past the end of the vector, like
+    /// [`Vec::drain`]. The vector is left unchanged in that case.
+    ///
+    /// # Example
+    /// ```
+    /// use ars::prelude::*;
+    /// use ars::range::Range;
+    /// let mut v: Vec<u32> = (0..10).collect();
+    /// let len = v.drain_ranges(&[Range(7, 9), Range(1, 3), Range(2, 4)]);
+    /// assert_eq!(len, 5);
+    /// assert_eq!(v, [0, 4, 5, 6, 9]);
+    /// ```
+    fn drain_ranges(&mut self, ranges: &[Range]) -> usize;
+}
+
+impl<T> VecExt<T> for Vec<T> {
+    #[track_caller]
+    fn drain_ranges(&mut self, ranges: &[Range]) -> usize {
+        let len = self.len();
+        for &r in ranges {
+            assert!(
+                r.start() <= r.end(),
+                "range start {} is greater than end {}",
+                r.start(),
+                r.end()
+            );
+            assert!(
+                r.end() <= len,
+                "range {r} out of bounds for vector of length {len}"
+            );
+        }
+        let removed = coalesce_iter(ranges.iter().copied());
+        let mut removed = removed.iter().peekable();
+        let mut index = 0;
+        self.retain(|_| {
+            while removed.next_if(|r| r.end() <= index).is_some() {}
+            let keep = removed.peek().is_none_or(|r| index < r.start());
+            index += 1;
+            keep
+        });
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate std;
+    use std::string::{String, ToString};
+    use std::vec;
+
+    #[test]
+    fn overlapping_and_unsorted_ranges() {
+        let mut v: Vec<u32> = (0..12).collect();
+        assert_eq!(
+            v.drain_ranges(&[
+                Range(10, 12),
+                Range(0, 1),
+                Range(3, 6),
+                Range(4, 8),
+                Range(5, 5)
+            ]),
+            4
+        );
+        assert_eq!(v, [1, 2, 8, 9]);
+
+        let mut v = vec![1, 2, 3];
+        assert_eq!(v.drain_ranges(&[]), 3);
+        assert_eq!(v.drain_ranges(&[Range(0, 3), Range(1, 2)]), 0);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn drops_removed_elements() {
+        let mut v: Vec<String> = ["a", "b", "c", "d"].map(ToString::to_string).to_vec();
+        v.drain_ranges(&[Range(1, 2), Range(3, 4)]);
+        assert_eq!(v, ["a", "c"]);
+    }
+
+    #[test]
+    #[should_panic = "range 2..6 out of bounds for vector of length 5"]
+    fn out_of_bounds_panics() {
+        vec![0; 5].drain_ranges(&[Range(0, 1), Range(2, 6)]);
+    }
+
+    #[test]
+    fn randomized_against_repeated_drain() {
+        use rand::rngs::StdRng;
+        use rand::{RngExt, SeedableRng};
+
+        for seed in 0..300 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let len = rng.random_range(0..60);
+            let original: Vec<usize> = (0..len).collect();
+            let ranges: Vec<Range> = (0..rng.random_range(0..8))
+                .map(|_| {
+                    let start = rng.random_range(0..=len);
+                    Range(start, rng.random_range(start..=len))
+                })
+                .collect();
+
+            // Naive model: drain each removed index from the back.
+            let mut expected = original.clone();
+            let mut doomed: Vec<usize> = ranges.iter().flat_map(Range::iter).collect();
+            doomed.sort_unstable();
+            doomed.dedup();
+            for &i in doomed.iter().rev() {
+                expected.drain(i..=i);
+            }
+
+            let mut v = original;
+            assert_eq!(v.drain_ranges(&ranges), expected.len());
+            assert_eq!(v, expected, "seed {seed}");
+        }
+    }
+}